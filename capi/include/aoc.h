@@ -0,0 +1,44 @@
+/* The C API of the Advent of Code 2023 solvers, from libaoc.so or libaoc.a.
+ *
+ * Generated from capi/src/lib.rs by `UPDATE_HEADER=1 cargo test -p aoc2023rs-capi`; do not edit.
+ */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The error codes returned by aoc_solve. */
+#define AOC_OK          0  /* success */
+#define AOC_ERROR_DAY   1  /* the day is not between 1 and 25 */
+#define AOC_ERROR_PART  2  /* day 25 has no part 2 */
+#define AOC_ERROR_NULL  3  /* the input is NULL but not empty, or both outputs are NULL */
+#define AOC_ERROR_UTF8  4  /* the input is not UTF-8 */
+#define AOC_ERROR_PARSE 5  /* the input is malformed */
+#define AOC_ERROR_RANGE 6  /* an answer does not fit in an int64_t */
+
+/* Solves the puzzle for the given day (1-25) with the input_len bytes at input, which is the
+ * puzzle input as UTF-8 text. input may be NULL if input_len is 0.
+ *
+ * The answer to each part is stored through the corresponding output pointer. Either output may
+ * be NULL to skip that part, which saves the work that only that part needs. Day 25 has no part 2,
+ * so its out_part2 is set to 0, or if out_part1 is NULL, AOC_ERROR_PART is returned. Nothing is
+ * stored unless the result is AOC_OK.
+ *
+ * The solvers keep no state between calls, so aoc_solve may be called from several threads at
+ * once. */
+int32_t aoc_solve(uint32_t day, const uint8_t *input, size_t input_len,
+                  int64_t *out_part1, int64_t *out_part2);
+
+/* Returns a static, NUL-terminated description of an error code. */
+const char *aoc_strerror(int32_t code);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
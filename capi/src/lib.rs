@@ -0,0 +1,126 @@
+use aoc2023rs::{Answer, Part, NUM_DAYS};
+use std::ffi::{c_char, CStr};
+use std::slice;
+use std::str;
+
+// A C API for the solvers, so that tools written in other languages can solve a puzzle for an input
+// they hold in memory. The API is declared in `include/aoc.h`, which is generated by `header` and
+// kept up to date by the tests.
+
+// The error codes returned by `aoc_solve`. Their values are part of the API, so they must never
+// change, and new codes must be added at the end.
+pub const AOC_OK: i32 = 0;
+pub const AOC_ERROR_DAY: i32 = 1;
+pub const AOC_ERROR_PART: i32 = 2;
+pub const AOC_ERROR_NULL: i32 = 3;
+pub const AOC_ERROR_UTF8: i32 = 4;
+pub const AOC_ERROR_PARSE: i32 = 5;
+pub const AOC_ERROR_RANGE: i32 = 6;
+
+// Each error code, with its name in the header and the description returned by `aoc_strerror`.
+const ERRORS: [(i32, &str, &CStr); 7] = [
+    (AOC_OK, "AOC_OK", c"success"),
+    (AOC_ERROR_DAY, "AOC_ERROR_DAY", c"the day is not between 1 and 25"),
+    (AOC_ERROR_PART, "AOC_ERROR_PART", c"day 25 has no part 2"),
+    (AOC_ERROR_NULL, "AOC_ERROR_NULL",
+     c"the input is NULL but not empty, or both outputs are NULL"),
+    (AOC_ERROR_UTF8, "AOC_ERROR_UTF8", c"the input is not UTF-8"),
+    (AOC_ERROR_PARSE, "AOC_ERROR_PARSE", c"the input is malformed"),
+    (AOC_ERROR_RANGE, "AOC_ERROR_RANGE", c"an answer does not fit in an int64_t"),
+];
+
+// Solves the puzzle for `day` with the `input_len` bytes at `input`, storing the answer to each
+// part through the corresponding output pointer. An output may be NULL to skip that part, and
+// nothing is stored unless the result is `AOC_OK`.
+//
+// Safety: `input` must point to `input_len` readable bytes (or may be NULL if `input_len` is 0),
+// and each output must be NULL or point to a writable `int64_t`.
+#[no_mangle]
+pub unsafe extern "C" fn aoc_solve(
+    day: u32,
+    input: *const u8,
+    input_len: usize,
+    out_part1: *mut i64,
+    out_part2: *mut i64,
+) -> i32 {
+    let Ok(day @ 1..=NUM_DAYS) = u8::try_from(day) else { return AOC_ERROR_DAY };
+    let part = match (out_part1.is_null(), out_part2.is_null()) {
+        (false, false) => None,
+        (false, true) => Some(Part::One),
+        (true, false) => Some(Part::Two),
+        (true, true) => return AOC_ERROR_NULL,
+    };
+    if part == Some(Part::Two) && !aoc2023rs::has_part(day, Part::Two) { return AOC_ERROR_PART }
+    let input: &[u8] = if input_len == 0 {
+        &[]
+    } else if input.is_null() {
+        return AOC_ERROR_NULL;
+    } else {
+        unsafe { slice::from_raw_parts(input, input_len) }
+    };
+    let Ok(input) = str::from_utf8(input) else { return AOC_ERROR_UTF8 };
+    let Ok(solution) = aoc2023rs::solve_parts(day, part, input) else { return AOC_ERROR_PARSE };
+    // Day 25 has no part 2, so its answer is stored as 0.
+    let narrow = |answer: Option<Answer>| answer.map_or(Ok(0), i64::try_from);
+    let (Ok(part1), Ok(part2)) = (narrow(solution.part1), narrow(solution.part2)) else {
+        return AOC_ERROR_RANGE;
+    };
+    if !out_part1.is_null() { unsafe { *out_part1 = part1 } }
+    if !out_part2.is_null() { unsafe { *out_part2 = part2 } }
+    return AOC_OK;
+}
+
+// Returns a static, NUL-terminated description of an error code.
+#[no_mangle]
+pub extern "C" fn aoc_strerror(code: i32) -> *const c_char {
+    let description = ERRORS.iter().find(|(c, _, _)| *c == code).map(|(_, _, d)| *d);
+    return description.unwrap_or(c"unknown error code").as_ptr();
+}
+
+// Returns the text of `include/aoc.h`.
+pub fn header() -> String {
+    let mut codes = String::new();
+    for (code, name, description) in ERRORS {
+        let description = description.to_str().unwrap();
+        codes.push_str(&format!("#define {:<16}{}  /* {} */\n", name, code, description));
+    }
+    return format!("\
+/* The C API of the Advent of Code 2023 solvers, from libaoc.so or libaoc.a.
+ *
+ * Generated from capi/src/lib.rs by `UPDATE_HEADER=1 cargo test -p aoc2023rs-capi`; do not edit.
+ */
+#ifndef AOC_H
+#define AOC_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern \"C\" {{
+#endif
+
+/* The error codes returned by aoc_solve. */
+{codes}
+/* Solves the puzzle for the given day (1-25) with the input_len bytes at input, which is the
+ * puzzle input as UTF-8 text. input may be NULL if input_len is 0.
+ *
+ * The answer to each part is stored through the corresponding output pointer. Either output may
+ * be NULL to skip that part, which saves the work that only that part needs. Day 25 has no part 2,
+ * so its out_part2 is set to 0, or if out_part1 is NULL, AOC_ERROR_PART is returned. Nothing is
+ * stored unless the result is AOC_OK.
+ *
+ * The solvers keep no state between calls, so aoc_solve may be called from several threads at
+ * once. */
+int32_t aoc_solve(uint32_t day, const uint8_t *input, size_t input_len,
+                  int64_t *out_part1, int64_t *out_part2);
+
+/* Returns a static, NUL-terminated description of an error code. */
+const char *aoc_strerror(int32_t code);
+
+#ifdef __cplusplus
+}}
+#endif
+
+#endif
+");
+}
@@ -0,0 +1,72 @@
+// Tests for the C API: the checked-in header must match the one generated from the code, and a C
+// program built against the header and the static library must pass its checks.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+fn capi_dir() -> &'static Path {
+    return Path::new(env!("CARGO_MANIFEST_DIR"));
+}
+
+// The directory containing the libraries that cargo built, such as `target/debug`. Integration
+// tests are built in its `deps` directory.
+fn target_dir() -> PathBuf {
+    let exe = env::current_exe().unwrap();
+    return exe.parent().unwrap().parent().unwrap().to_path_buf();
+}
+
+#[test]
+fn header_is_up_to_date() {
+    let path = capi_dir().join("include/aoc.h");
+    let expected = aoc::header();
+    if env::var_os("UPDATE_HEADER").is_some() {
+        fs::write(&path, &expected).unwrap();
+    }
+    let actual = fs::read_to_string(&path).unwrap_or_default();
+    assert!(actual == expected,
+            "{} is out of date; run `UPDATE_HEADER=1 cargo test -p aoc2023rs-capi` to update it",
+            path.display());
+}
+
+#[test]
+fn c_program() {
+    // Tests only need the library as an rlib, so the static library has to be built separately.
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo.args(["build", "--lib", "--package", "aoc2023rs-capi"]).current_dir(capi_dir());
+    if target_dir().ends_with("release") { cargo.arg("--release"); }
+    assert!(cargo.status().unwrap().success(), "can't build the static library");
+    let library = target_dir().join("libaoc.a");
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("solve");
+    let cc = env::var("CC").unwrap_or("cc".to_string());
+    let compiled = Command::new(&cc)
+        .arg("-Wall")
+        .arg("-Werror")
+        .arg("-I").arg(capi_dir().join("include"))
+        .arg(capi_dir().join("tests/solve.c"))
+        .arg(&library)
+        .args(["-lpthread", "-ldl", "-lm"])
+        .arg("-o").arg(&program)
+        .status();
+    // Like the puzzle inputs, a C compiler is optional, so without one the test is skipped.
+    let Ok(compiled) = compiled else {
+        eprintln!("skipped (no C compiler: {})", cc);
+        return;
+    };
+    assert!(compiled.success(), "can't compile tests/solve.c against {}", library.display());
+    let output = Command::new(&program).output().unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success(), "{}", stdout);
+    assert_eq!(stdout, "ok\n");
+}
+
+#[test]
+fn solve_from_rust() {
+    let input = b"rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+    let (mut part1, mut part2) = (0, 0);
+    let result = unsafe { aoc::aoc_solve(15, input.as_ptr(), input.len(), &mut part1, &mut part2) };
+    assert_eq!((result, part1, part2), (aoc::AOC_OK, 1320, 145));
+    let empty = unsafe { aoc::aoc_solve(15, std::ptr::null(), 0, &mut part1, &mut part2) };
+    assert_eq!(empty, aoc::AOC_ERROR_PARSE);
+}
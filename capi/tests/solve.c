@@ -0,0 +1,65 @@
+/* Exercises the C API through include/aoc.h, as a C caller would. Prints each failed check and
+ * exits with a nonzero status if there were any. */
+#include <stdio.h>
+#include <string.h>
+
+#include "aoc.h"
+
+static int failures = 0;
+
+static void check(int ok, const char *what) {
+    if (!ok) {
+        printf("FAILED: %s\n", what);
+        failures++;
+    }
+}
+
+static int32_t solve(uint32_t day, const char *input, int64_t *part1, int64_t *part2) {
+    return aoc_solve(day, (const uint8_t *)input, strlen(input), part1, part2);
+}
+
+int main(void) {
+    const char *day15 = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+    const char *day25 =
+        "jqt: rhn xhk nvd\n"
+        "rsh: frs pzl lsr\n"
+        "xhk: hfx\n"
+        "cmg: qnr nvd lhk bvb\n"
+        "rhn: xhk bvb hfx\n"
+        "bvb: xhk hfx\n"
+        "pzl: lsr hfx nvd\n"
+        "qnr: nvd\n"
+        "ntq: jqt hfx bvb xhk\n"
+        "nvd: lhk\n"
+        "lsr: lhk\n"
+        "rzs: qnr cmg lsr rsh\n"
+        "frs: qnr lhk lsr\n";
+    int64_t part1 = -1, part2 = -1;
+
+    check(solve(15, day15, &part1, &part2) == AOC_OK, "day 15 is solved");
+    check(part1 == 1320 && part2 == 145, "day 15 has the example's answers");
+
+    part1 = -1;
+    check(solve(15, day15, NULL, &part2) == AOC_OK, "part 2 is solved on its own");
+    check(part1 == -1 && part2 == 145, "only part 2 is stored");
+
+    check(solve(25, day25, &part1, &part2) == AOC_OK, "day 25 is solved");
+    check(part1 == 54 && part2 == 0, "day 25 has the example's answer and no part 2");
+    check(solve(25, day25, NULL, &part2) == AOC_ERROR_PART, "day 25 has no part 2");
+
+    part1 = -1;
+    check(solve(0, day15, &part1, &part2) == AOC_ERROR_DAY, "day 0 doesn't exist");
+    check(solve(26, day15, &part1, &part2) == AOC_ERROR_DAY, "day 26 doesn't exist");
+    check(solve(15, day15, NULL, NULL) == AOC_ERROR_NULL, "an output is needed");
+    check(aoc_solve(15, NULL, 1, &part1, &part2) == AOC_ERROR_NULL, "a NULL input is empty");
+    check(solve(15, "rn=\xff\n", &part1, &part2) == AOC_ERROR_UTF8, "the input must be UTF-8");
+    check(solve(15, "rn=x\n", &part1, &part2) == AOC_ERROR_PARSE, "the input must parse");
+    check(part1 == -1, "nothing is stored after an error");
+
+    check(strcmp(aoc_strerror(AOC_ERROR_PARSE), "the input is malformed") == 0,
+          "errors are described");
+    check(strcmp(aoc_strerror(-1), "unknown error code") == 0, "unknown codes are described");
+
+    if (failures == 0) printf("ok\n");
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,39 @@
+// Helpers shared by the fuzz targets, which are each built as a binary that includes this file.
+// Not every target needs all of them.
+#![allow(dead_code)]
+
+use aoc2023rs::Part;
+use std::borrow::Cow;
+
+// An input from the fuzzer, together with its text for sizing a day's buffers.
+pub struct Input<'a> {
+    // The input as text, with anything that isn't UTF-8 replaced.
+    pub text: Cow<'a, str>,
+    utf8: bool,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(bytes: &'a [u8]) -> Input<'a> {
+        let text = String::from_utf8_lossy(bytes);
+        let utf8 = matches!(text, Cow::Borrowed(_));
+        return Input { text, utf8 };
+    }
+
+    // Returns `len` if the input is UTF-8, and otherwise half of it, so that buffers which are too
+    // small for the input get exercised as well.
+    pub fn size(&self, len: usize) -> usize {
+        if self.utf8 { return len; }
+        return len / 2;
+    }
+}
+
+// Runs a day through `solve` and `solve_part` too, if the input is UTF-8.
+pub fn solve_str(day: u8, bytes: &[u8]) {
+    let Ok(input) = std::str::from_utf8(bytes) else { return };
+    let _ = aoc2023rs::solve(day, input);
+    for part in [Part::One, Part::Two] {
+        if aoc2023rs::has_part(day, part) {
+            let _ = aoc2023rs::solve_part(day, part, input);
+        }
+    }
+}
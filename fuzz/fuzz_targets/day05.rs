@@ -0,0 +1,14 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day05;
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let len = input.size(day05::buffer_len(&input.text));
+    let _ = day05::solve_bytes(bytes, &mut vec![(0, 0); len], &mut vec![(0, 0); len]);
+    common::solve_str(5, bytes);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day06;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let _ = day06::solve_bytes(bytes);
+    common::solve_str(6, bytes);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day07::{self, Record};
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let len = input.size(input.text.lines().count());
+    let _ = day07::solve_bytes(bytes, &mut vec![Record::default(); len]);
+    common::solve_str(7, bytes);
+});
@@ -0,0 +1,19 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day08;
+use aoc2023rs::graph;
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let (names_len, edges_len) = day08::buffer_lens(bytes);
+    let (names_len, edges_len) = (input.size(names_len), input.size(edges_len));
+    let (offsets_len, adjacent_len) = graph::buffer_lens(names_len, edges_len, true);
+    let (mut names, mut edges) = (vec![&bytes[..0]; names_len], vec![(0, 0, 0); edges_len]);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    let _ = day08::solve_bytes(bytes, &mut names, &mut edges, &mut offsets, &mut adjacent);
+    common::solve_str(8, bytes);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day09;
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let len = input.size(day09::buffer_len(&input.text));
+    let _ = day09::solve_bytes(bytes, &mut vec![0; len]);
+    common::solve_str(9, bytes);
+});
@@ -0,0 +1,13 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day10;
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let _ = day10::solve_bytes(bytes, &mut vec![0; input.size(bytes.len())]);
+    common::solve_str(10, bytes);
+});
@@ -0,0 +1,11 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day11;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let _ = day11::solve_bytes(bytes);
+    common::solve_str(11, bytes);
+});
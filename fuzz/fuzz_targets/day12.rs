@@ -0,0 +1,15 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day12;
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let (row_len, table_len) = day12::buffer_lens(&input.text);
+    let (row_len, table_len) = (input.size(row_len), input.size(table_len));
+    let _ = day12::solve_bytes(bytes, &mut vec![0; row_len], &mut vec![0; table_len]);
+    common::solve_str(12, bytes);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day14;
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let len = day14::read_input(&input.text).map_or(0, |grid| day14::buffer_len(&grid));
+    let _ = day14::solve_bytes(bytes, &mut vec![0; input.size(len)]);
+    common::solve_str(14, bytes);
+});
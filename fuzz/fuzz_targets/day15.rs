@@ -0,0 +1,14 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day15::{self, Lens};
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let len = input.size(day15::buffer_len(&input.text));
+    let _ = day15::solve_bytes(bytes, &mut vec![Lens::default(); len]);
+    common::solve_str(15, bytes);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day16;
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let len = day16::read_input(&input.text).map_or(0, |grid| day16::buffer_len(&grid));
+    let _ = day16::solve_bytes(bytes, &mut vec![0; input.size(len)]);
+    common::solve_str(16, bytes);
+});
@@ -0,0 +1,16 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day17::{self, Slot};
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let lens = day17::read_input(&input.text).map_or((0, 0), |grid| day17::buffer_lens(&grid));
+    let (cells, slots) = (input.size(lens.0), input.size(lens.1));
+    let (mut cells, mut slots) = (vec![0; cells], vec![Slot::default(); slots]);
+    let _ = day17::solve_bytes(bytes, &mut cells, &mut slots);
+    common::solve_str(17, bytes);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day19::{self, Action, Op};
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let len = input.size(day19::buffer_len(&input.text));
+    let _ = day19::solve_bytes(bytes, &mut vec![Op::Unconditionally(Action::Reject()); len]);
+    common::solve_str(19, bytes);
+});
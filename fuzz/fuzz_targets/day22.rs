@@ -0,0 +1,17 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day22::{self, Brick};
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let (bricks_len, contacts_len, flags_len) = day22::buffer_lens(&input.text);
+    let mut bricks = vec![Brick::default(); input.size(bricks_len)];
+    let mut contacts = vec![(0, 0); input.size(contacts_len)];
+    let mut flags = vec![false; input.size(flags_len)];
+    let _ = day22::solve_bytes(bytes, &mut bricks, &mut contacts, &mut flags);
+    common::solve_str(22, bytes);
+});
@@ -0,0 +1,30 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day23;
+use aoc2023rs::graph;
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let (cells, n) = day23::buffer_lens(bytes);
+    let (cells, n) = (input.size(cells), input.size(n));
+    let (mut nodes, mut edges) = (vec![0; cells], vec![(0, 0, 0, 0); 2 * n]);
+    let (mut rows, mut path) = (vec![[(0, 0); 4]; 2 * n], vec![(0, 0, 0); n]);
+    let mut visited = vec![0; n.div_ceil(64)];
+    let (offsets_len, adjacent_len) = graph::buffer_lens(n, 2 * n, false);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    let _ = day23::solve_bytes(
+        bytes,
+        &mut nodes,
+        &mut edges,
+        &mut rows,
+        &mut path,
+        &mut visited,
+        &mut offsets,
+        &mut adjacent,
+    );
+    common::solve_str(23, bytes);
+});
@@ -0,0 +1,14 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day24::{self, Hailstone};
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let len = input.size(day24::buffer_len(&input.text));
+    let _ = day24::solve_bytes(bytes, &mut vec![Hailstone::default(); len]);
+    common::solve_str(24, bytes);
+});
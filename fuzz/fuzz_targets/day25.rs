@@ -0,0 +1,22 @@
+#![no_main]
+
+mod common;
+
+use aoc2023rs::day25;
+use common::Input;
+use libfuzzer_sys::fuzz_target;
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+fuzz_target!(|bytes: &[u8]| {
+    let input = Input::new(bytes);
+    let (names_len, edges_len, scratch_len, adjacent_len) = day25::buffer_lens(&input.text);
+    let mut names = vec![&bytes[..0]; input.size(names_len)];
+    let mut edges = vec![(0, 0); input.size(edges_len)];
+    let mut scratch = vec![0; input.size(scratch_len)];
+    let mut adjacent = vec![(0, 0); input.size(adjacent_len)];
+    // A fixed seed keeps each input's run reproducible.
+    let rng = &mut ChaCha8Rng::seed_from_u64(0);
+    let _ = day25::solve_bytes(bytes, rng, &mut names, &mut edges, &mut scratch, &mut adjacent);
+    common::solve_str(25, bytes);
+});
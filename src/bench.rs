@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+// Time spent running the function before any measurements are taken.
+const WARMUP: Duration = Duration::from_millis(100);
+// Time spent taking measurements, subject to a minimum number of runs.
+const BUDGET: Duration = Duration::from_millis(500);
+const MIN_RUNS: usize = 5;
+
+// Summary statistics for a set of timing samples.
+pub struct Stats {
+    pub runs: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+    // The number of samples outside the Tukey fences (1.5 times the interquartile range below the
+    // first quartile or above the third quartile).
+    pub outliers: usize,
+}
+
+impl Stats {
+    pub fn new(samples: &[Duration]) -> Stats {
+        let n = samples.len();
+        if n == 0 { panic!("no samples") }
+        let mut us: Vec<f64> = samples.iter().map(|d| d.as_secs_f64() * 1e6).collect();
+        us.sort_by(f64::total_cmp);
+        let mean = us.iter().sum::<f64>() / n as f64;
+        let variance = if n > 1 {
+            us.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        let (q1, q3) = (us[n / 4], us[3 * n / 4]);
+        let (low, high) = (q1 - 1.5 * (q3 - q1), q3 + 1.5 * (q3 - q1));
+        return Stats{
+            runs: n,
+            mean: Duration::from_secs_f64(mean / 1e6),
+            median: Duration::from_secs_f64(us[n / 2] / 1e6),
+            stddev: Duration::from_secs_f64(variance.sqrt() / 1e6),
+            outliers: us.iter().filter(|x| **x < low || high < **x).count(),
+        };
+    }
+}
+
+// Repeatedly runs `f` and measures how long it takes. `f` is run for a short warmup period first,
+// and then as many times as fit in the measurement budget.
+pub fn measure(mut f: impl FnMut()) -> Stats {
+    let start = Instant::now();
+    loop {
+        f();
+        if start.elapsed() >= WARMUP { break }
+    }
+    let mut samples = Vec::new();
+    let start = Instant::now();
+    while samples.len() < MIN_RUNS || start.elapsed() < BUDGET {
+        let t = Instant::now();
+        f();
+        samples.push(t.elapsed());
+    }
+    return Stats::new(&samples);
+}
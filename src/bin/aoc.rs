@@ -0,0 +1,668 @@
+use aoc2023rs::{bench, cli, gen, input, registry, render, Part};
+use aoc2023rs::cli::Format;
+use aoc2023rs::error::ParseError;
+use aoc2023rs::phases::{self, Phase};
+use aoc2023rs::registry::{Recorded, Registry};
+use aoc2023rs::render::{Frames, ImageFormat};
+use aoc2023rs::{Answer, Solution};
+use std::env;
+use std::fs;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::thread;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+usage: aoc run <days> [--inputs <dir>] [--part 1|2] [--format text|json]
+               [--explain] [--phases] [--registry <file>] [<file>...]
+       aoc bench <days> [--inputs <dir>] [--part 1|2] [--readme] [--phases]
+                 [<file>...]
+       aoc check <days> [--inputs <dir>] [--registry <file>] [<file>...]
+       aoc record <days> [--inputs <dir>] [--registry <file>] [<file>...]
+       aoc draw <days> [--inputs <dir>] [--out <dir>] [--format png|ppm]
+                [--scale <n>] [<file>...]
+       aoc gen <day> [--seed <n>] [--params] [<name>=<value>...]
+       aoc lint <day> <file>...
+       aoc graph <day> [--inputs <dir>] [<file>...]
+
+  <days> is a single day (`17`), an inclusive range (`5..9`), `all`, or a
+  comma-separated list of these. The input for each day is read from
+  `<dir>/dayNN.input`, where `<dir>` defaults to `inputs`. Alternatively, a
+  single day can be given any number of input files, which are solved in
+  turn, with the output for each one labelled with its name. With `--part`,
+  only the given part of each day is solved (day 25 has no part 2, so it is
+  skipped).
+
+  The registry (default `answers.txt`) holds the verified answers for every
+  input anyone has recorded, keyed by the SHA-256 of the input. Whenever a
+  day's input is in the registry, `run` and `check` compare the answers with
+  it and report any difference.
+
+commands:
+  run    Solve each day and print its answers. With `--format json`, print
+         one JSON object per line instead, including the time taken. With
+         `--explain`, describe the intermediate results on stderr.
+  bench  Time each day's solver and print statistics. With `--readme`, print
+         the table in the format used by README.md.
+
+         With `--phases`, `run` and `bench` also time each phase of the
+         solvers, such as parsing and each part, and finish with a table of
+         the time spent in each phase. For `bench`, this is the mean over all
+         runs, including the warmup, and the extra timing slows the solvers
+         down slightly.
+  check  Solve every day at once, each on its own thread, and print a table
+         of the answers, the time taken, and whether they match those in
+         the registry, or with `<dir>/dayNN.output` for inputs that aren't
+         in the registry.
+  record Solve each day and add its input's hash and answers to the
+         registry. An input which is already recorded with different answers
+         is left alone and reported.
+  draw   Draw pictures of how the grid puzzles (days 10, 14, 16, 17, 21, and
+         23) are solved. Each day writes one or more frames to
+         `<out>/dayNN/frameNNNN.png`, where `<out>` defaults to `frames`, or
+         to `<out>/dayNN/<file>/frameNNNN.png` for an input file.
+         Each cell is drawn as a square of `<n>` pixels (default 4).
+  gen    Print a random input for a single day. The same seed (default 0)
+         always gives the same input. Each day has its own size parameters,
+         such as `rows=100`, and `--params` lists them with their defaults.
+  lint   Check whether an input has the structure that a day's solver relies
+         on beyond what the puzzle promises, such as the clear lanes through
+         the map on day 21, and list each assumption that the input breaks.
+         Days 3, 8, 20, 21, and 23 make such assumptions.
+  graph  Print the network in a single day's input (days 8, 20, 23, and 25)
+         in Graphviz's DOT language, such as the modules and their
+         connections on day 20, to be laid out with `dot -Tsvg`.";
+
+#[derive(PartialEq)]
+enum Command { Run, Bench, Check, Record, Draw, Gen, Lint, Graph }
+
+struct Options {
+    command: Command,
+    days: Vec<u8>,
+    inputs: PathBuf,
+    registry: PathBuf,
+    readme: bool,
+    phases: bool,
+    format: Format,
+    out: PathBuf,
+    image_format: ImageFormat,
+    scale: usize,
+    part: Option<Part>,
+    seed: u64,
+    params: bool,
+    assignments: Vec<(String, usize)>,
+    files: Vec<PathBuf>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let command = match args.next().as_deref() {
+        Some("run") => Command::Run,
+        Some("bench") => Command::Bench,
+        Some("check") => Command::Check,
+        Some("record") => Command::Record,
+        Some("draw") => Command::Draw,
+        Some("gen") => Command::Gen,
+        Some("lint") => Command::Lint,
+        Some("graph") => Command::Graph,
+        Some(command) => return Err(format!("unknown command: {}", command)),
+        None => return Err("missing command".to_string()),
+    };
+    let mut days = None;
+    let mut inputs = PathBuf::from("inputs");
+    let mut registry = PathBuf::from("answers.txt");
+    let mut readme = false;
+    let mut phases = false;
+    let mut format = Format::Text;
+    let mut out = PathBuf::from("frames");
+    let mut image_format = ImageFormat::Png;
+    let mut scale = 4;
+    let mut part = None;
+    let mut seed = 0;
+    let mut params = false;
+    let mut assignments = Vec::new();
+    let mut files = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--inputs" if command != Command::Gen && command != Command::Lint => {
+                let Some(dir) = args.next() else {
+                    return Err("--inputs needs a value".to_string());
+                };
+                inputs = PathBuf::from(dir);
+            }
+            "--registry" if matches!(command, Command::Run | Command::Check | Command::Record) => {
+                let Some(file) = args.next() else {
+                    return Err("--registry needs a value".to_string());
+                };
+                registry = PathBuf::from(file);
+            }
+            "--part" if command == Command::Run || command == Command::Bench => {
+                let Some(value) = args.next() else {
+                    return Err("--part needs a value".to_string());
+                };
+                part = Some(cli::parse_part(&value)?);
+            }
+            "--format" if command == Command::Run => {
+                let Some(value) = args.next() else {
+                    return Err("--format needs a value".to_string());
+                };
+                format = cli::parse_format(&value)?;
+            }
+            "--out" if command == Command::Draw => {
+                let Some(dir) = args.next() else {
+                    return Err("--out needs a value".to_string());
+                };
+                out = PathBuf::from(dir);
+            }
+            "--format" if command == Command::Draw => {
+                let Some(value) = args.next() else {
+                    return Err("--format needs a value".to_string());
+                };
+                image_format = render::parse_image_format(&value)?;
+            }
+            "--scale" if command == Command::Draw => {
+                let Some(value) = args.next() else {
+                    return Err("--scale needs a value".to_string());
+                };
+                match value.parse() {
+                    Ok(value @ 1..=64) => scale = value,
+                    _ => return Err(format!("not a scale between 1 and 64: {:?}", value)),
+                }
+            }
+            "--explain" if command == Command::Run => aoc2023rs::explain::set_enabled(true),
+            "--readme" if command == Command::Bench => readme = true,
+            "--phases" if command == Command::Run || command == Command::Bench => phases = true,
+            "--seed" if command == Command::Gen => {
+                let Some(value) = args.next() else {
+                    return Err("--seed needs a value".to_string());
+                };
+                let Ok(value) = value.parse() else {
+                    return Err(format!("not a valid seed: {:?}", value));
+                };
+                seed = value;
+            }
+            "--params" if command == Command::Gen => params = true,
+            flag if flag.starts_with("--") => return Err(format!("unknown flag: {}", flag)),
+            _ if days.is_none() => days = Some(cli::parse_days(&arg)?),
+            _ if command == Command::Gen => assignments.push(gen::parse_assignment(&arg)?),
+            _ => files.push(PathBuf::from(arg)),
+        }
+    }
+    let Some(mut days) = days else { return Err("missing days".to_string()) };
+    if command == Command::Gen && days.len() != 1 {
+        return Err("gen needs a single day".to_string());
+    }
+    if !files.is_empty() && days.len() != 1 {
+        return Err("input files need a single day".to_string());
+    }
+    if command == Command::Lint {
+        let [day] = days[..] else { return Err("lint needs a single day".to_string()) };
+        if !aoc2023rs::LINTABLE_DAYS.contains(&day) {
+            return Err(format!("day {} makes no assumptions to check", day));
+        }
+        if files.is_empty() { return Err("missing input file".to_string()) }
+    }
+    if command == Command::Graph {
+        let [day] = days[..] else { return Err("graph needs a single day".to_string()) };
+        if !aoc2023rs::GRAPH_DAYS.contains(&day) {
+            return Err(format!("day {} has no graph to print", day));
+        }
+    }
+    if let Some(part) = part {
+        if let [day] = days[..] {
+            if !aoc2023rs::has_part(day, part) {
+                return Err(format!("day {} has no part {}", day, part));
+            }
+        }
+        days.retain(|day| aoc2023rs::has_part(*day, part));
+    }
+    if command == Command::Draw {
+        if let [day] = days[..] {
+            if !aoc2023rs::DRAWABLE_DAYS.contains(&day) {
+                return Err(format!("day {} has nothing to draw", day));
+            }
+        }
+        days.retain(|day| aoc2023rs::DRAWABLE_DAYS.contains(day));
+    }
+    return Ok(Options{
+        command, days, inputs, registry, readme, phases, format, out, image_format, scale, part,
+        seed, params, assignments, files,
+    });
+}
+
+// An input for one of the selected days.
+struct Input {
+    day: u8,
+    path: PathBuf,
+    // Whether the input was named on the command line, rather than found in the inputs directory.
+    named: bool,
+}
+
+impl Input {
+    // Identifies the input at the start of each line of output: just the day, unless the input was
+    // named on the command line.
+    fn label(&self) -> String {
+        if !self.named { return format!("day{:02}", self.day) }
+        return format!("day{:02} {}", self.day, self.path.display());
+    }
+
+    // Reports an error in the input on stderr, naming the file if it was given on the command line.
+    fn report(&self, error: &ParseError) {
+        if self.named {
+            eprintln!("{}: {}", self.path.display(), error);
+        } else {
+            eprintln!("{}", error);
+        }
+    }
+
+    // Reads the input, or describes why it can't be read.
+    fn read(&self) -> Result<String, String> {
+        return input::read_file(&self.path).map_err(|error| {
+            return format!("day{:02}: can't read {}: {}", self.day, self.path.display(), error);
+        });
+    }
+}
+
+// Lists the inputs to use for the selected days: either the files named on the command line, in
+// order, or the input for each day in the inputs directory.
+fn inputs(options: &Options) -> Vec<Input> {
+    if options.files.is_empty() {
+        return options.days
+            .iter()
+            .map(|&day| Input{day, path: cli::input_path(&options.inputs, day), named: false})
+            .collect();
+    }
+    return options.days
+        .iter()
+        .flat_map(|&day| options.files.iter().map(move |path| {
+            return Input{day, path: path.clone(), named: true};
+        }))
+        .collect();
+}
+
+// Runs the selected command for each selected day, printing one line per day. Returns false if any
+// day could not be run.
+fn run(options: &Options) -> bool {
+    if options.command == Command::Gen { return generate(options) }
+    if options.command == Command::Draw { return draw(options) }
+    if options.command == Command::Lint { return lint(options) }
+    if options.command == Command::Graph { return graph(options) }
+    let registry = if options.command == Command::Bench {
+        Registry::default()
+    } else {
+        match Registry::load(&options.registry) {
+            Ok(registry) => registry,
+            Err(message) => {
+                eprintln!("{}", message);
+                return false;
+            }
+        }
+    };
+    if options.command == Command::Check { return check(options, &registry) }
+    if options.command == Command::Record { return record(options, registry) }
+    phases::set_enabled(options.phases);
+    let mut ok = true;
+    let mut breakdown = Vec::new();
+    for file in inputs(options) {
+        let day = file.day;
+        let input = match file.read() {
+            Ok(input) => input,
+            Err(message) => {
+                eprintln!("{}", message);
+                ok = false;
+                continue;
+            }
+        };
+        match options.command {
+            Command::Run => {
+                let start = Instant::now();
+                let result = aoc2023rs::solve_parts(day, options.part, &input);
+                let elapsed = start.elapsed();
+                if let Ok(solution) = &result {
+                    let hash = registry::sha256(input.as_bytes());
+                    if let Some(expected) = registry.lookup(day, &hash) {
+                        if !registry::agrees(&expected, solution) {
+                            eprintln!("{}: answers differ from {}: expected {}",
+                                      file.label(), options.registry.display(),
+                                      cli::answers(&expected));
+                            ok = false;
+                        }
+                    }
+                }
+                match (result, options.format) {
+                    (Ok(solution), Format::Json) => {
+                        let path = file.named.then_some(file.path.as_path());
+                        println!("{}", cli::json(day, path, &solution, elapsed));
+                    }
+                    (Ok(solution), Format::Text) => {
+                        println!("{}: {}", file.label(), cli::answers(&solution));
+                    }
+                    (Err(error), _) => {
+                        file.report(&error);
+                        ok = false;
+                    }
+                }
+            }
+            Command::Check
+            | Command::Record
+            | Command::Draw
+            | Command::Gen
+            | Command::Lint
+            | Command::Graph => unreachable!(),
+            Command::Bench => {
+                if let Err(error) = aoc2023rs::solve_parts(day, options.part, &input) {
+                    file.report(&error);
+                    ok = false;
+                    continue;
+                }
+                // Only time the phases of the runs that are measured, not the check above.
+                phases::take();
+                let stats = bench::measure(|| {
+                    let _ = black_box(aoc2023rs::solve_parts(day, options.part, black_box(&input)));
+                });
+                print!("{}: Ran {} times, mean {}us",
+                       file.label(), stats.runs, stats.mean.as_micros());
+                if !options.readme {
+                    print!(", median {}us", stats.median.as_micros());
+                }
+                print!(", stddev {}us", stats.stddev.as_micros());
+                if !options.readme {
+                    print!(", {} outliers", stats.outliers);
+                }
+                println!();
+            }
+        }
+        let (runs, phases) = phases::take();
+        if !phases.is_empty() { breakdown.push((file.label(), runs, phases)) }
+    }
+    if !breakdown.is_empty() { print_phases(&breakdown) }
+    return ok;
+}
+
+// Prints the mean time per run spent in each phase of each solver, along with its share of the
+// total.
+fn print_phases(breakdown: &[(String, usize, Vec<Phase>)]) {
+    let width = breakdown.iter().map(|(label, _, _)| label.len() + 2).max().unwrap_or(0).max(7);
+    println!();
+    println!("{:<width$}{:<20}{:>12}{:>8}", "day", "phase", "time", "share");
+    for (label, runs, phases) in breakdown {
+        let total: Duration = phases.iter().map(|phase| phase.total).sum();
+        for (i, phase) in phases.iter().enumerate() {
+            let label = if i == 0 { label.as_str() } else { "" };
+            let us = phase.total.as_secs_f64() * 1e6 / *runs as f64;
+            let share = 100.0 * phase.total.as_secs_f64() / total.as_secs_f64();
+            println!("{:<width$}{:<20}{:>10.1}us{:>7.1}%", label, phase.name, us, share);
+        }
+    }
+}
+
+// Whether a day's answers match its expected output.
+enum Status {
+    Pass,
+    Fail,
+    // There is no expected output to compare against.
+    Unchecked,
+    // The day couldn't be solved, for the given reason.
+    Error(String),
+}
+
+struct Report {
+    label: String,
+    solution: Option<Solution>,
+    elapsed: Duration,
+    status: Status,
+}
+
+impl Report {
+    fn error(file: &Input, message: String) -> Report {
+        let status = Status::Error(message);
+        return Report{label: file.label(), solution: None, elapsed: Duration::ZERO, status};
+    }
+}
+
+fn check_input(registry: &Registry, file: &Input) -> Report {
+    let input = match file.read() {
+        Ok(input) => input,
+        Err(message) => return Report::error(file, message),
+    };
+    let start = Instant::now();
+    let result = aoc2023rs::solve(file.day, &input);
+    let elapsed = start.elapsed();
+    let solution = match result {
+        Ok(solution) => solution,
+        Err(error) if file.named => {
+            return Report::error(file, format!("{}: {}", file.path.display(), error));
+        }
+        Err(error) => return Report::error(file, error.to_string()),
+    };
+    let status = match registry.lookup(file.day, &registry::sha256(input.as_bytes())) {
+        Some(expected) if expected == solution => Status::Pass,
+        Some(_) => Status::Fail,
+        None => match fs::read_to_string(file.path.with_extension("output")) {
+            Ok(expected) if expected == solution.to_string() => Status::Pass,
+            Ok(_) => Status::Fail,
+            Err(_) => Status::Unchecked,
+        },
+    };
+    return Report{label: file.label(), solution: Some(solution), elapsed, status};
+}
+
+// Solves the selected inputs concurrently and prints a summary table, followed by any errors.
+// Returns false if any input failed or could not be solved.
+fn check(options: &Options, registry: &Registry) -> bool {
+    let files = inputs(options);
+    let start = Instant::now();
+    let reports: Vec<Report> = thread::scope(|scope| {
+        let threads: Vec<_> = files
+            .iter()
+            .map(|file| (file, scope.spawn(move || check_input(registry, file))))
+            .collect();
+        return threads
+            .into_iter()
+            .map(|(file, thread)| thread.join().unwrap_or_else(|_| {
+                return Report::error(file, format!("{}: panicked", file.label()));
+            }))
+            .collect();
+    });
+    let wall_time = start.elapsed();
+
+    // The labels of named inputs include the file name, so the first column grows to fit them, and
+    // the answer columns grow to fit the longest answers, with a space before each.
+    let answer = |part: Option<Answer>| part.map_or("-".to_string(), |a| a.to_string());
+    let answers: Vec<(String, String)> = reports.iter().map(|report| match report.solution {
+        Some(solution) => (answer(solution.part1), answer(solution.part2)),
+        None => ("-".to_string(), "-".to_string()),
+    }).collect();
+    let width = reports.iter().map(|report| report.label.len() + 2).max().unwrap_or(0).max(7);
+    let width1 = answers.iter().map(|(part1, _)| part1.len() + 1).max().unwrap_or(0).max(16);
+    let width2 = answers.iter().map(|(_, part2)| part2.len() + 1).max().unwrap_or(0).max(16);
+    println!("{:<width$}{:>width1$}{:>width2$}{:>10}  status", "day", "part 1", "part 2", "time");
+    let (mut passed, mut failed, mut unchecked, mut errors) = (0, 0, 0, Vec::new());
+    for (report, (part1, part2)) in reports.iter().zip(&answers) {
+        let status = match &report.status {
+            Status::Pass => { passed += 1; "ok" }
+            Status::Fail => { failed += 1; "FAIL" }
+            Status::Unchecked => { unchecked += 1; "unchecked" }
+            Status::Error(message) => { errors.push(message); "error" }
+        };
+        println!("{:<width$}{:>width1$}{:>width2$} {:>7}us  {}",
+                 report.label, part1, part2, report.elapsed.as_micros(), status);
+    }
+    println!("{} passed, {} failed, {} unchecked, {} errors in {}us",
+             passed, failed, unchecked, errors.len(), wall_time.as_micros());
+    for message in &errors { eprintln!("{}", message) }
+    return failed == 0 && errors.is_empty();
+}
+
+// Solves each selected day and records its input's hash and answers in the registry, which is
+// saved if anything was added. Returns false if any day could not be solved, or was already
+// recorded with different answers.
+fn record(options: &Options, mut registry: Registry) -> bool {
+    let mut ok = true;
+    let mut changed = false;
+    for file in inputs(options) {
+        let day = file.day;
+        let input = match file.read() {
+            Ok(input) => input,
+            Err(message) => {
+                eprintln!("{}", message);
+                ok = false;
+                continue;
+            }
+        };
+        let solution = match aoc2023rs::solve(day, &input) {
+            Ok(solution) => solution,
+            Err(error) => {
+                file.report(&error);
+                ok = false;
+                continue;
+            }
+        };
+        let hash = registry::sha256(input.as_bytes());
+        match registry.record(day, hash, solution) {
+            Recorded::New => {
+                println!("{}: recorded {} with {}", file.label(), registry::hex(&hash),
+                         cli::answers(&solution));
+                changed = true;
+            }
+            Recorded::Unchanged => println!("{}: already recorded", file.label()),
+            Recorded::Conflict(expected) => {
+                eprintln!("{}: already recorded with {}, but solved as {}",
+                         file.label(), cli::answers(&expected), cli::answers(&solution));
+                ok = false;
+            }
+        }
+    }
+    if changed {
+        if let Err(error) = registry.save(&options.registry) {
+            eprintln!("can't write {}: {}", options.registry.display(), error);
+            return false;
+        }
+    }
+    return ok;
+}
+
+// Draws the frames for each selected input, printing the number of frames written. Returns false if
+// any input could not be drawn.
+fn draw(options: &Options) -> bool {
+    let mut ok = true;
+    for file in inputs(options) {
+        let day = file.day;
+        let input = match file.read() {
+            Ok(input) => input,
+            Err(message) => {
+                eprintln!("{}", message);
+                ok = false;
+                continue;
+            }
+        };
+        let mut dir = options.out.join(format!("day{:02}", day));
+        if file.named { dir.push(file.path.file_name().unwrap_or_default()) }
+        let mut frames = Frames::new(&dir, options.image_format, options.scale);
+        if let Err(error) = aoc2023rs::draw(day, &input, &mut frames) {
+            file.report(&error);
+            ok = false;
+            continue;
+        }
+        match frames.finish() {
+            Ok(count) => println!("{}: {} frames in {}", file.label(), count, dir.display()),
+            Err(error) => {
+                eprintln!("{}: can't write to {}: {}", file.label(), dir.display(), error);
+                ok = false;
+            }
+        }
+    }
+    return ok;
+}
+
+// Prints a generated input for the selected day, or the parameters that its generator accepts.
+// Returns false if the input could not be generated.
+fn generate(options: &Options) -> bool {
+    let day = options.days[0];
+    if options.params {
+        for param in gen::params(day) {
+            let assignment = format!("{}={}", param.name, param.default);
+            println!("{:<18}{} (between {} and {})",
+                     assignment, param.description, param.min, param.max);
+        }
+        return true;
+    }
+    match gen::generate(day, options.seed, &options.assignments) {
+        Ok(input) => {
+            print!("{}", input);
+            return true;
+        }
+        Err(message) => {
+            eprintln!("day{:02}: {}", day, message);
+            return false;
+        }
+    }
+}
+
+// Checks the selected day's assumptions about each input file, printing one line for each. Returns
+// false if any input breaks any of them, or can't be read or parsed.
+fn lint(options: &Options) -> bool {
+    let mut ok = true;
+    for file in inputs(options) {
+        let input = match file.read() {
+            Ok(input) => input,
+            Err(message) => {
+                eprintln!("{}", message);
+                ok = false;
+                continue;
+            }
+        };
+        let assumptions = match aoc2023rs::lint(file.day, &input) {
+            Ok(assumptions) => assumptions,
+            Err(error) => {
+                file.report(&error);
+                ok = false;
+                continue;
+            }
+        };
+        for assumption in &assumptions { println!("{}: {}", file.label(), assumption) }
+        ok &= assumptions.iter().all(|assumption| assumption.violation.is_none());
+    }
+    return ok;
+}
+
+// Prints the graph of each selected input in the DOT language. Since the graphs go to stdout, any
+// errors are reported on stderr. Returns false if any input could not be read or parsed.
+fn graph(options: &Options) -> bool {
+    let mut ok = true;
+    for file in inputs(options) {
+        let input = match file.read() {
+            Ok(input) => input,
+            Err(message) => {
+                eprintln!("{}", message);
+                ok = false;
+                continue;
+            }
+        };
+        match aoc2023rs::graph(file.day, &input) {
+            Ok(dot) => print!("{}", dot),
+            Err(error) => {
+                file.report(&error);
+                ok = false;
+            }
+        }
+    }
+    return ok;
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let [flag] = args.as_slice() {
+        if flag == "-h" || flag == "--help" {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+    }
+    match parse_args(args.into_iter()) {
+        Ok(options) => if run(&options) { ExitCode::SUCCESS } else { ExitCode::FAILURE },
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, USAGE);
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::error::{Error, Result};
+use core::ops::{Deref, DerefMut};
+
+// The solvers' core doesn't allocate, so any storage which grows with the input is a slice provided
+// by the caller. The `&str` entry points size each slice from the input, but other callers might
+// not, so running out of space is reported as an error rather than a panic.
+
+// Returns the error for running out of space at the start of `rest`, the part of the input which
+// needed it.
+pub fn too_small(rest: &[u8]) -> Error {
+    return Error::new(rest, "an input small enough for the buffers");
+}
+
+// Fails unless `buffer` holds at least `len` items. The error is placed at the end of `input`,
+// since it's the input as a whole which is too large.
+pub fn check_len<T>(input: &[u8], buffer: &[T], len: usize) -> Result<()> {
+    if buffer.len() < len { return Err(too_small(&input[input.len()..])) }
+    return Ok(());
+}
+
+// A vector with a fixed capacity, which keeps its items at the start of a slice.
+pub struct Buffer<'a, T> {
+    items: &'a mut [T],
+    len: usize,
+}
+
+impl<'a, T> Buffer<'a, T> {
+    pub fn new(items: &'a mut [T]) -> Buffer<'a, T> {
+        return Buffer{items, len: 0};
+    }
+
+    // Appends `item`, or fails at the start of `rest` (the part of the input that the item came
+    // from) if the buffer is full.
+    #[inline]
+    pub fn push(&mut self, item: T, rest: &[u8]) -> Result<()> {
+        let Some(slot) = self.items.get_mut(self.len) else { return Err(too_small(rest)) };
+        *slot = item;
+        self.len += 1;
+        return Ok(());
+    }
+
+    pub fn pop(&mut self) -> Option<T> where T: Copy {
+        if self.len == 0 { return None }
+        self.len -= 1;
+        return Some(self.items[self.len]);
+    }
+
+    pub fn truncate(&mut self, len: usize) {
+        self.len = self.len.min(len);
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    // Returns the items, for callers which need them to outlive the buffer.
+    pub fn into_slice(self) -> &'a mut [T] {
+        return &mut self.items[..self.len];
+    }
+}
+
+impl<T> Deref for Buffer<'_, T> {
+    type Target = [T];
+    #[inline]
+    fn deref(&self) -> &[T] {
+        return &self.items[..self.len];
+    }
+}
+
+impl<T> DerefMut for Buffer<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        return &mut self.items[..self.len];
+    }
+}
@@ -0,0 +1,112 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use crate::{Answer, Part, Solution, NUM_DAYS};
+
+// How the answers for each day are printed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    // Plain text. The per-day binaries print one answer per line, in the same format as
+    // `inputs/dayNN.output`.
+    Text,
+    // One JSON object per day, as produced by `json`.
+    Json,
+}
+
+pub fn parse_format(s: &str) -> Result<Format, String> {
+    match s {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        _ => Err(format!("unknown format: {:?} (expected `text` or `json`)", s)),
+    }
+}
+
+pub fn parse_part(s: &str) -> Result<Part, String> {
+    match s {
+        "1" => Ok(Part::One),
+        "2" => Ok(Part::Two),
+        _ => Err(format!("unknown part: {:?} (expected `1` or `2`)", s)),
+    }
+}
+
+fn json_answer(answer: Option<Answer>) -> String {
+    match answer {
+        Some(answer) => answer.to_string(),
+        None => "null".to_string(),
+    }
+}
+
+// Quotes a string for JSON, escaping quotes, backslashes, and control characters.
+fn json_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            c if c.is_control() => result.push_str(&format!("\\u{:04x}", c as u32)),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    return result;
+}
+
+// Formats the answers for a day as a single line of JSON, such as:
+//
+//   {"day": 17, "part1": 102, "part2": 94, "elapsed_us": 1234}
+//
+// A part which wasn't solved is `null`, as is `part2` for day 25. An input which was read from a
+// file named on the command line is identified by a `"file"` after the day.
+pub fn json(day: u8, file: Option<&Path>, solution: &Solution, elapsed: Duration) -> String {
+    let file = match file {
+        Some(file) => format!(", \"file\": {}", json_string(&file.to_string_lossy())),
+        None => String::new(),
+    };
+    return format!("{{\"day\": {}{}, \"part1\": {}, \"part2\": {}, \"elapsed_us\": {}}}",
+                   day, file, json_answer(solution.part1), json_answer(solution.part2),
+                   elapsed.as_micros());
+}
+
+// Formats the answers which were solved on a single line, separated by spaces.
+pub fn answers(solution: &Solution) -> String {
+    let answers: Vec<String> = [solution.part1, solution.part2]
+        .iter()
+        .flatten()
+        .map(|answer| answer.to_string())
+        .collect();
+    return answers.join(" ");
+}
+
+// Parses a selection of days such as `17`, `5..9`, `all`, or a comma-separated list of these.
+// Ranges are inclusive, so `5..9` selects days 5 through 9 (`5..=9` is also accepted). The result
+// is sorted and free of duplicates.
+pub fn parse_days(s: &str) -> Result<Vec<u8>, String> {
+    let mut days = Vec::new();
+    for item in s.split(',') {
+        let (first, last) = if item == "all" {
+            (1, NUM_DAYS)
+        } else if let Some((a, b)) = item.split_once("..") {
+            let b = b.strip_prefix('=').unwrap_or(b);
+            (parse_day(a)?, parse_day(b)?)
+        } else {
+            let day = parse_day(item)?;
+            (day, day)
+        };
+        if first > last { return Err(format!("empty range of days: {}", item)) }
+        days.extend(first..=last);
+    }
+    days.sort();
+    days.dedup();
+    return Ok(days);
+}
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.parse() {
+        Ok(day @ 1..=NUM_DAYS) => Ok(day),
+        _ => Err(format!("not a day between 1 and {}: {:?}", NUM_DAYS, s)),
+    }
+}
+
+// Returns the conventional location of the input for the given day within `dir`.
+pub fn input_path(dir: &Path, day: u8) -> PathBuf {
+    return dir.join(format!("day{:02}.input", day));
+}
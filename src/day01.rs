@@ -1,14 +1,21 @@
-use std::io;
+use crate::{Answer, OVERFLOW};
+use crate::error::{utf8, Error, Result};
+use crate::phase;
 
 // Returns an iterator over all suffixes of a string.
-fn suffixes(s: &str) -> impl Iterator<Item = &str> + DoubleEndedIterator {
+fn suffixes(s: &str) -> impl DoubleEndedIterator<Item = &str> {
     return s.char_indices().map(|(i, _)| &s[i..]);
 }
 
+// Returns the numerical digit at the start of the given string, if any.
+fn numeric_prefix(s: &str) -> Option<u32> {
+    return s.chars().next().unwrap().to_digit(10);
+}
+
 // Returns the numerical digit represented by a suffix of the given string, if
 // any. The representation can be a digit, or a word naming a digit.
 fn digit_prefix(s: &str) -> Option<u32> {
-    if let Some(i) = s.chars().nth(0).unwrap().to_digit(10) { return Some(i); }
+    if let Some(i) = numeric_prefix(s) { return Some(i); }
     if s.starts_with("zero") { return Some(0); }
     if s.starts_with("one") { return Some(1); }
     if s.starts_with("two") { return Some(2); }
@@ -22,19 +29,71 @@ fn digit_prefix(s: &str) -> Option<u32> {
     return None;
 }
 
-fn main() {
-    let mut part1 = 0;
-    let mut part2 = 0;
-    for line in io::stdin().lines().map(|l| l.unwrap()) {
-        // Part 1
-        let first = line.chars().find_map(|c| c.to_digit(10)).unwrap();
-        let last = line.chars().rev().find_map(|c| c.to_digit(10)).unwrap();
-        part1 += 10 * first + last;
+// Returns the sum of the calibration values, formed from the first and last digit on each line as
+// recognised by `digit`.
+fn calibrate(input: &str, digit: fn(&str) -> Option<u32>) -> Result<Answer> {
+    let mut total = Answer::default();
+    for line in input.lines() {
+        let no_digit = || Error::new(line.as_bytes(), "a line containing a digit");
+        let first = suffixes(line).find_map(digit).ok_or_else(no_digit)?;
+        let last = suffixes(line).rev().find_map(digit).ok_or_else(no_digit)?;
+        let value = 10 * first + last;
+        total = total.checked_add(value).ok_or(Error::new(line.as_bytes(), OVERFLOW))?;
+    }
+    return Ok(total);
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    return calibrate(input, numeric_prefix);
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    return calibrate(input, digit_prefix);
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let part1 = part1(input)?;
+    phase!("part 1");
+    let part2 = part2(input)?;
+    phase!("part 2");
+    return Ok((part1, part2));
+}
+
+pub fn solve_bytes(input: &[u8]) -> Result<(Answer, Answer)> {
+    return solve(utf8(input)?);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+";
+
+    // Some lines of the second example have no numeric digits, so it only works for part 2.
+    const WORDS: &str = "\
+two1nine
+eightwothree
+abcone2threexyz
+xtwone3four
+4nineeightseven2
+zoneight234
+7pqrstsixteen
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(142));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(142));
+    }
 
-        // Part 2
-        let first = suffixes(&line).find_map(digit_prefix).unwrap();
-        let last = suffixes(&line).rev().find_map(digit_prefix).unwrap();
-        part2 += 10 * first + last;
+    #[test]
+    fn words() {
+        assert_eq!(part2(WORDS).unwrap(), Answer::from(281));
+        assert!(part1(WORDS).is_err());
     }
-    print!("{}\n{}\n", part1, part2);
 }
@@ -1,58 +1,126 @@
-use std::io;
-use std::io::Read;
-use std::str;
+use crate::{Answer, OVERFLOW};
+use crate::error::{eat_str, utf8, Error, Result};
+use crate::phase;
 
-fn parse_int(s: &mut &str) -> u32 {
-    let n = s.find(|c: char| !c.is_digit(10)).unwrap_or(s.len());
-    let i = s[0..n].parse().unwrap();
-    *s = &mut &s[n..];
-    return i;
+fn parse_int(s: &mut &str) -> Result<u32> {
+    let n = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let i = s[0..n].parse().map_err(|_| Error::new(s.as_bytes(), "a number"))?;
+    *s = &s[n..];
+    return Ok(i);
 }
 
-fn main() {
-    let mut buffer = [0; 11 * 1024];
-    let size = io::stdin().read(&mut buffer).unwrap();
-    let input = str::from_utf8(&buffer[0..size]).unwrap();
+// The maximum number of cubes of each colour seen during one game.
+pub struct Game {
+    id: u32,
+    red: u32,
+    green: u32,
+    blue: u32,
+}
+
+// Reads the game at the start of `i`, and moves `i` past it.
+fn read_game(i: &mut &str) -> Result<Game> {
+    *i = eat_str(i, "Game ")?;
+    let id = parse_int(i)?;
+    *i = eat_str(i, ": ")?;
+
+    let mut r = 0;
+    let mut g = 0;
+    let mut b = 0;
+    loop {
+        let count = parse_int(i)?;
+        if let Some(j) = i.strip_prefix(" red") {
+            *i = j;
+            r = r.max(count);
+        } else if let Some(j) = i.strip_prefix(" green") {
+            *i = j;
+            g = g.max(count);
+        } else if let Some(j) = i.strip_prefix(" blue") {
+            *i = j;
+            b = b.max(count);
+        } else {
+            return Err(Error::new(i.as_bytes(), "a colour"));
+        }
+        if i.starts_with('\n') { break }
+        if let Some(j) = i.strip_prefix("; ") {
+            *i = j;
+        } else {
+            *i = eat_str(i, ", ")?;
+        }
+    }
+    *i = eat_str(i, "\n")?;
+    return Ok(Game{id, red: r, green: g, blue: b});
+}
+
+fn possible(game: &Game) -> Answer {
+    if game.red <= 12 && game.green <= 13 && game.blue <= 14 { return game.id.into() }
+    return Answer::default();
+}
+
+// The product of three 32-bit counts always fits in an `Answer`.
+fn power(game: &Game) -> Answer {
+    return (game.red as i128 * game.green as i128 * game.blue as i128).into();
+}
+
+// Returns the sum of `f` over all of the games.
+fn sum(input: &str, f: fn(&Game) -> Answer) -> Result<Answer> {
     let mut i = input;
+    let mut total = Answer::default();
+    while !i.is_empty() {
+        let start = i;
+        let game = read_game(&mut i)?;
+        total = total.checked_add(f(&game)).ok_or(Error::new(start.as_bytes(), OVERFLOW))?;
+    }
+    return Ok(total);
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    return sum(input, possible);
+}
 
-    let mut part1 = 0;
-    let mut part2 = 0;
-    let mut rec = 0;
+pub fn part2(input: &str) -> Result<Answer> {
+    return sum(input, power);
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let mut i = input;
+    let (mut total_possible, mut total_power) = (Answer::default(), Answer::default());
     while !i.is_empty() {
-        rec += 1;
-        i = i.strip_prefix("Game ").unwrap();
-        let id = parse_int(&mut i);
-        i = i.strip_prefix(": ").unwrap();
-
-        let mut r = 0;
-        let mut g = 0;
-        let mut b = 0;
-        loop {
-            let count = parse_int(&mut i);
-            if let Some(j) = i.strip_prefix(" red") {
-                i = j;
-                r = r.max(count);
-            } else if let Some(j) = i.strip_prefix(" green") {
-                i = j;
-                g = g.max(count);
-            } else if let Some(j) = i.strip_prefix(" blue") {
-                i = j;
-                b = b.max(count);
-            } else {
-                panic!("line {}: can't parse suffix: {}", rec, i);
-            }
-            if i.chars().nth(0).unwrap() == '\n' { break }
-            if let Some(j) = i.strip_prefix("; ") {
-                i = j;
-            } else {
-                i = i.strip_prefix(", ").unwrap();
-            }
-        }
-        i = i.strip_prefix("\n").unwrap();
+        let start = i;
+        let game = read_game(&mut i)?;
+        let overflow = Error::new(start.as_bytes(), OVERFLOW);
+        total_possible = total_possible.checked_add(possible(&game)).ok_or(overflow)?;
+        total_power = total_power.checked_add(power(&game)).ok_or(overflow)?;
+    }
+    phase!("games");
+    return Ok((total_possible, total_power));
+}
+
+pub fn solve_bytes(input: &[u8]) -> Result<(Answer, Answer)> {
+    return solve(utf8(input)?);
+}
 
-        if r <= 12 && g <= 13 && b <= 14 { part1 += id }
-        part2 += r * g * b;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 4: 1 green, 3 red, 6 blue; 3 green, 6 red; 3 green, 15 blue, 14 red
+Game 5: 6 red, 1 blue, 3 green; 2 blue, 1 red, 2 green
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(8));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(2286));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(8), Answer::from(2286)));
     }
 
-    print!("{}\n{}\n", part1, part2);
+    #[test]
+    fn large_power() {
+        let input = "Game 1: 4000000000 red, 4000000000 green, 4000000000 blue\n";
+        assert_eq!(part2(input).unwrap(), Answer::from(64000000000000000000000000000i128));
+    }
 }
@@ -1,41 +1,40 @@
-use std::io;
-use std::io::Read;
-use std::str;
-
-type Grid = [[char; 140]; 140];
-
-fn read_input() -> Grid {
-    let mut buffer = [0; 20480];
-    let size = io::stdin().read(&mut buffer).unwrap();
-    let input = str::from_utf8(&buffer[0..size]).unwrap();
-    let width = input.find('\n').unwrap();
-    if width > 140 { panic!("grid too wide") }
-    let mut height = 0;
-    let mut grid = [['.'; 140]; 140];
-    for line in input.lines() {
-        if line.len() != width { panic!("not a grid") }
-        for (i, c) in line.char_indices() { grid[height][i] = c }
-        height += 1;
+use crate::Answer;
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+#[cfg(feature = "std")]
+use crate::lint::{self, Assumption};
+use crate::phase;
+
+pub fn read_input(input: &[u8]) -> Result<Grid<&[u8]>> {
+    let grid = Grid::parse(input)?;
+    grid.validate(|c| c.is_ascii_graphic(), "a digit, '.', or a symbol")?;
+    // Part numbers have at most 3 digits, which keeps the sums and products small.
+    for row in grid.rows() {
+        for (i, window) in row.windows(4).enumerate() {
+            if window.iter().all(|c| c.is_ascii_digit()) {
+                return Err(Error::new(&row[i..], "a number with at most 3 digits"));
+            }
+        }
     }
-    return grid;
+    return Ok(grid);
 }
 
 // NumberIterator iterates over numbers that appear in a sequence of characters.
-struct NumberIterator<'a, T: Iterator<Item = &'a char>> {
+struct NumberIterator<'a, T: Iterator<Item = &'a u8>> {
     data: T,
 }
 
-impl<'a, T: Iterator<Item = &'a char>> Iterator for NumberIterator<'a, T> {
+impl<'a, T: Iterator<Item = &'a u8>> Iterator for NumberIterator<'a, T> {
     type Item = u32;
 
     fn next(&mut self) -> Option<Self::Item> {
         // Skip any non-digit characters.
         let i = &mut self.data;
-        if let Some(c) = i.find(|c| c.is_digit(10)) {
-            let mut n = c.to_digit(10).unwrap();
-            while let Some(c) = i.next() {
-                if let Some(d) = c.to_digit(10) {
-                    n = 10 * n + d;
+        if let Some(c) = i.find(|c| c.is_ascii_digit()) {
+            let mut n = (c - b'0') as u32;
+            for c in i.by_ref() {
+                if c.is_ascii_digit() {
+                    n = 10 * n + (c - b'0') as u32;
                 } else { break }
             }
             return Some(n);
@@ -45,26 +44,26 @@ impl<'a, T: Iterator<Item = &'a char>> Iterator for NumberIterator<'a, T> {
     }
 }
 
-fn numbers<'a, T: Iterator<Item = &'a char>>(i: T) -> NumberIterator<'a, T> {
+fn numbers<'a, T: Iterator<Item = &'a u8>>(i: T) -> NumberIterator<'a, T> {
     return NumberIterator{data: i};
 }
 
 struct Part<'a> {
-    grid: &'a Grid,
+    grid: &'a Grid<&'a [u8]>,
     x: usize,
     y: usize,
 }
 
-fn to_part<'a>(grid: &'a Grid, x: usize, y: usize) -> Option<Part<'a>> {
-    let c = grid[y][x];
-    if c == '.' || c.is_digit(10) { return None }
-    return Some(Part{grid: &grid, x: x, y: y});
+fn to_part<'a>(grid: &'a Grid<&'a [u8]>, x: usize, y: usize) -> Option<Part<'a>> {
+    let c = grid[(x, y)];
+    if c == b'.' || c.is_ascii_digit() { return None }
+    return Some(Part{grid, x, y});
 }
 
 // Iterate over all parts in the grid.
-fn parts(grid: &Grid) -> impl Iterator<Item = Part> {
-    return (0..140).flat_map(move |x| {
-        (0..140).filter_map(move |y| {
+fn parts<'a>(grid: &'a Grid<&'a [u8]>) -> impl Iterator<Item = Part<'a>> {
+    return (0..grid.width()).flat_map(move |x| {
+        (0..grid.height()).filter_map(move |y| {
             to_part(grid, x, y)
         })
     });
@@ -73,39 +72,156 @@ fn parts(grid: &Grid) -> impl Iterator<Item = Part> {
 // Iterate over all the numbers adjacent to a part.
 fn neighbours<'a>(part: &'a Part) -> impl Iterator<Item = u32> + 'a {
     let min_y = if part.y == 0 { 0 } else { part.y - 1 };
-    let max_y = (part.y + 2).min(140);
+    let max_y = (part.y + 2).min(part.grid.height());
     return (min_y .. max_y).flat_map(move |iy| {
         // Scan left/right as far as there are digits. The individual numbers
         // are picked out by the loop below.
-        let row = &part.grid[iy];
+        let row = part.grid.row(iy);
         let min_x = row[..part.x]
             .iter()
-            .rposition(|c| !c.is_digit(10))
+            .rposition(|c| !c.is_ascii_digit())
             .unwrap_or(0);
         let max_x = row[part.x + 1..]
             .iter()
-            .position(|c| !c.is_digit(10))
+            .position(|c| !c.is_ascii_digit())
             .map(|i| part.x + 1 + i)
-            .unwrap_or(140);
-        numbers(part.grid[iy][min_x .. max_x].iter())
+            .unwrap_or(row.len());
+        numbers(row[min_x .. max_x].iter())
     });
 }
 
-fn main() {
-    let grid = read_input();
-    let mut part1 = 0;
-    let mut part2 = 0;
-    // Assumption #1: No number is adjacent to two symbols.
-    // Assumption #2: Only '*' symbols are adjacent to exactly two numbers.
-    for part in parts(&grid) {
+// Assumption #1: No number is adjacent to two symbols.
+fn part_numbers(grid: &Grid<&[u8]>) -> u64 {
+    return parts(grid).map(|part| neighbours(&part).map(u64::from).sum::<u64>()).sum();
+}
+
+// Assumption #2: Only '*' symbols are adjacent to exactly two numbers.
+fn gear_ratios(grid: &Grid<&[u8]>) -> u64 {
+    let mut total = 0;
+    for part in parts(grid) {
         let mut count = 0;
         let mut product = 1;
         for number in neighbours(&part) {
             count += 1;
-            part1 += number;
-            product *= number;
+            product *= number as u64;
+        }
+        if count == 2 { total += product }
+    }
+    return total;
+}
+
+#[cfg(feature = "std")]
+fn is_symbol(c: u8) -> bool {
+    return c != b'.' && !c.is_ascii_digit();
+}
+
+// Describes the first of the given positions, and how many others there are.
+#[cfg(feature = "std")]
+fn first_of(positions: &[(usize, usize)], what: &str) -> Option<String> {
+    let (first, others) = positions.split_first()?;
+    let mut result = format!("{} at {}", what, lint::position(*first));
+    if !others.is_empty() { result += &format!(" and {} more", others.len()) }
+    return Some(result);
+}
+
+#[cfg(feature = "std")]
+pub fn lint(input: &str) -> Result<Vec<Assumption>> {
+    let grid = read_input(input.as_bytes())?;
+    let mut shared = Vec::new();
+    for y in 0..grid.height() {
+        let row = grid.row(y);
+        let mut x = 0;
+        while x < row.len() {
+            if !row[x].is_ascii_digit() {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            while x < row.len() && row[x].is_ascii_digit() { x += 1 }
+            let mut symbols = 0;
+            for ny in y.saturating_sub(1)..(y + 2).min(grid.height()) {
+                let cells = &grid.row(ny)[start.saturating_sub(1)..(x + 1).min(row.len())];
+                symbols += cells.iter().filter(|c| is_symbol(**c)).count();
+            }
+            if symbols > 1 { shared.push((start, y)) }
+        }
+    }
+    let mut gears = Vec::new();
+    for part in parts(&grid) {
+        if grid[(part.x, part.y)] != b'*' && neighbours(&part).count() == 2 {
+            gears.push((part.x, part.y));
         }
-        if count == 2 { part2 += product }
     }
-    print!("{}\n{}\n", part1, part2);
+    gears.sort_by_key(|(x, y)| (*y, *x));
+    return Ok(vec![
+        Assumption::new("no number is adjacent to two symbols",
+                        first_of(&shared, "a number next to several symbols")),
+        Assumption::new("only '*' symbols are adjacent to exactly two numbers",
+                        first_of(&gears, "a symbol other than '*' next to two numbers")),
+    ]);
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    return Ok(part_numbers(&read_input(input.as_bytes())?).into());
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    return Ok(gear_ratios(&read_input(input.as_bytes())?).into());
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    return solve_bytes(input.as_bytes());
+}
+
+pub fn solve_bytes(input: &[u8]) -> Result<(Answer, Answer)> {
+    let grid = read_input(input)?;
+    phase!("parse");
+    let part1 = part_numbers(&grid);
+    phase!("part 1");
+    let part2 = gear_ratios(&grid);
+    phase!("part 2");
+    return Ok((part1.into(), part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(4361));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(467835));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(4361), Answer::from(467835)));
+    }
+
+    #[test]
+    fn lint() {
+        assert!(super::lint(EXAMPLE).unwrap().iter().all(|a| a.violation.is_none()));
+        let shared = EXAMPLE.replace("..35..633.", "..35#.633.");
+        let gear = EXAMPLE.replace("...$.*....", "...$.+....");
+        let violations = |input: &str| -> Vec<Option<String>> {
+            return super::lint(input).unwrap().into_iter().map(|a| a.violation).collect();
+        };
+        assert_eq!(violations(&shared), [
+            Some("a number next to several symbols at line 3, column 3".to_string()),
+            None,
+        ]);
+        assert_eq!(violations(&gear), [
+            None,
+            Some("a symbol other than '*' next to two numbers at line 9, column 6".to_string()),
+        ]);
+    }
 }
@@ -1,45 +1,136 @@
-use std::io;
-use std::str;
-
-fn parse_wins(input: &str) -> u32 {
-    let input = input.split_once(':').unwrap().1;
-    let mut win_buffer = [0; 10];
-    let mut winning_numbers = 0;
-    let (win_str, values_str) = input.split_once('|').unwrap();
-    for n in win_str.split_ascii_whitespace().map(|n| n.parse().unwrap()) {
-        if winning_numbers == 10 { panic!("Too many winning numbers") }
-        win_buffer[winning_numbers] = n;
-        winning_numbers += 1;
-    }
-    let wins = &win_buffer[0..winning_numbers];
+use crate::Answer;
+use crate::buffer::{too_small, Buffer};
+use crate::error::{parse_num, utf8, Error, Result};
+use crate::phase;
+
+// Returns the number of matches on a card. `winning` is scratch space for the winning numbers,
+// which is reused from card to card.
+pub fn parse_wins(input: &str, winning: &mut [u32]) -> Result<u32> {
+    let Some((_, input)) = input.split_once(':') else {
+        return Err(Error::byte(input.as_bytes(), b':'));
+    };
+    let Some((win_str, values_str)) = input.split_once('|') else {
+        return Err(Error::byte(input.as_bytes(), b'|'));
+    };
+    let mut wins = Buffer::new(winning);
+    for n in win_str.split_ascii_whitespace() { wins.push(parse_num::<u32>(n)?, n.as_bytes())? }
     let mut num_wins = 0;
-    for n in values_str.split_ascii_whitespace().map(|n| n.parse().unwrap()) {
-        if wins.contains(&n) { num_wins += 1 }
+    for n in values_str.split_ascii_whitespace() {
+        if wins.contains(&parse_num(n)?) { num_wins += 1 }
     }
-    return num_wins;
+    return Ok(num_wins);
 }
 
-fn main() {
-    // Parse the cards.
-    let mut part1 = 0;
-    let mut part2 = 0;
-    let mut counts = [1; 10];
-    let mut i = 0;
-    for line in io::stdin().lines().map(|l| l.unwrap()) {
-        let num_wins = parse_wins(&line);
+// Every number on a card takes at least two bytes with the space before it, so this is more than
+// the number of winning numbers or wins on any card. It is the length of both buffers.
+pub fn buffer_len(input: &str) -> usize {
+    return input.lines().map(|line| line.len() / 2 + 1).max().unwrap_or(0);
+}
 
-        // Part 1: accumulate points based on the number of wins.
-        part1 += (1 << num_wins) >> 1;
+// The totals for each part, which are `None` if they don't fit in 64 bits.
+struct Totals {
+    points: Option<u64>,
+    cards: Option<u64>,
+}
 
-        // Part 2: accumulate cards.
+// Part 1 accumulates points based on the number of wins, where each win doubles the points for a
+// card. Part 2 accumulates cards, and the number of copies can double with every card. Only the
+// cards up to the largest number of wins ahead need to be counted, so the counts are kept in a
+// ring buffer, which must be longer than the number of wins on any card.
+fn totals(input: &str, winning: &mut [u32], counts: &mut [u64]) -> Result<Totals> {
+    let mut points: Option<u64> = Some(0);
+    let mut cards: Option<u64> = Some(0);
+    let size = counts.len();
+    counts.fill(1);
+    let mut i = 0;
+    for line in input.lines() {
+        let num_wins = parse_wins(line, winning)?;
+        if num_wins as usize >= size { return Err(too_small(line.as_bytes())) }
+        let card_points = if num_wins == 0 { Some(0) } else { 1u64.checked_shl(num_wins - 1) };
+        points = points.zip(card_points).and_then(|(a, b)| a.checked_add(b));
         let n = counts[i];
-        part2 += n;
+        cards = cards.and_then(|total| total.checked_add(n));
         counts[i] = 1;
-        i = if i < 9 { i + 1 } else { 0 };
+        i = if i + 1 < size { i + 1 } else { 0 };
         for j in 0 .. num_wins as usize {
-            let k = if i + j < 10 { i + j } else { i + j - 10 };
-            counts[k] += n;
+            let k = if i + j < size { i + j } else { i + j - size };
+            // A count which saturates is at least 2^64, and so is the total once it's added.
+            counts[k] = counts[k].saturating_add(n);
         }
     }
-    print!("{}\n{}\n", part1, part2);
+    return Ok(Totals{points, cards});
+}
+
+fn total_points(input: &str, totals: &Totals) -> Result<Answer> {
+    let Some(total) = totals.points else {
+        return Err(Error::new(&input.as_bytes()[input.len()..], "fewer than 2^64 points in total"));
+    };
+    return Ok(total.into());
+}
+
+fn total_cards(input: &str, totals: &Totals) -> Result<Answer> {
+    let Some(total) = totals.cards else {
+        return Err(Error::new(&input.as_bytes()[input.len()..], "fewer than 2^64 cards in total"));
+    };
+    return Ok(total.into());
+}
+
+#[cfg(feature = "std")]
+fn totals_with_buffers(input: &str) -> Result<Totals> {
+    let len = buffer_len(input);
+    return totals(input, &mut vec![0; len], &mut vec![0; len]);
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    return total_points(input, &totals_with_buffers(input)?);
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    return total_cards(input, &totals_with_buffers(input)?);
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let totals = totals_with_buffers(input)?;
+    return Ok((total_points(input, &totals)?, total_cards(input, &totals)?));
+}
+
+// Solves both parts, using buffers of at least `buffer_len` items for the winning numbers and for
+// the counts of cards.
+pub fn solve_bytes(
+    input: &[u8],
+    winning: &mut [u32],
+    counts: &mut [u64],
+) -> Result<(Answer, Answer)> {
+    let input = utf8(input)?;
+    let totals = totals(input, winning, counts)?;
+    phase!("winning numbers");
+    let part1 = total_points(input, &totals)?;
+    phase!("part 1");
+    let part2 = total_cards(input, &totals)?;
+    phase!("part 2");
+    return Ok((part1, part2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(13));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(30));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(13), Answer::from(30)));
+    }
 }
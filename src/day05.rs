@@ -1,7 +1,21 @@
-use std::io;
+use crate::Answer;
+use crate::buffer::Buffer;
+use crate::error::{parse_num, utf8, Error, Result};
+use crate::explain;
+use crate::phase;
 
-fn map(destination: u64, source: u64, range: u64,
-       input: &mut Vec<(u64, u64)>, output: &mut Vec<(u64, u64)>) {
+pub type Range = (u64, u64);
+
+// Ranges are stored by their ends, so they must end within 64 bits.
+const TOO_LONG: &str = "a range which ends below 2^64";
+
+// Maps the parts of the ranges in `input` which overlap `[source, source + range)` to the
+// destination, moving them to `output`. `line` is the mapping in the input.
+pub fn map(destination: u64, source: u64, range: u64, line: &[u8],
+           input: &mut Buffer<Range>, output: &mut Buffer<Range>) -> Result<()> {
+    if source.checked_add(range).is_none() || destination.checked_add(range).is_none() {
+        return Err(Error::new(line, TOO_LONG));
+    }
     for i in (0 .. input.len()).rev() {
         let (a, b) = input[i];
         if b <= source || source + range <= a {
@@ -12,10 +26,10 @@ fn map(destination: u64, source: u64, range: u64,
             let a2 = a.max(source);
             let b2 = b.min(source + range);
             if a2 == b2 { continue }
-            output.push((a2 - source + destination, b2 - source + destination));
+            output.push((a2 - source + destination, b2 - source + destination), line)?;
             if a < a2 && b2 < b {
                 input[i] = (a, a2);
-                input.push((b2, b));
+                input.push((b2, b), line)?;
             } else if a < a2 {
                 input[i] = (a, a2);
             } else if b2 < b {
@@ -26,46 +40,196 @@ fn map(destination: u64, source: u64, range: u64,
             }
         }
     }
+    return Ok(());
 }
 
-fn main() {
-    let mut seed_line = String::new();
-    io::stdin().read_line(&mut seed_line).unwrap();
-    let seeds: Vec<u64> = seed_line
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|n| n.parse().unwrap())
-        .collect();
-    let mut part1_input: Vec<(u64, u64)> = seeds
-        .iter()
-        .map(|x| (*x, *x + 1))
-        .collect();
-    let mut part2_input: Vec<(u64, u64)> = seeds
-        .chunks_exact(2)
-        .map(|ab| (ab[0], ab[0] + ab[1]))
-        .collect();
-    let mut part2_output = Vec::new();
-    let mut part1_output = Vec::new();
-    for line in io::stdin().lines().map(|l| l.unwrap()) {
-        if line.starts_with(|c: char| c.is_lowercase()) {
-            part1_input.append(&mut part1_output);
-            part2_input.append(&mut part2_output);
-        } else if line == "" {
-            continue;
+// Sorts the ranges and merges the ones which overlap, so that the rest are disjoint.
+fn merge(ranges: &mut Buffer<Range>) {
+    ranges.sort_unstable();
+    let mut n = 0;
+    for i in 0..ranges.len() {
+        let (a, b) = ranges[i];
+        if n > 0 && a <= ranges[n - 1].1 {
+            ranges[n - 1].1 = ranges[n - 1].1.max(b);
         } else {
-            let mut parts = line
-                .split_ascii_whitespace()
-                .map(|n| n.parse().unwrap());
-            let destination: u64 = parts.next().unwrap();
-            let source: u64 = parts.next().unwrap();
-            let range: u64 = parts.next().unwrap();
-            map(destination, source, range, &mut part1_input, &mut part1_output);
-            map(destination, source, range, &mut part2_input, &mut part2_output);
+            ranges[n] = (a, b);
+            n += 1;
         }
     }
-    part1_input.append(&mut part1_output);
-    part2_input.append(&mut part2_output);
-    let part1 = part1_input.iter().map(|(a, _)| a).min().unwrap();
-    let part2 = part2_input.iter().map(|(a, _)| a).min().unwrap();
-    print!("{}\n{}\n", part1, part2);
+    ranges.truncate(n);
+}
+
+// Moves on from the stage numbered `stage`, by putting the ranges that it mapped back with the
+// others. `rest` is where the next stage starts.
+fn next_stage(
+    stage: &mut usize,
+    input: &mut Buffer<Range>,
+    output: &mut Buffer<Range>,
+    rest: &[u8],
+) -> Result<()> {
+    for range in output.iter() { input.push(*range, rest)? }
+    output.clear();
+    merge(input);
+    explain!("after map {}: {} intervals {:?}", stage, input.len(), &input[..]);
+    *stage += 1;
+    return Ok(());
+}
+
+fn is_header(line: &str) -> bool {
+    return line.starts_with(|c: char| c.is_lowercase());
+}
+
+// The ranges are disjoint at the start of each stage, so each mapping can only split two of them,
+// at its two ends. Each seed starts as its own range, so this is the most there can be.
+pub fn buffer_len(input: &str) -> usize {
+    let mut lines = input.lines();
+    let seeds = lines.next().map_or(0, |line| line.split_ascii_whitespace().count());
+    return seeds + 2 * lines.filter(|line| !line.is_empty() && !is_header(line)).count();
+}
+
+// Maps the seeds through every stage of the almanac and returns the lowest location. The seeds are
+// taken in pairs, as the start and length of a range, if `in_pairs` is set. Both buffers must hold
+// `buffer_len` ranges.
+fn lowest_location(
+    input: &str,
+    in_pairs: bool,
+    ranges: &mut [Range],
+    mapped: &mut [Range],
+) -> Result<u64> {
+    let mut lines = input.lines();
+    let seed_line = lines.next().unwrap_or(input);
+    let seeds = seed_line.strip_prefix("seeds:");
+    let seeds = seeds.ok_or(Error::token(seed_line.as_bytes(), "seeds:"))?;
+    let num_seeds = seeds.split_ascii_whitespace().count();
+    if num_seeds == 0 || !num_seeds.is_multiple_of(2) {
+        return Err(Error::new(seed_line.as_bytes(), "an even number of seeds"));
+    }
+    let mut ranges = Buffer::new(ranges);
+    let mut seeds = seeds.split_ascii_whitespace();
+    while let Some(seed) = seeds.next() {
+        let start: u64 = parse_num(seed)?;
+        let length: u64 = if in_pairs { parse_num(seeds.next().unwrap())? } else { 1 };
+        let end = start.checked_add(length).ok_or(Error::new(seed.as_bytes(), TOO_LONG))?;
+        ranges.push((start, end), seed.as_bytes())?;
+    }
+    merge(&mut ranges);
+    let mut mapped = Buffer::new(mapped);
+    let mut stage = 0;
+    for line in lines {
+        if is_header(line) {
+            next_stage(&mut stage, &mut ranges, &mut mapped, line.as_bytes())?;
+        } else if !line.is_empty() {
+            let mut parts = line.split_ascii_whitespace().map(parse_num);
+            let missing = Error::new(line.as_bytes(), "three numbers");
+            let mut next = || parts.next().unwrap_or(Err(missing));
+            let destination: u64 = next()?;
+            let source: u64 = next()?;
+            let range: u64 = next()?;
+            map(destination, source, range, line.as_bytes(), &mut ranges, &mut mapped)?;
+        }
+    }
+    next_stage(&mut stage, &mut ranges, &mut mapped, &input.as_bytes()[input.len()..])?;
+    // Every seed maps to exactly one location, so the list can't be empty.
+    return Ok(ranges[0].0);
+}
+
+#[cfg(feature = "std")]
+fn lowest_location_with_buffers(input: &str, in_pairs: bool) -> Result<Answer> {
+    let len = buffer_len(input);
+    let (mut ranges, mut mapped) = (vec![(0, 0); len], vec![(0, 0); len]);
+    return Ok(lowest_location(input, in_pairs, &mut ranges, &mut mapped)?.into());
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    return lowest_location_with_buffers(input, false);
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    return lowest_location_with_buffers(input, true);
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let len = buffer_len(input);
+    return solve_bytes(input.as_bytes(), &mut vec![(0, 0); len], &mut vec![(0, 0); len]);
+}
+
+// Solves both parts, using buffers of at least `buffer_len` ranges.
+pub fn solve_bytes(
+    input: &[u8],
+    ranges: &mut [Range],
+    mapped: &mut [Range],
+) -> Result<(Answer, Answer)> {
+    let input = utf8(input)?;
+    let seeds = lowest_location(input, false, ranges, mapped)?;
+    phase!("part 1");
+    let seed_ranges = lowest_location(input, true, ranges, mapped)?;
+    phase!("part 2");
+    return Ok((seeds.into(), seed_ranges.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(35));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(46));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(35), Answer::from(46)));
+    }
+
+    #[test]
+    fn long_ranges() {
+        // The pair of seeds is a range which ends past 2^64, though each seed is fine by itself.
+        let seeds = "\
+seeds: 18446744073709551614 2
+
+seed-to-soil map:
+0 18446744073709551614 1
+";
+        assert_eq!(part1(seeds).unwrap(), Answer::from(0));
+        assert!(part2(seeds).is_err());
+        let mapping = "seeds: 1 1\n\nseed-to-soil map:\n0 18446744073709551615 2\n";
+        assert!(part1(mapping).is_err());
+        assert!(solve(mapping).is_err());
+    }
 }
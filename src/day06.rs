@@ -1,9 +1,14 @@
-use std::io;
+use crate::Answer;
+use crate::error::{eat_str, parse_num, utf8, Error, Result};
+use crate::phase;
 
-fn ways(time: u64, distance: u64) -> u64 {
+pub fn ways(time: u64, distance: u64) -> u64 {
     // Part 1: find the number of values of t where
     //                      t * (time - t) > distance
+    let time = time as u128;
+    let distance = distance as u128;
     let max = time / 2;
+    if max * (time - max) <= distance { return 0 }
     // Binary search for the min.
     let mut a = 1;
     let mut b = max;
@@ -16,42 +21,106 @@ fn ways(time: u64, distance: u64) -> u64 {
         }
     }
     let low = a;
-    // Probe for the max.
-    let mut high = 2 * max - a - 1;
-    while high * (time - high) > distance { high += 1 }
-    return high - low;
-}
-
-fn main() {
-    let mut time_line = String::new();
-    io::stdin().read_line(&mut time_line).unwrap();
-    let mut distance_line = String::new();
-    io::stdin().read_line(&mut distance_line).unwrap();
-    let times = time_line
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|x| x.parse().unwrap());
-    let distances = distance_line
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|x| x.parse().unwrap());
-    let part1: u64 = times.zip(distances).map(|(t, d)| ways(t, d)).product();
-    let time: u64 = time_line
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|s| s.chars())
-        .flatten()
-        .collect::<String>()
-        .parse()
-        .unwrap();
-    let distance: u64 = distance_line
-        .split_ascii_whitespace()
-        .skip(1)
-        .map(|s| s.chars())
-        .flatten()
-        .collect::<String>()
-        .parse()
-        .unwrap();
-    let part2 = ways(time, distance);
-    print!("{}\n{}\n", part1, part2);
+    // The distance is symmetric about `time / 2`, so the max is `time - low`.
+    return (time - 2 * low + 1) as u64;
+}
+
+// The lines of times and distances for the individual races, and the single race formed by ignoring
+// the spaces between numbers.
+pub struct Races<'a> {
+    times: &'a str,
+    distances: &'a str,
+    race: (u64, u64),
+}
+
+impl Races<'_> {
+    fn races(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        // The numbers were checked by `read_input`.
+        fn numbers(line: &str) -> impl Iterator<Item = u64> + '_ {
+            return line.split_ascii_whitespace().map(|s| s.parse().unwrap());
+        }
+        return numbers(self.times).zip(numbers(self.distances));
+    }
+}
+
+// Returns the number of numbers on a line.
+fn count(line: &str) -> Result<usize> {
+    return line.split_ascii_whitespace().try_fold(0, |n, s| parse_num::<u64>(s).map(|_| n + 1));
+}
+
+// Parses the digits on a line as a single number.
+fn concatenation(line: &str) -> Result<u64> {
+    let mut digits = line.bytes().filter(|c| !c.is_ascii_whitespace()).peekable();
+    let mut result: Option<u64> = digits.peek().map(|_| 0);
+    for c in digits {
+        let digit = if c.is_ascii_digit() { Some((c - b'0') as u64) } else { None };
+        result = result.and_then(|x| x.checked_mul(10)?.checked_add(digit?));
+    }
+    return result.ok_or(Error::new(line.as_bytes(), "a number"));
+}
+
+pub fn read_input(input: &str) -> Result<Races<'_>> {
+    let mut lines = input.lines();
+    let time_line = eat_str(lines.next().unwrap_or(input), "Time:")?;
+    let distance_line = eat_str(lines.next().unwrap_or(&input[input.len()..]), "Distance:")?;
+    if count(time_line)? != count(distance_line)? {
+        return Err(Error::new(distance_line.as_bytes(), "one distance for each time"));
+    }
+    let time = concatenation(time_line)?;
+    let distance = concatenation(distance_line)?;
+    // The answer to part 1 is at most the product of the number of ways to hold the button.
+    let ways = time_line.split_ascii_whitespace().map(|s| s.parse::<u64>().unwrap())
+        .try_fold(1u64, |p, t| t.checked_add(1).and_then(|t| p.checked_mul(t)));
+    if ways.is_none() {
+        return Err(Error::new(time_line.as_bytes(), "times whose product fits in 64 bits"));
+    }
+    return Ok(Races{times: time_line, distances: distance_line, race: (time, distance)});
+}
+
+fn product(races: &Races) -> u64 {
+    return races.races().map(|(t, d)| ways(t, d)).product();
+}
+
+fn single(races: &Races) -> u64 {
+    let (time, distance) = races.race;
+    return ways(time, distance);
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    return Ok(product(&read_input(input)?).into());
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    return Ok(single(&read_input(input)?).into());
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let races = read_input(input)?;
+    phase!("parse");
+    let part1 = product(&races);
+    phase!("part 1");
+    let part2 = single(&races);
+    phase!("part 2");
+    return Ok((part1.into(), part2.into()));
+}
+
+pub fn solve_bytes(input: &[u8]) -> Result<(Answer, Answer)> {
+    return solve(utf8(input)?);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+Time:      7  15   30
+Distance:  9  40  200
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(288));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(71503));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(288), Answer::from(71503)));
+    }
 }
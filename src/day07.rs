@@ -1,25 +1,28 @@
-use std::io;
+use crate::{Answer, OVERFLOW};
+use crate::buffer::Buffer;
+use crate::error::{parse_num, utf8, Error, Result};
+use crate::phase;
 
 type Card = u8;
 type HandType = u8;
 
-fn card(c: char) -> Card {
+fn card(c: u8) -> Option<Card> {
     match c {
         // Joker => 0,
-        '2' => 1,
-        '3' => 2,
-        '4' => 3,
-        '5' => 4,
-        '6' => 5,
-        '7' => 6,
-        '8' => 7,
-        '9' => 8,
-        'T' => 9,
-        'J' => 10,
-        'Q' => 11,
-        'K' => 12,
-        'A' => 13,
-        _ => panic!("Bad card"),
+        b'2' => Some(1),
+        b'3' => Some(2),
+        b'4' => Some(3),
+        b'5' => Some(4),
+        b'6' => Some(5),
+        b'7' => Some(6),
+        b'8' => Some(7),
+        b'9' => Some(8),
+        b'T' => Some(9),
+        b'J' => Some(10),
+        b'Q' => Some(11),
+        b'K' => Some(12),
+        b'A' => Some(13),
+        _ => None,
     }
 }
 
@@ -28,7 +31,7 @@ fn hand_type(hand: &[Card; 5]) -> HandType {
     for card in hand { counts[*card as usize] += 1 }
     let jokers = counts[0];
     counts[0] = 0;
-    counts.sort();
+    counts.sort_unstable();
     // It's always best to pretend that the jokers are whatever card we have the
     // most of.
     counts[13] += jokers;
@@ -44,52 +47,112 @@ fn hand_type(hand: &[Card; 5]) -> HandType {
     }
 }
 
-#[derive(Eq, PartialEq, Ord, PartialOrd)]
-struct Hand {
+#[derive(Clone, Copy, Default, Eq, PartialEq, Ord, PartialOrd)]
+pub struct Hand {
     hand_type: HandType,
     cards: [Card; 5],
 }
 
-fn hand(s: &str) -> Hand {
-    let cards: [Card; 5] = s
-        .chars()
-        .map(|c| card(c))
-        .collect::<Vec<Card>>()  // This allocation makes me sad :(
-        .try_into()
-        .unwrap();
-    return Hand{hand_type: hand_type(&cards), cards: cards};
+fn hand(s: &str) -> Result<Hand> {
+    if s.len() != 5 { return Err(Error::new(s.as_bytes(), "a hand of 5 cards")) }
+    let mut cards: [Card; 5] = [0; 5];
+    for (i, c) in s.bytes().enumerate() {
+        cards[i] = card(c).ok_or(Error::new(&s.as_bytes()[i..], "a card"))?;
+    }
+    return Ok(Hand{hand_type: hand_type(&cards), cards});
 }
 
-fn record(s: &str) -> (Hand, u32) {
-    let (h, b) = s.split_once(" ").unwrap();
-    return (hand(h), b.parse().unwrap());
+// A hand and its bid.
+pub type Record = (Hand, u32);
+
+fn record(s: &str) -> Result<Record> {
+    let Some((h, b)) = s.split_once(" ") else { return Err(Error::byte(s.as_bytes(), b' ')) };
+    return Ok((hand(h)?, parse_num(b)?));
 }
 
-fn winnings(records: &[(Hand, u32)]) -> u32 {
-    let mut total = 0;
+// Returns the total winnings, or `None` if they overflow.
+fn winnings(records: &[Record]) -> Option<Answer> {
+    let mut total = Answer::default();
     for (rank, (_, bet)) in (1..).zip(records) {
-        total += rank * bet;
+        total = total.checked_add(Answer::from(*bet).checked_mul(rank as u64)?)?;
     }
-    return total;
+    return Some(total);
+}
+
+// Reads the records into `buffer`, which must hold one for each line of the input.
+fn read_input<'a>(input: &str, buffer: &'a mut [Record]) -> Result<&'a mut [Record]> {
+    let mut records = Buffer::new(buffer);
+    for line in input.lines() { records.push(record(line)?, line.as_bytes())? }
+    return Ok(records.into_slice());
+}
+
+// Sorts the records by hand strength and returns the total winnings. The records no longer match
+// the lines of the input once they are sorted, so an overflow is reported for the whole input.
+fn rank(input: &str, records: &mut [Record]) -> Result<Answer> {
+    records.sort_unstable();
+    return winnings(records).ok_or(Error::new(input.as_bytes(), OVERFLOW));
 }
 
-fn main() {
-    let mut records: Vec<(Hand, u32)> = io::stdin()
-        .lines()
-        .map(|l| l.unwrap())
-        .map(|s| record(&s))
-        .collect();
-    records.sort();
-    let part1 = winnings(&records);
-    for (hand, _) in &mut records {
+fn use_jokers(records: &mut [Record]) {
+    for (hand, _) in records {
         // Rewrite Jacks as Jokers.
         for c in &mut hand.cards {
-            if *c == card('J') { *c = 0 }
+            if Some(*c) == card(b'J') { *c = 0 }
         }
         // Re-determine hand types.
         hand.hand_type = hand_type(&hand.cards);
     }
-    records.sort();
-    let part2 = winnings(&records);
-    print!("{}\n{}\n", part1, part2);
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    let mut buffer = vec![Record::default(); input.lines().count()];
+    return rank(input, read_input(input, &mut buffer)?);
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    let mut buffer = vec![Record::default(); input.lines().count()];
+    let records = read_input(input, &mut buffer)?;
+    use_jokers(records);
+    return rank(input, records);
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let mut buffer = vec![Record::default(); input.lines().count()];
+    return solve_bytes(input.as_bytes(), &mut buffer);
+}
+
+// Solves both parts, using a buffer of at least one record for each line of the input.
+pub fn solve_bytes(input: &[u8], records: &mut [Record]) -> Result<(Answer, Answer)> {
+    let input = utf8(input)?;
+    let records = read_input(input, records)?;
+    phase!("parse");
+    let part1 = rank(input, records)?;
+    phase!("part 1");
+    use_jokers(records);
+    let part2 = rank(input, records)?;
+    phase!("part 2");
+    return Ok((part1, part2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+32T3K 765
+T55J5 684
+KK677 28
+KTJJT 220
+QQQJA 483
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(6440));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(5905));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(6440), Answer::from(5905)));
+    }
 }
@@ -1,92 +1,339 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::{check_len, Buffer};
+use crate::error::{eat, strip_newline, Error, Result};
+use crate::explain;
+use crate::graph::{self, name_key, Graph, Names, NodeId, NAME_KEYS, NO_NODE};
+#[cfg(feature = "std")]
+use crate::lint::Assumption;
+use crate::phase;
 use num_integer;
 
-fn id(x: &[u8; 3]) -> u16 {
-    let mut out = 0;
-    for b in x {
-        match b {
-            b'A'..=b'Z' => out = 32 * out + (b - b'A' + 1) as u16,
-            _ => panic!("bad id"),
-        }
+// Each node has an edge for each step, labelled with the step, `L` then `R`.
+pub type Network<'a> = Graph<'a, u8>;
+pub type Edge = (NodeId, NodeId, u8);
+
+fn key(name: &[u8]) -> usize {
+    return name_key(name).unwrap();
+}
+
+fn parse_id<'i>(names: &mut Names<'_, 'i>, x: &'i [u8]) -> Result<NodeId> {
+    match x {
+        [b'A'..=b'Z', b'A'..=b'Z', b'A'..=b'Z'] => names.intern(key(x), x),
+        _ => Err(Error::new(x, "a node name")),
     }
-    return out;
-}
-
-fn is_end(id: u16) -> bool { id % 32 == 26 }
-
-const MAX_NODES: usize = 26 * 32 * 32 + 26 * 32 + 26 + 1;
-type Nodes = [[u16; 2]; MAX_NODES];
-
-fn read_input<'a>(
-    step_buffer: &'a mut [bool],
-    nodes: &mut Nodes,
-) -> &'a [bool] {
-    let mut buffer = [0; 14 * 1024];
-    let len = io::stdin().read(&mut buffer).unwrap();
-    if len == 0 || buffer[len - 1] != b'\n' { panic!("bad input") }
-    let input = &buffer[0..len - 1];
-    let num_steps = input.iter().position(|b| *b == b'\n').unwrap();
-    for i in 0..num_steps {
-        match input[i] {
-            b'L' => step_buffer[i] = false,
-            b'R' => step_buffer[i] = true,
-            _ => panic!("bad step"),
-        }
+}
+
+// Returns the lengths of the buffers that `solve_bytes` needs for the names and the edges. Each
+// line names at most 3 nodes and defines 2 edges.
+pub fn buffer_lens(input: &[u8]) -> (usize, usize) {
+    let lines = input.iter().filter(|b| **b == b'\n').count();
+    return ((3 * lines).min(NAME_KEYS), 2 * lines);
+}
+
+// Reads the network, using `ids` (which must have an entry for each name key) and buffers of the
+// lengths from `buffer_lens` to hold it, and returns the list of steps, which is the first line of
+// the input (each step is either `L` or `R`), along with the names and the graph of the nodes.
+pub fn read_input<'a, 'i>(
+    input: &'i [u8],
+    ids: &'a mut [NodeId],
+    names: &'a mut [&'i [u8]],
+    edges: &'a mut [Edge],
+    offsets: &'a mut [u32],
+    adjacent: &'a mut [(NodeId, u8)],
+) -> Result<(&'i [u8], Names<'a, 'i>, Network<'a>)> {
+    let input = strip_newline(input)?;
+    let Some(num_steps) = input.iter().position(|b| *b == b'\n') else {
+        return Err(Error::byte(&input[input.len()..], b'\n'));
+    };
+    if num_steps == 0 { return Err(Error::new(input, "a step ('L' or 'R')")) }
+    if let Some(i) = input[..num_steps].iter().position(|b| !matches!(b, b'L' | b'R')) {
+        return Err(Error::new(&input[i..], "a step ('L' or 'R')"));
     }
-    let body = &input[num_steps + 2..];
+    let body = eat(&input[num_steps..], "\n\n")?;
+    let mut names = Names::new(ids, names);
+    let mut edges = Buffer::new(edges);
     for line in body.split(|b| *b == b'\n') {
         match line {
-            [a1, a2, a3, b' ', b'=', b' ', b'(', b1, b2, b3, b',', b' ', c1, c2, c3, b')'] => {
-                nodes[id(&[*a1, *a2, *a3]) as usize] = [id(&[*b1, *b2, *b3]), id(&[*c1, *c2, *c3])];
+            [_, _, _, b' ', b'=', b' ', b'(', _, _, _, b',', b' ', _, _, _, b')'] => {
+                let a = parse_id(&mut names, &line[0..3])?;
+                edges.push((a, parse_id(&mut names, &line[7..10])?, b'L'), line)?;
+                edges.push((a, parse_id(&mut names, &line[12..15])?, b'R'), line)?;
             }
-            _ => panic!("bad node"),
+            _ => return Err(Error::new(line, "a node such as \"AAA = (BBB, CCC)\"")),
+        }
+    }
+    let (offsets_len, adjacent_len) = graph::buffer_lens(names.len(), edges.len(), true);
+    check_len(input, offsets, offsets_len)?;
+    check_len(input, adjacent, adjacent_len)?;
+    let graph = Graph::directed(names.len(), edges.iter().copied(), offsets, adjacent);
+    // A node which is only mentioned has no edges, but one which is defined twice has too many.
+    for u in 0..names.len() as NodeId {
+        if graph.degree(u) > 2 {
+            return Err(Error::new(&input[input.len()..], "a single definition of each node"));
         }
     }
+    return Ok((&input[..num_steps], names, graph));
+}
 
-    return &step_buffer[0..num_steps];
+// Returns the index of the edge to follow for a step: 0 for `L`, or 1 for `R`.
+fn turn(step: u8) -> usize {
+    return (step == b'R') as usize;
 }
 
-fn part1(steps: &[bool], nodes: &Nodes) -> u32 {
-    let mut next_step = 0;
+// Follows the steps from `start`, beginning with `steps[first_step]`, until reaching a node for
+// which `done` is true, and returns the number of steps taken and the node reached. The walk has
+// repeated itself once it has taken more steps than there are (node, step) pairs, so it returns
+// `None` if the end hasn't been reached by then, or if it reaches a node which isn't defined.
+fn walk(
+    steps: &[u8],
+    graph: &Network,
+    start: NodeId,
+    first_step: usize,
+    done: impl Fn(NodeId) -> bool,
+) -> Option<(u64, NodeId)> {
+    let limit = (graph.num_nodes() as u64 + 1) * steps.len() as u64;
+    let mut next_step = first_step;
     let mut num_steps = 0;
-    let mut node: u16 = id(b"AAA");
-    let end = id(b"ZZZ");
-    while node != end {
-        let d = steps[next_step] as usize;
+    let mut node = start;
+    while !done(node) {
+        if num_steps > limit { return None }
+        let d = turn(steps[next_step]);
         next_step += 1;
         if next_step >= steps.len() { next_step = 0 }
-        node = nodes[node as usize][d];
+        node = graph.edges(node).get(d)?.0;
         num_steps += 1;
     }
-    return num_steps;
-}
-
-fn part2(steps: &[bool], nodes: &Nodes) -> u64 {
-    let mut total = 1;
-    for a in b'A'..=b'Z' {
-        for b in b'A'..=b'Z' {
-            let start = id(&[a, b, b'A']);
-            if nodes[start as usize][0] == 0 { continue }
-            let mut node: u16 = start;
-            let mut num_steps = 0;
-            let mut next_step = 0;
-            while !is_end(node) {
-                let d = steps[next_step] as usize;
-                next_step += 1;
-                if next_step >= steps.len() { next_step = 0 }
-                node = nodes[node as usize][d];
-                num_steps += 1;
+    return Some((num_steps, node));
+}
+
+fn is_end(names: &Names, node: NodeId) -> bool {
+    return names.name(node)[2] == b'Z';
+}
+
+// Returns the nodes which the ghosts start from, which are those defined with names ending in `A`,
+// in order of their names.
+fn ghost_starts<'n, 'a, 'i>(
+    names: &'n Names<'a, 'i>,
+    graph: &'n Network<'a>,
+) -> impl Iterator<Item = NodeId> + use<'n, 'a, 'i> {
+    let ids = (0..NAME_KEYS).filter_map(move |key| names.get(key));
+    return ids.filter(move |id| names.name(*id)[2] == b'A' && graph.degree(*id) != 0);
+}
+
+pub fn steps_to_end(steps: &[u8], names: &Names, graph: &Network) -> Option<u64> {
+    let (start, end) = (names.get(key(b"AAA"))?, names.get(key(b"ZZZ"))?);
+    return walk(steps, graph, start, 0, |node| node == end).map(|(n, _)| n);
+}
+
+pub fn ghost_steps(steps: &[u8], names: &Names, graph: &Network) -> Option<u64> {
+    let mut total: u64 = 1;
+    for start in ghost_starts(names, graph) {
+        let (num_steps, _) = walk(steps, graph, start, 0, |node| is_end(names, node))?;
+        explain!("ghost from {} reaches an end after {} steps",
+                 core::str::from_utf8(names.name(start)).unwrap(), num_steps);
+        total = (total / num_integer::gcd(total, num_steps)).checked_mul(num_steps)?;
+    }
+    explain!("the ghosts all reach an end together after lcm = {} steps", total);
+    return Some(total);
+}
+
+// Part 1 starts from AAA, which the examples for part 2 don't have.
+fn check_start(input: &[u8], names: &Names, graph: &Network) -> Result<()> {
+    if names.get(key(b"AAA")).is_some_and(|start| graph.degree(start) != 0) { return Ok(()) }
+    return Err(Error::new(&input[input.len()..], "a node named AAA"));
+}
+
+fn to_end(input: &[u8], steps: &[u8], names: &Names, graph: &Network) -> Result<Answer> {
+    let Some(n) = steps_to_end(steps, names, graph) else {
+        return Err(Error::new(&input[input.len()..], "a path from AAA to ZZZ"));
+    };
+    return Ok(n.into());
+}
+
+// Every ghost has to reach an end, and they have to do so together within 64 bits of steps.
+fn ghosts(input: &[u8], steps: &[u8], names: &Names, graph: &Network) -> Result<Answer> {
+    let Some(n) = ghost_steps(steps, names, graph) else {
+        let rest = &input[input.len()..];
+        return Err(Error::new(rest, "ghosts which all reach an end within 64 bits of steps"));
+    };
+    return Ok(n.into());
+}
+
+// Reads the network with buffers sized from the input, and passes it to `f`.
+#[cfg(feature = "std")]
+fn with_network<T>(
+    input: &str,
+    f: impl FnOnce(&[u8], &Names, &Network) -> Result<T>,
+) -> Result<T> {
+    let input = input.as_bytes();
+    let (names_len, edges_len) = buffer_lens(input);
+    let (mut names, mut edges) = (vec![&input[..0]; names_len], vec![(0, 0, 0); edges_len]);
+    let (offsets_len, adjacent_len) = graph::buffer_lens(names_len, edges_len, true);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    let mut ids = vec![NO_NODE; NAME_KEYS];
+    let (steps, names, graph) =
+        read_input(input, &mut ids, &mut names, &mut edges, &mut offsets, &mut adjacent)?;
+    return f(steps, &names, &graph);
+}
+
+#[cfg(feature = "std")]
+fn name(names: &Names, id: NodeId) -> String {
+    return String::from_utf8_lossy(names.name(id)).into_owned();
+}
+
+// Writes the network in the DOT language, with each edge labelled with its step.
+#[cfg(feature = "std")]
+pub fn graph(input: &str) -> Result<String> {
+    return with_network(input, |_, names, graph| {
+        return Ok(graph.dot("day08", |id| name(names, id), |step| (step as char).to_string()));
+    });
+}
+
+// Part 2 takes the least common multiple of the number of steps each ghost needs to reach an end.
+// That's only the right answer if each ghost then goes around a cycle which brings it back to the
+// same end after the same number of steps again, and never passes another end on the way.
+#[cfg(feature = "std")]
+pub fn lint(input: &str) -> Result<Vec<Assumption>> {
+    return with_network(input, |steps, names, graph| {
+        let mut violation = None;
+        for start in ghost_starts(names, graph) {
+            let done = |node| is_end(names, node);
+            let Some((first, end)) = walk(steps, graph, start, 0, done) else {
+                let start = name(names, start);
+                violation = Some(format!("the ghost from {} never reaches an end", start));
+                break;
+            };
+            // Take one step off the end before looking for the next one.
+            let step = first as usize % steps.len();
+            let next = graph.edges(end).get(turn(steps[step]));
+            let again = next.and_then(|(next, _)| {
+                return walk(steps, graph, *next, (step + 1) % steps.len(), done);
+            });
+            if again != Some((first - 1, end)) {
+                let again = match again {
+                    Some((n, node)) => format!("{} after {} more steps", name(names, node), n + 1),
+                    None => "no end".to_string(),
+                };
+                violation = Some(format!("the ghost from {} reaches {} after {} steps, then {}",
+                                         name(names, start), name(names, end), first, again));
+                break;
             }
-            total = num_integer::lcm(total, num_steps);
         }
-    }
-    return total;
+        return Ok(vec![Assumption::new(
+            "each ghost returns to its first end after the same number of steps, passing no \
+             other end",
+            violation,
+        )]);
+    });
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    return with_network(input, |steps, names, graph| {
+        let input = input.as_bytes();
+        check_start(input, names, graph)?;
+        return to_end(input, steps, names, graph);
+    });
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    return with_network(input, |steps, names, graph| {
+        return ghosts(input.as_bytes(), steps, names, graph);
+    });
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let input = input.as_bytes();
+    let (names_len, edges_len) = buffer_lens(input);
+    let (mut names, mut edges) = (vec![&input[..0]; names_len], vec![(0, 0, 0); edges_len]);
+    let (offsets_len, adjacent_len) = graph::buffer_lens(names_len, edges_len, true);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    return solve_bytes(input, &mut names, &mut edges, &mut offsets, &mut adjacent);
+}
+
+// Solves both parts, using buffers of the lengths from `buffer_lens` for the names and the edges,
+// and from `graph::buffer_lens` for the directed graph of the nodes.
+pub fn solve_bytes<'i>(
+    input: &'i [u8],
+    names: &mut [&'i [u8]],
+    edges: &mut [Edge],
+    offsets: &mut [u32],
+    adjacent: &mut [(NodeId, u8)],
+) -> Result<(Answer, Answer)> {
+    let mut ids = [NO_NODE; NAME_KEYS];
+    let (steps, names, graph) = read_input(input, &mut ids, names, edges, offsets, adjacent)?;
+    check_start(input, &names, &graph)?;
+    phase!("parse");
+    let part1 = to_end(input, steps, &names, &graph)?;
+    phase!("part 1");
+    let part2 = ghosts(input, steps, &names, &graph)?;
+    phase!("part 2");
+    return Ok((part1, part2));
 }
 
-fn main() {
-    let mut step_buffer = [false; 300];
-    let mut nodes = [[0, 0]; MAX_NODES];
-    let steps = read_input(&mut step_buffer, &mut nodes);
-    print!("{}\n{}\n", part1(&steps, &nodes), part2(&steps, &nodes));
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+RL
+
+AAA = (BBB, CCC)
+BBB = (DDD, EEE)
+CCC = (ZZZ, GGG)
+DDD = (DDD, DDD)
+EEE = (EEE, EEE)
+GGG = (GGG, GGG)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    const REPEATED: &str = "\
+LLR
+
+AAA = (BBB, BBB)
+BBB = (AAA, ZZZ)
+ZZZ = (ZZZ, ZZZ)
+";
+
+    // The example for part 2 uses digits in node names, which are replaced with letters here
+    // since the real inputs only use letters. It has no AAA node, so it only works for part 2.
+    const GHOSTS: &str = "\
+LR
+
+PPA = (PPB, XXX)
+PPB = (XXX, PPZ)
+PPZ = (PPB, XXX)
+QQA = (QQB, XXX)
+QQB = (QQC, QQC)
+QQC = (QQZ, QQZ)
+QQZ = (QQB, QQB)
+XXX = (XXX, XXX)
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(2));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(2));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(2), Answer::from(2)));
+    }
+
+    #[test]
+    fn repeated() {
+        assert_eq!(part1(REPEATED).unwrap(), Answer::from(6));
+    }
+
+    #[test]
+    fn ghosts() {
+        assert_eq!(part2(GHOSTS).unwrap(), Answer::from(6));
+        assert!(part1(GHOSTS).is_err());
+    }
+
+    #[test]
+    fn lint() {
+        assert_eq!(super::lint(GHOSTS).unwrap()[0].violation, None);
+        let expected = "the ghost from AAA reaches ZZZ after 2 steps, then ZZZ after 1 more steps";
+        assert_eq!(super::lint(EXAMPLE).unwrap()[0].violation.as_deref(), Some(expected));
+    }
 }
@@ -1,29 +1,91 @@
-use std::io;
+use crate::Answer;
+use crate::buffer::Buffer;
+use crate::error::{parse_num, utf8, Error, Result};
+use crate::phase;
 
-fn extrapolate(values: &[i32]) -> (i32, i32) {
-    if values.iter().all(|x| *x == 0) { return (0, 0) }
-    let mut deltas = [0; 20];
-    for i in 1 .. values.len() {
-        deltas[i - 1] = values[i] - values[i - 1];
+// Returns the next value in the sequence, replacing the values with their differences as it goes.
+// The next value is the sum of the last value in each row of differences, down to a row of zeros.
+// Extrapolating backwards is the same as extrapolating the reversed sequence forwards.
+//
+// Each row of differences is at most twice as large as the one above, so 64 bits is enough for
+// any 21 values of 32 bits. Longer sequences can overflow, and fail if they do.
+pub fn extrapolate(values: &mut [i64]) -> Option<i64> {
+    let mut result: i64 = 0;
+    let mut n = values.len();
+    while values[..n].iter().any(|x| *x != 0) {
+        result = result.checked_add(values[n - 1])?;
+        for i in 1..n {
+            values[i - 1] = values[i].checked_sub(values[i - 1])?;
+        }
+        n -= 1;
     }
-    let (a, b) = extrapolate(&deltas[0 .. values.len() - 1]);
-    return (values[0] - a, values[values.len() - 1] + b)
-}
-
-fn main() {
-    let mut part1 = 0;
-    let mut part2 = 0;
-    for line in io::stdin().lines().map(|l| l.unwrap()) {
-        let mut n = 0;
-        let mut values = [0; 21];
-        for x in line.split_ascii_whitespace().map(|x| x.parse().unwrap()) {
-            if n >= values.len() { panic!("too many") }
-            values[n] = x;
-            n += 1;
+    return Some(result);
+}
+
+// Every value takes at least two bytes with the space before it, so this is more than the number
+// of values on any line.
+pub fn buffer_len(input: &str) -> usize {
+    return input.lines().map(|line| line.len() / 2 + 1).max().unwrap_or(0);
+}
+
+// Returns the sum of the extrapolated values for each line, in the given direction. `buffer` must
+// hold at least `buffer_len` values.
+fn sum(input: &str, backwards: bool, buffer: &mut [i64]) -> Result<i128> {
+    let mut total = 0;
+    let mut values = Buffer::new(buffer);
+    for line in input.lines() {
+        values.clear();
+        for x in line.split_ascii_whitespace() {
+            values.push(parse_num::<i32>(x)? as i64, x.as_bytes())?;
         }
-        let (a, b) = extrapolate(&values[0..n]);
-        part1 += b;
-        part2 += a;
+        if backwards { values.reverse() }
+        let Some(next) = extrapolate(&mut values) else {
+            return Err(Error::new(line.as_bytes(), "differences which fit in 64 bits"));
+        };
+        total += next as i128;
+    }
+    return Ok(total);
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    return Ok(sum(input, false, &mut vec![0; buffer_len(input)])?.into());
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    return Ok(sum(input, true, &mut vec![0; buffer_len(input)])?.into());
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    return solve_bytes(input.as_bytes(), &mut vec![0; buffer_len(input)]);
+}
+
+// Solves both parts, using a buffer of at least `buffer_len` values.
+pub fn solve_bytes(input: &[u8], values: &mut [i64]) -> Result<(Answer, Answer)> {
+    let input = utf8(input)?;
+    let part1 = sum(input, false, values)?;
+    phase!("part 1");
+    let part2 = sum(input, true, values)?;
+    phase!("part 2");
+    return Ok((part1.into(), part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+0 3 6 9 12 15
+1 3 6 10 15 21
+10 13 16 21 30 45
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(114));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(2));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(114), Answer::from(2)));
     }
-    print!("{}\n{}\n", part1, part2);
 }
@@ -1,48 +1,29 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::check_len;
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::phase;
+#[cfg(feature = "std")]
+use crate::render::{Frames, Palette};
 
-struct Input<'a> {
-    grid: &'a[u8],
-    size: (i32, i32),
+pub struct Input<'a> {
+    grid: Grid<&'a [u8]>,
     start: (i32, i32),
 }
 
 impl<'a> Input<'a> {
-    fn index(&self, x: i32, y: i32) -> usize {
-        return (y * (self.size.0 + 1) + x) as usize;
-    }
     fn cell(&self, x: i32, y: i32) -> u8 {
-        return self.grid[self.index(x, y)];
+        return self.grid[(x as usize, y as usize)];
     }
 }
 
-fn is_line(input: &[u8]) -> bool {
-    match input {
-        [line @ .., b'\n'] => line.iter().all(|b| *b != b'\n'),
-        _ => false,
-    }
-}
-
-fn read_input<'a>(buffer: &'a mut[u8]) -> Input<'a> {
-    let length = io::stdin().read(buffer).unwrap();
-    let input = &buffer[0..length];
-
-    // Validate the input.
-    if !input.is_ascii() { panic!("Input is not ASCII.") }
-    let width = input.iter().position(|b| *b == b'\n').unwrap();
-    if !input.chunks(width + 1).all(is_line) {
-        panic!("Not all lines are the same length.");
-    }
-    let height = length / (width + 1);
-    let start_index = input.iter().position(|b| *b == b'S').unwrap();
-    let start_x = (start_index % (width + 1)) as i32;
-    let start_y = (start_index / (width + 1)) as i32;
-
-    return Input{
-        grid: input,
-        size: (width as i32, height as i32),
-        start: (start_x, start_y),
+pub fn read_input<'a>(input: &'a [u8]) -> Result<Input<'a>> {
+    let grid = Grid::parse(input)?;
+    grid.validate(|c| c.is_ascii(), "ASCII")?;
+    let Some((start_x, start_y)) = grid.find(b'S') else {
+        return Err(Error::byte(&input[input.len()..], b'S'));
     };
+    return Ok(Input{grid, start: (start_x as i32, start_y as i32)});
 }
 
 struct PipeIterator<'a, 'b> {
@@ -59,7 +40,7 @@ impl<'a, 'b> Iterator for PipeIterator<'a, 'b> {
 
     fn next(&mut self) -> Option<((i32, i32), (i32, i32))> {
         if self.direction == (0, 0) { return None }
-        let (w, h) = self.input.size;
+        let (w, h) = (self.input.grid.width() as i32, self.input.grid.height() as i32);
         let (px, py) = (self.position.0 + self.direction.0,
                         self.position.1 + self.direction.1);
         if px < 0 || w <= px || py < 0 || h <= py { return None }
@@ -91,12 +72,14 @@ fn follow_pipe<'a, 'b>(
     start_direction: (i32, i32),
 ) -> PipeIterator<'a, 'b> {
     return PipeIterator{
-        input: input,
+        input,
         position: input.start,
         direction: start_direction,
     };
 }
 
+type Loop = (u32, (i32, i32), (i32, i32));
+
 // Finds the looped pipe connected to the start position.
 // Returns (steps, from_direction, start_direction), where:
 //   * steps is the number of steps taken around the loop.
@@ -106,24 +89,25 @@ fn follow_pipe<'a, 'b>(
 //     position.
 // From from_direction and start_direction, we can infer what piece of pipe is
 // in the start position.
-fn find_loop(input: &Input) -> (u32, (i32, i32), (i32, i32)) {
+fn find_loop(input: &Input) -> Result<Loop> {
     for start_direction in [(-1, 0), (1, 0), (0, -1), (0, 1)] {
         let mut steps = 0;
         for (from_direction, position) in follow_pipe(input, start_direction) {
             steps += 1;
             if position == input.start {
-                return (steps, from_direction, start_direction);
+                return Ok((steps, from_direction, start_direction));
             }
         }
     }
-    panic!("not found");
+    let start = input.grid.index((input.start.0 as usize, input.start.1 as usize));
+    return Err(Error::new(&input.grid.cells()[start..], "a start position on a loop of pipe"));
 }
 
 // Skip over a piece of pipe in a horizontal line.
 // Returns (line', is_toggle), where:
 //   * line' is the rest of the line after the bit of skipped pipe
 //   * is_toggle is whether we toggled from inside to outside or vice versa
-fn skip_pipe<'a>(line: &'a [u8]) -> (&'a [u8], bool) {
+fn skip_pipe(line: &[u8]) -> (&[u8], bool) {
     let mut line = line;
     let start = line[0];
     match start {
@@ -131,7 +115,7 @@ fn skip_pipe<'a>(line: &'a [u8]) -> (&'a [u8], bool) {
         b'L' | b'F' => {
             loop {
                 line = &line[1..];
-                if line.len() == 0 { panic!("not looped") }
+                if line.is_empty() { panic!("not looped") }
                 match line[0] {
                     b'-' => { continue },
                     b'7' => { return (&line[1..], start == b'L') },
@@ -144,21 +128,17 @@ fn skip_pipe<'a>(line: &'a [u8]) -> (&'a [u8], bool) {
     }
 }
 
-fn main() {
-    let mut buffer = [0; 141 * 140];
-    let input = read_input(&mut buffer);
-
-    // Part 1: find the loop of pipe and calculate the number of steps required
-    // to reach the furthest position (which is just half the steps required to
-    // traverse the loop, rounded down).
-    let (steps, from_direction, start_direction) = find_loop(&input);
-    let part1 = steps / 2;
-
-    // Create a copy of the grid where every bit of pipe except for the loop is
-    // replaced with a space.
-    let mut copy = [[b' '; 140]; 140];
-    for (_, (x, y)) in follow_pipe(&input, start_direction) {
-        copy[y as usize][x as usize] = input.cell(x, y);
+// Creates a copy of the grid where every bit of pipe except for the loop found by `find_loop` is
+// replaced with a space, and the start position holds the bit of pipe which must be there. The
+// copy is stored in `cells`, which must be at least as long as the input.
+fn loop_only<'a>(
+    input: &Input,
+    (from_direction, start_direction): ((i32, i32), (i32, i32)),
+    cells: &'a mut [u8],
+) -> Grid<&'a mut [u8]> {
+    let mut copy = Grid::new_in(cells, input.grid.width(), input.grid.height(), b' ');
+    for (_, (x, y)) in follow_pipe(input, start_direction) {
+        copy[(x as usize, y as usize)] = input.cell(x, y);
     }
     // Fill in the start tile with the appropriate bit of pipe.
     let start_value = match (from_direction, start_direction) {
@@ -176,32 +156,174 @@ fn main() {
         ((0, -1), (1, 0)) => b'F',
         _ => panic!("can't deduce start pipe"),
     };
-    copy[input.start.1 as usize][input.start.0 as usize] = start_value;
+    copy[(input.start.0 as usize, input.start.1 as usize)] = start_value;
+    return copy;
+}
 
+// Replaces every empty cell which is enclosed by the loop in `copy` (from `loop_only`) with
+// `fill`, and returns the number of such cells.
+fn fill_inside(copy: &mut Grid<&mut [u8]>, fill: u8) -> u32 {
     // Count the number of empty cells which are enclosed by the pipe loop. We
     // can calculate this line by line by keeping track of every time we cross
     // over the pipe and thereby toggle from inside to outside or vice versa.
     let mut num_inside = 0;
-    for y in 0 .. input.size.1 {
+    for y in 0 .. copy.height() {
         let mut inside = false;
-        let mut line = copy[y as usize].as_slice();
+        let line = copy.row_mut(y);
+        let mut x = 0;
         loop {
             // Skip to the next bit of pipe.
-            let mut spaces = 0;
-            while line.len() > 0 && line[0] == b' ' {
-                spaces += 1;
-                line = &line[1..];
+            let start = x;
+            while x < line.len() && line[x] == b' ' { x += 1 }
+            if inside {
+                num_inside += (x - start) as u32;
+                line[start..x].fill(fill);
             }
-            if inside { num_inside += spaces }
-            if line.len() == 0 { break }
+            if x == line.len() { break }
             // Skip over the bit of pipe.
-            let (rest, is_toggle) = skip_pipe(line);
-            line = rest;
+            let (rest, is_toggle) = skip_pipe(&line[x..]);
+            x = line.len() - rest.len();
             if is_toggle { inside = !inside }
         }
         if inside { panic!("not looped") }
     }
-    let part2 = num_inside;
+    return num_inside;
+}
+
+// Counts the number of empty cells which are enclosed by the loop found by `find_loop`, using
+// `cells` as in `loop_only`.
+fn enclosed(input: &Input, directions: ((i32, i32), (i32, i32)), cells: &mut [u8]) -> u32 {
+    return fill_inside(&mut loop_only(input, directions, cells), b' ');
+}
+
+#[cfg(feature = "std")]
+const PALETTE: Palette = Palette::new(&[
+    (b'|', [255, 200, 0]),
+    (b'-', [255, 200, 0]),
+    (b'L', [255, 200, 0]),
+    (b'J', [255, 200, 0]),
+    (b'7', [255, 200, 0]),
+    (b'F', [255, 200, 0]),
+    (b'I', [0, 160, 60]),
+    (b' ', [20, 20, 60]),
+]);
+
+// Draws the loop, with the cells that it encloses filled in.
+#[cfg(feature = "std")]
+pub fn draw(input: &str, frames: &mut Frames) -> Result<()> {
+    let mut cells = vec![0; input.len()];
+    let input = read_input(input.as_bytes())?;
+    let (_, from_direction, start_direction) = find_loop(&input)?;
+    let mut copy = loop_only(&input, (from_direction, start_direction), &mut cells);
+    fill_inside(&mut copy, b'I');
+    frames.emit(&copy, &PALETTE);
+    return Ok(());
+}
+
+// Part 1: find the loop of pipe and calculate the number of steps required to
+// reach the furthest position (which is just half the steps required to
+// traverse the loop, rounded down).
+pub fn part1(input: &str) -> Result<Answer> {
+    let input = read_input(input.as_bytes())?;
+    let (steps, _, _) = find_loop(&input)?;
+    return Ok((steps / 2).into());
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    let mut cells = vec![0; input.len()];
+    let input = read_input(input.as_bytes())?;
+    let (_, from_direction, start_direction) = find_loop(&input)?;
+    return Ok(enclosed(&input, (from_direction, start_direction), &mut cells).into());
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    return solve_bytes(input.as_bytes(), &mut vec![0; input.len()]);
+}
+
+// Solves both parts, using a buffer at least as long as the input for a copy of the grid.
+pub fn solve_bytes(input: &[u8], cells: &mut [u8]) -> Result<(Answer, Answer)> {
+    check_len(input, cells, input.len())?;
+    let input = read_input(input)?;
+    phase!("parse");
+    let (steps, from_direction, start_direction) = find_loop(&input)?;
+    let part1 = steps / 2;
+    phase!("part 1");
+    let part2 = enclosed(&input, (from_direction, start_direction), cells);
+    phase!("part 2");
+    return Ok((part1.into(), part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SQUARE: &str = "\
+-L|F7
+7S-7|
+L|7||
+-L-J|
+L|-JF
+";
+
+    const COMPLEX: &str = "\
+7-F7-
+.FJ|7
+SJLL7
+|F--J
+LJ.LJ
+";
+
+    const SQUEEZED: &str = "\
+...........
+.S-------7.
+.|F-----7|.
+.||.....||.
+.||.....||.
+.|L-7.F-J|.
+.|..|.|..|.
+.L--J.L--J.
+...........
+";
+
+    const LARGER: &str = "\
+.F----7F7F7F7F-7....
+.|F--7||||||||FJ....
+.||.FJ||||||||L7....
+FJL7L7LJLJ||LJ.L-7..
+L--J.L7...LJS7F-7L7.
+....F-J..F7FJ|L7L7L7
+....L7.F7||L7|.L7L7|
+.....|FJLJ|FJ|F7|.LJ
+....FJL-7.||.||||...
+....L---J.LJ.LJLJ...
+";
+
+    const JUNK: &str = "\
+FF7FSF7F7F7F7F7F---7
+L|LJ||||||||||||F--J
+FL-7LJLJ||||||LJL-77
+F--JF--7||LJLJ7F7FJ-
+L---JF-JLJ.||-FJLJJ7
+|F|F-JF---7F7-L7L|7|
+|FFJF7L7F-JF7|JL---7
+7-L-JL7||F7|L7F-7F7|
+L.L7LFJ|||||FJL7||LJ
+L7JLJL-JLJLJL--JLJ.L
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(SQUARE).unwrap(), Answer::from(4));
+        assert_eq!(part1(COMPLEX).unwrap(), Answer::from(8));
+    }
 
-    print!("{}\n{}\n", part1, part2);
+    #[test]
+    fn enclosed() {
+        assert_eq!(part2(SQUEEZED).unwrap(), Answer::from(4));
+        assert_eq!(part2(LARGER).unwrap(), Answer::from(8));
+        assert_eq!(part2(JUNK).unwrap(), Answer::from(10));
+        assert_eq!(solve(LARGER).unwrap(), (Answer::from(70), Answer::from(8)));
+    }
 }
@@ -1,110 +1,116 @@
-use std::io;
-use std::io::Read;
+use crate::{Answer, OVERFLOW};
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::phase;
+use core::iter;
 
-struct Input<'a> {
-    grid: &'a[u8],
-    size: (usize, usize),
+pub fn read_input(input: &[u8]) -> Result<Grid<&[u8]>> {
+    let grid = Grid::parse(input)?;
+    grid.validate(|c| c.is_ascii(), "ASCII")?;
+    return Ok(grid);
 }
 
-fn is_line(input: &[u8]) -> bool {
-    match input {
-        [line @ .., b'\n'] => line.iter().all(|b| *b != b'\n'),
-        _ => false,
+// Returns the sum of the differences between each pair of `values`, which must be in ascending
+// order. Each value is larger than all of the `n` values before it, so it adds `n * value` minus
+// their sum. Returns `None` if the sum overflows.
+fn sum_differences(values: impl Iterator<Item = Answer>) -> Option<Answer> {
+    let (mut total, mut sum, mut n) = (Answer::default(), Answer::default(), 0usize);
+    for value in values {
+        total = total.checked_add(value.checked_mul(n)?.checked_sub(sum)?)?;
+        sum = sum.checked_add(value)?;
+        n += 1;
     }
+    return Some(total);
 }
 
-fn read_input<'a>(buffer: &'a mut[u8]) -> Input<'a> {
-    let length = io::stdin().read(buffer).unwrap();
-    let input = &buffer[0..length];
+// Returns whether a column or row of cells is empty, and the number of galaxies in it.
+fn galaxies(cells: impl Iterator<Item = u8>) -> (bool, usize) {
+    return cells.fold((true, 0), |(empty, n), c| (empty && c == b'.', n + (c == b'#') as usize));
+}
 
-    // Validate the input.
-    if !input.is_ascii() { panic!("Input is not ASCII.") }
-    let width = input.iter().position(|b| *b == b'\n').unwrap();
-    if !input.chunks(width + 1).all(is_line) {
-        panic!("Not all lines are the same length.");
-    }
-    let height = length / (width + 1);
-    return Input{grid: input, size: (width, height)};
+// Maps the columns (or rows) of the grid, given by `galaxies`, to the coordinate of each galaxy in
+// turn, after each empty column has been expanded to `expansion` columns. There are fewer than
+// 2^64 columns, so the coordinates can't overflow 128 bits.
+fn expand(
+    columns: impl Iterator<Item = (bool, usize)>,
+    expansion: usize,
+) -> impl Iterator<Item = Answer> {
+    let coordinates = columns.scan(0, move |offset: &mut i128, (empty, n)| {
+        *offset += if empty { expansion as i128 } else { 1 };
+        return Some(iter::repeat_n(Answer::from(*offset), n));
+    });
+    return coordinates.flatten();
 }
 
-fn distance((ax, ay): (usize, usize), (bx, by): (usize, usize)) -> usize {
-    let dx = if ax < bx { bx - ax } else { ax - bx };
-    let dy = if ay < by { by - ay } else { ay - by };
-    return dx + dy;
+// Returns the sum of the distances between each pair of galaxies, after each empty row and column
+// has been expanded to `expansion` rows or columns. The distances are Manhattan distances, so the
+// columns and rows of the galaxies can be summed separately, one column or row at a time.
+// Returns `None` if the sum overflows.
+fn sum_distances(grid: &Grid<&[u8]>, expansion: usize) -> Option<Answer> {
+    let (w, h) = (grid.width(), grid.height());
+    let columns = (0 .. w).map(|x| galaxies((0 .. h).map(|y| grid[(x, y)])));
+    let rows = (0 .. h).map(|y| galaxies(grid.row(y).iter().copied()));
+    let horizontal = sum_differences(expand(columns, expansion))?;
+    return horizontal.checked_add(sum_differences(expand(rows, expansion))?);
 }
 
-fn main() {
-    let mut buffer = [0; 141 * 140];
-    let input = read_input(&mut buffer);
-    let (w, h) = input.size;
+// Like `sum_distances`, but reports an overflow as an error for the whole input.
+fn checked_sum_distances(input: &[u8], grid: &Grid<&[u8]>, expansion: usize) -> Result<Answer> {
+    return sum_distances(grid, expansion).ok_or(Error::new(input, OVERFLOW));
+}
 
-    // Identify all empty columns.
-    let mut part1_xs = [0; 140];
-    let mut part2_xs = [0; 140];
-    {
-        let mut part1_ox = 0;
-        let mut part2_ox = 0;
-        for x in 0 .. w {
-            let is_empty_column = (0 .. h)
-                .map(|y| input.grid[y * (w + 1) + x])
-                .all(|c| c == b'.');
-            if is_empty_column {
-                part1_ox += 2;
-                part2_ox += 1000000;
-            } else {
-                part1_ox += 1;
-                part2_ox += 1;
-            }
-            part1_xs[x] = part1_ox;
-            part2_xs[x] = part2_ox;
-        }
-    }
+pub fn part1(input: &str) -> Result<Answer> {
+    let input = input.as_bytes();
+    return checked_sum_distances(input, &read_input(input)?, 2);
+}
 
-    // Identify all empty rows.
-    let mut part1_ys = [0; 140];
-    let mut part2_ys = [0; 140];
-    {
-        let mut part1_oy = 0;
-        let mut part2_oy = 0;
-        for y in 0 .. h {
-            let is_empty_row = (0 .. w)
-                .map(|x| input.grid[y * (w + 1) + x])
-                .all(|c| c == b'.');
-            if is_empty_row {
-                part1_oy += 2;
-                part2_oy += 1000000;
-            } else {
-                part1_oy += 1;
-                part2_oy += 1;
-            }
-            part1_ys[y] = part1_oy;
-            part2_ys[y] = part2_oy;
-        }
-    }
+pub fn part2(input: &str) -> Result<Answer> {
+    let input = input.as_bytes();
+    return checked_sum_distances(input, &read_input(input)?, 1000000);
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    return solve_bytes(input.as_bytes());
+}
+
+pub fn solve_bytes(input: &[u8]) -> Result<(Answer, Answer)> {
+    let grid = read_input(input)?;
+    phase!("parse");
+    let part1 = checked_sum_distances(input, &grid, 2)?;
+    phase!("part 1");
+    let part2 = checked_sum_distances(input, &grid, 1000000)?;
+    phase!("part 2");
+    return Ok((part1, part2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+...#......
+.......#..
+#.........
+..........
+......#...
+.#........
+.........#
+..........
+.......#..
+#...#.....
+";
 
-    // Identify all stars, mapping their coordinates.
-    let mut star_buffer = [((0, 0), (0, 0)); 512];
-    let mut num_stars = 0;
-    for y in 0 .. h {
-        for x in 0 .. w {
-            if input.grid[y * (w + 1) + x] == b'#' {
-                let part1 = (part1_xs[x], part1_ys[y]);
-                let part2 = (part2_xs[x], part2_ys[y]);
-                star_buffer[num_stars] = (part1, part2);
-                num_stars += 1;
-            }
-        }
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(374));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(82000210));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(374), Answer::from(82000210)));
     }
-    let stars = &star_buffer[0..num_stars];
 
-    // Calculate all the distance pairs.
-    let mut part1 = 0;
-    let mut part2 = 0;
-    for i in 0 .. num_stars {
-        for j in i + 1 .. num_stars {
-            part1 += distance(stars[i].0, stars[j].0);
-            part2 += distance(stars[i].1, stars[j].1);
-        }
+    #[test]
+    fn expansion() {
+        let grid = read_input(EXAMPLE.as_bytes()).unwrap();
+        assert_eq!(sum_distances(&grid, 10), Some(Answer::from(1030)));
+        assert_eq!(sum_distances(&grid, 100), Some(Answer::from(8410)));
     }
-    print!("{}\n{}\n", part1, part2);
 }
@@ -1,40 +1,65 @@
-use std::io;
+use crate::Answer;
+use crate::buffer::too_small;
+use crate::error::{parse_num, utf8, Error, Result};
+use crate::phase;
+use core::ops::Index;
 
-const MAX_INPUT_PATTERN_LENGTH: usize = 20;
-const MAX_INPUT_GROUPS: usize = 6;
+type Ascii = [u8];
 
-const MAX_PATTERN_LENGTH: usize = MAX_INPUT_PATTERN_LENGTH * 5 + 4;
-const MAX_GROUPS: usize = MAX_INPUT_GROUPS * 5;
+// A table of arrangement counts for the suffixes of a pattern and a list of groups.
+// `table[(i, j)]` is the number of arrangements for `&pattern[i..]` and `&groups[j..]`.
+pub struct Table<'a> {
+    width: usize,
+    counts: &'a [u64],
+}
 
-type Ascii = [u8];
-type Table = [[u64; MAX_GROUPS + 1]; MAX_PATTERN_LENGTH + 1];
+impl Index<(usize, usize)> for Table<'_> {
+    type Output = u64;
+
+    fn index(&self, (i, j): (usize, usize)) -> &u64 {
+        return &self.counts[i * self.width + j];
+    }
+}
 
-fn arrangements(pattern: &Ascii, groups: &[u8]) -> Table {
+// The size of the buffer that `arrangements` needs for a pattern of length `n` and `m` groups.
+pub fn table_len(n: usize, m: usize) -> usize {
+    return (n + 1) * (m + 2);
+}
+
+// Fills in the table for `pattern` and `groups` at the start of `buffer`, which must hold
+// `table_len` counts, or returns `None` if a count doesn't fit in 64 bits.
+pub fn arrangements<'a>(
+    pattern: &Ascii,
+    groups: &[u8],
+    buffer: &'a mut [u64],
+) -> Option<Table<'a>> {
     let n = pattern.len();
     let m = groups.len();
+    // `counts[i * w + j]` is `table[(i, j)]`. Indexing the slice directly is much faster than
+    // going through a `Table` in the loops below.
+    let w = m + 1;
+    let (counts, bad_run) = buffer[..table_len(n, m)].split_at_mut((n + 1) * w);
     // `bad_run[i]` is the number of consecutive positions, starting at `i`,
     // which are either bad ('#') or unknown ('?').
-    let mut bad_run = [0; MAX_PATTERN_LENGTH + 1];
+    bad_run.fill(0);
     for i in (0 .. n).rev() {
         if pattern[i] != b'.' {
             bad_run[i] = bad_run[i + 1] + 1;
         }
     }
-    // `a[i][j]` is the number of arrangements for `&pattern[i..]` and
-    // `&groups[j..]`.
-    let mut a = [[0; MAX_GROUPS + 1]; MAX_PATTERN_LENGTH + 1];
     // The empty pattern has one matching arrangement for zero groups, and no
     // matching arrangements for any nonzero number of groups (which are covered
-    // by the default initialization to 0).
-    a[n][m] = 1;
+    // by the initialization to 0).
+    counts.fill(0);
+    counts[n * w + m] = 1;
     for i in (0..n).rev() {
-        a[i][m] = if pattern[i] == b'#' { 0 } else { a[i + 1][m] };
+        counts[i * w + m] = if pattern[i] == b'#' { 0 } else { counts[(i + 1) * w + m] };
     }
     for i in (0..n).rev() {
         if pattern[i] == b'.' {
             // Empty space: skip to the next group.
             for j in 0..m {
-                a[i][j] = a[i + 1][j];
+                counts[i * w + j] = counts[(i + 1) * w + j];
             }
         } else {
             for j in (0..m).rev() {
@@ -43,64 +68,206 @@ fn arrangements(pattern: &Ascii, groups: &[u8]) -> Table {
                 if end <= n {
                     // True if a sequence of `c` bad entries can fit before the
                     // next empty space.
-                    let can_fit = bad_run[i] >= c;
+                    let can_fit = bad_run[i] >= c as u64;
                     // True if a sequence starting at `i` could stop at `end`.
                     let can_stop = end == n || pattern[end] != b'#';
                     let mut count = 0;
                     // Calculate the number of arrangements where the `j`th
                     // sequence starts at position `i`.
                     if can_fit && can_stop {
-                        count += a[n.min(end + 1)][j + 1]
+                        count += counts[n.min(end + 1) * w + j + 1]
                     }
                     // Calculate the number of arrangements where the `j`th
                     // sequence starts strictly after position `i`.
                     if pattern[i] == b'?' {
-                        count += a[i + 1][j]
+                        count = count.checked_add(counts[(i + 1) * w + j])?;
                     }
-                    a[i][j] = count;
+                    counts[i * w + j] = count;
                 }
             }
         }
     }
-    return a;
+    return Some(Table{width: w, counts});
 }
 
-fn main() {
-    let mut part1 = 0;
-    let mut part2 = 0;
-    for line in io::stdin().lines().map(|l| l.unwrap()) {
-        // Parse the input line.
-        let (p, list) = line.split_once(" ").unwrap();
-        if !p.is_ascii() || p.len() > MAX_INPUT_PATTERN_LENGTH {
-            panic!("bad pattern");
-        }
-        let mut pattern_buffer = [0; MAX_PATTERN_LENGTH];
-        let pattern_len = p.len();
-        pattern_buffer[0..pattern_len].copy_from_slice(p.as_bytes());
-        let mut num_groups = 0;
-        let mut group_buffer = [0; MAX_GROUPS];
-        for n in list.split(",").map(|x| x.parse().unwrap()) {
-            if num_groups == MAX_INPUT_GROUPS {
-                panic!("too many groups")
+// A row of springs and the sizes of its groups of damaged springs, unfolded
+// into five copies (with patterns separated by '?').
+struct Row<'a> {
+    pattern: &'a [u8],
+    pattern_len: usize,
+    groups: &'a [u8],
+    num_groups: usize,
+}
+
+// Returns the length of the pattern and the number of groups in a line.
+fn row_size(line: &str) -> (usize, usize) {
+    let (p, list) = line.split_once(" ").unwrap_or((line, ""));
+    return (p.len(), list.split(",").count());
+}
+
+// Returns the sizes of the buffers for the unfolded rows and for their tables.
+pub fn buffer_lens(input: &str) -> (usize, usize) {
+    let (mut row_len, mut table_len) = (0, 0);
+    for line in input.lines() {
+        let (n, m) = row_size(line);
+        row_len = row_len.max(5 * n + 4 + 5 * m);
+        table_len = table_len.max(self::table_len(5 * n + 4, 5 * m));
+    }
+    return (row_len, table_len);
+}
+
+// Reads a row into `buffer`, which must hold the unfolded row from `buffer_lens`.
+fn read_row<'a>(line: &str, buffer: &'a mut [u8]) -> Result<Row<'a>> {
+    let Some((p, list)) = line.split_once(" ") else {
+        return Err(Error::byte(line.as_bytes(), b' '));
+    };
+    if let Some(i) = p.bytes().position(|b| !matches!(b, b'.' | b'#' | b'?')) {
+        return Err(Error::new(&p.as_bytes()[i..], "a spring ('.', '#', or '?')"));
+    }
+    let (pattern_len, num_groups) = row_size(line);
+    if buffer.len() < 5 * pattern_len + 4 + 5 * num_groups {
+        return Err(too_small(line.as_bytes()));
+    }
+    let (pattern, groups) = buffer.split_at_mut(5 * pattern_len + 4);
+    for (i, n) in list.split(",").enumerate() { groups[i] = parse_num(n)? }
+    for i in 1..5 { groups.copy_within(0..num_groups, i * num_groups) }
+    for (i, copy) in pattern.chunks_mut(pattern_len + 1).enumerate() {
+        copy[..pattern_len].copy_from_slice(p.as_bytes());
+        if i < 4 { copy[pattern_len] = b'?' }
+    }
+    let groups = &groups[..5 * num_groups];
+    return Ok(Row{pattern, pattern_len, groups, num_groups});
+}
+
+const TOO_MANY: &str = "fewer than 2^64 arrangements in total";
+
+// Returns the table for the given number of copies of the row, or `None` if a count doesn't fit in
+// 64 bits. `buffer` must hold the table from `buffer_lens`.
+fn count<'a>(row: &Row, copies: usize, buffer: &'a mut [u64]) -> Option<Table<'a>> {
+    let pattern = &row.pattern[0..copies * (row.pattern_len + 1) - 1];
+    let groups = &row.groups[0..copies * row.num_groups];
+    return arrangements(pattern, groups, buffer);
+}
+
+// Fails unless `table` can hold the table for the unfolded row on `line`.
+fn check_table(line: &str, table: &[u64]) -> Result<()> {
+    let (n, m) = row_size(line);
+    if table.len() < table_len(5 * n + 4, 5 * m) { return Err(too_small(line.as_bytes())) }
+    return Ok(());
+}
+
+#[cfg(feature = "std")]
+fn total(input: &str, copies: usize) -> Result<u64> {
+    let (row_len, table_len) = buffer_lens(input);
+    let (mut row_buffer, mut table) = (vec![0; row_len], vec![0; table_len]);
+    let mut total: u64 = 0;
+    for line in input.lines() {
+        let row = read_row(line, &mut row_buffer)?;
+        let count = count(&row, copies, &mut table).map(|table| table[(0, 0)]);
+        let Some(sum) = count.and_then(|n| total.checked_add(n)) else {
+            return Err(Error::new(line.as_bytes(), TOO_MANY));
+        };
+        total = sum;
+    }
+    return Ok(total);
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    return Ok(total(input, 1)?.into());
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    return Ok(total(input, 5)?.into());
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let (row_len, table_len) = buffer_lens(input);
+    let (part1, part2) =
+        solve_bytes(input.as_bytes(), &mut vec![0; row_len], &mut vec![0; table_len])?;
+    return Ok((part1, part2?));
+}
+
+// Solves both parts, using buffers of the sizes from `buffer_lens` for the rows and tables.
+// Part 2's answer is an error of its own if it doesn't fit in 64 bits, since part 1's may still.
+pub fn solve_bytes(
+    input: &[u8],
+    row: &mut [u8],
+    table: &mut [u64],
+) -> Result<(Answer, Result<Answer>)> {
+    let input = utf8(input)?;
+    let mut part1: u64 = 0;
+    let mut part2: u64 = 0;
+    // The first line whose unfolded arrangements don't fit in part 2's total.
+    let mut overflow = None;
+    for line in input.lines() {
+        let row = read_row(line, row)?;
+        let (pattern_len, num_groups) = (row.pattern_len, row.num_groups);
+        check_table(line, table)?;
+        phase!("parse");
+
+        // Compute the table of arrangement counts for different suffixes of the
+        // pattern and the list of groups. `table[(i, j)]` is the number of
+        // arrangements for `&pattern[i..]` and `&groups[j..]`. The last copy of
+        // the row gives the answer for part 1, so if the unfolded row has too
+        // many arrangements, part 1 is counted from a single copy instead.
+        let (count1, count2) = match arrangements(row.pattern, row.groups, table) {
+            Some(table) => {
+                (Some(table[(4 * pattern_len + 4, 4 * num_groups)]), Some(table[(0, 0)]))
+            }
+            None => (count(&row, 1, table).map(|table| table[(0, 0)]), None),
+        };
+        let Some(sum) = count1.and_then(|n| part1.checked_add(n)) else {
+            return Err(Error::new(line.as_bytes(), TOO_MANY));
+        };
+        part1 = sum;
+        if overflow.is_none() {
+            match count2.and_then(|n| part2.checked_add(n)) {
+                Some(sum) => part2 = sum,
+                None => overflow = Some(line),
             }
-            group_buffer[num_groups] = n;
-            num_groups += 1;
-        }
-        // Unfold the list into five copies (with patterns separated by '?').
-        for i in 1..5 {
-            group_buffer.copy_within(0..num_groups, i * num_groups);
-            pattern_buffer[i * (pattern_len + 1) - 1] = b'?';
-            pattern_buffer.copy_within(0..pattern_len, i * (pattern_len + 1));
         }
-        let pattern = &pattern_buffer[0..5 * pattern_len + 4];
-        let groups = &group_buffer[0..5 * num_groups];
+        phase!("arrangements");
+    }
+    let part2 = match overflow {
+        Some(line) => Err(Error::new(line.as_bytes(), TOO_MANY)),
+        None => Ok(part2.into()),
+    };
+    return Ok((part1.into(), part2));
+}
 
-        // Compute the table of arrangement counts for different suffixes of the
-        // pattern and the list of groups. `table[i][j]` is the number of
-        // arrangements for `&pattern[i..]` and `&groups[j..]`.
-        let table = arrangements(pattern, groups);
-        part1 += table[4 * pattern_len + 4][4 * num_groups];
-        part2 += table[0][0];
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+???.### 1,1,3
+.??..??...?##. 1,1,3
+?#?#?#?#?#?#?#? 1,3,1,6
+????.#...#... 4,1,1
+????.######..#####. 1,6,5
+?###???????? 3,2,1
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(21));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(525152));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(21), Answer::from(525152)));
+    }
+
+    #[test]
+    fn overflow() {
+        let input = format!("{} {}\n", "?".repeat(60), ["1"; 10].join(","));
+        assert_eq!(part1(&input).unwrap(), Answer::from(12777711870u64));
+        assert!(part2(&input).is_err());
+        assert!(solve(&input).is_err());
+        let (row_len, table_len) = buffer_lens(&input);
+        let (part1, part2) =
+            solve_bytes(input.as_bytes(), &mut vec![0; row_len], &mut vec![0; table_len]).unwrap();
+        assert_eq!(part1, Answer::from(12777711870u64));
+        assert!(part2.is_err());
     }
-    print!("{}\n{}\n", part1, part2);
 }
@@ -1,80 +1,109 @@
-use std::io;
-use std::io::Read;
-use std::str;
+use crate::{Answer, OVERFLOW};
+use crate::error::{utf8, Error, Result};
+use crate::grid::Grid;
+use crate::phase;
 
-fn mismatches(grid: &[u8], w: usize, h: usize) -> ([u32; 20], [u32; 20]) {
-    // `mismatches_x[i]` is the number of reflected positions which do not
-    // match for a mirror inbetween `x = i - 1` and `x = i`.
-    let mut mismatches_x = [0; 20];
-    for mirror in 1 .. w {
-        let x_min = mirror - mirror.min(w - mirror);
-        let x_max = mirror;
-        let mut count = 0;
-        for y in 0 .. h {
-            let row = &grid[y * (w + 1) ..][..w];
-            for x in x_min .. x_max {
-                let reflected = x_max + (x_max - x - 1);
-                if row[x] != row[reflected] { count += 1 }
-            }
+// The number of reflected positions which do not match for a horizontal
+// mirror inbetween `y = mirror - 1` and `y = mirror`.
+pub fn row_mismatches<T: AsRef<[u8]>>(grid: &Grid<T>, mirror: usize) -> u32 {
+    let mut count = 0;
+    for (a, b) in (0..mirror).rev().zip(mirror..grid.height()) {
+        let (a, b) = (grid.row(a), grid.row(b));
+        for x in 0 .. grid.width() {
+            if a[x] != b[x] { count += 1; }
         }
-        mismatches_x[mirror] = count;
     }
-    // `mismatches_y` is the same as `mismatches_x`, but for horizontal
-    // mirrors instead of vertical ones.
-    let mut mismatches_y = [0; 20];
-    for mirror in 1 .. h {
-        let y_min = mirror - mirror.min(h - mirror);
-        let y_max = mirror;
-        let mut count = 0;
-        for y in y_min .. y_max {
-            let r = y_max + (y_max - y - 1);
-            let a = &grid[y * (w + 1) ..][..w];
-            let b = &grid[r * (w + 1) ..][..w];
-            for x in 0 .. w {
-                if a[x] != b[x] { count += 1; }
-            }
+    return count;
+}
+
+// The number of reflected positions which do not match for a vertical mirror
+// inbetween `x = mirror - 1` and `x = mirror`.
+pub fn column_mismatches<T: AsRef<[u8]>>(grid: &Grid<T>, mirror: usize) -> u32 {
+    let mut count = 0;
+    for row in grid.rows() {
+        for (a, b) in (0..mirror).rev().zip(mirror..grid.width()) {
+            if row[a] != row[b] { count += 1; }
         }
-        mismatches_y[mirror] = count;
     }
-    return (mismatches_x, mismatches_y);
+    return count;
 }
 
-fn main() {
-    let mut buffer = [0; 20480];
-    let length = io::stdin().read(&mut buffer).unwrap();
-    if buffer[length - 1] != b'\n' { panic!("no newline at end of input") }
-    let input = str::from_utf8(&buffer[0..length - 1]).unwrap();
-    if !input.is_ascii() { panic!("input is not ascii") }
+// The mirror is the single one with exactly `smudges` mismatches: 0 for part
+// 1, and 1 for part 2.
+fn summarise(input: &str, smudges: u32) -> Result<Answer> {
+    let mut input = input.as_bytes();
+    let mut total = Answer::default();
+    loop {
+        // Each pattern ends at a blank line or at the end of the input.
+        let length = input.windows(2).position(|w| w == b"\n\n").map_or(input.len(), |i| i + 1);
+        let text = &input[..length];
+        let grid = Grid::parse(text)?;
+        grid.validate(|c| c == b'.' || c == b'#', "'.' or '#'")?;
+        let column = (1..grid.width()).find(|x| column_mismatches(&grid, *x) == smudges);
+        let summary = if let Some(x) = column {
+            Answer::from(x)
+        } else if let Some(y) = (1..grid.height()).find(|y| row_mismatches(&grid, *y) == smudges) {
+            Answer::from(100 * y as i128)
+        } else {
+            return Err(Error::new(text, "a pattern with a line of reflection"));
+        };
+        total = total.checked_add(summary).ok_or(Error::new(text, OVERFLOW))?;
 
-    let mut part1 = 0;
-    let mut part2 = 0;
-    for item in input.split("\n\n") {
-        // Get the grid dimensions and verify that the input is actually a grid.
-        let w = item.find('\n').unwrap();
-        let mut h = 0;
-        for line in item.split("\n") {
-            if line.len() != w { panic!("not a grid") }
-            h += 1;
+        // Skip the blank line before the next pattern.
+        match &input[length..] {
+            [] => break,
+            rest => input = &rest[1..],
         }
-        let (mx, my) = mismatches(item.as_bytes(), w, h);
+    }
+    return Ok(total);
+}
 
-        // Part 1: The mirror is the single entry with 0 mismatches.
-        if let Some(x) = mx[1 .. w].iter().position(|x| *x == 0) {
-            part1 += x + 1;
-        } else if let Some(y) = my[1 .. h].iter().position(|y| *y == 0) {
-            part1 += 100 * (y + 1);
-        } else {
-            panic!("no mirror");
-        }
+pub fn part1(input: &str) -> Result<Answer> {
+    return summarise(input, 0);
+}
 
-        // Part 2: The mirror is the single entry with 1 mismatch.
-        if let Some(x) = mx[1 .. w].iter().position(|x| *x == 1) {
-            part2 += x + 1;
-        } else if let Some(y) = my[1 .. h].iter().position(|y| *y == 1) {
-            part2 += 100 * (y + 1);
-        } else {
-            panic!("no mirror");
-        }
+pub fn part2(input: &str) -> Result<Answer> {
+    return summarise(input, 1);
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let part1 = summarise(input, 0)?;
+    phase!("part 1");
+    let part2 = summarise(input, 1)?;
+    phase!("part 2");
+    return Ok((part1, part2));
+}
+
+pub fn solve_bytes(input: &[u8]) -> Result<(Answer, Answer)> {
+    return solve(utf8(input)?);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.
+
+#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(405));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(400));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(405), Answer::from(400)));
     }
-    print!("{}\n{}\n", part1, part2);
 }
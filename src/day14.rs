@@ -1,13 +1,17 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::check_len;
+use crate::error::{utf8, Result};
+use crate::grid::Grid;
+use crate::phase;
+#[cfg(feature = "std")]
+use crate::render::{Frames, Palette};
+#[cfg(feature = "std")]
+use std::collections::HashSet;
 
-const MAX_SIZE: usize = 100;
-const ROW: usize = 128;
-
-fn load(grid: &[u8], size: usize) -> usize {
+pub fn load(grid: &[u8], size: usize) -> usize {
     let mut total = 0;
     for y in 0..size {
-        let row = &grid[y * ROW..][..size];
+        let row = &grid[y * size..][..size];
         total += (size - y) * row.iter().filter(|b| **b == b'O').count();
     }
     return total;
@@ -17,16 +21,16 @@ fn roll_n(grid: &mut [u8], size: usize) {
     for x in 0..size {
         let mut o = x;
         for y in 0..size {
-            let i = y * ROW + x;
+            let i = y * size + x;
             match grid[i] {
                 b'.' => {}
                 b'O' => {
                     grid[i] = b'.';
                     grid[o] = b'O';
-                    o += ROW;
+                    o += size;
                 }
                 b'#' => {
-                    o = (y + 1) * ROW + x;
+                    o = (y + 1) * size + x;
                 }
                 _ => panic!("bad contents"),
             }
@@ -37,7 +41,7 @@ fn roll_n(grid: &mut [u8], size: usize) {
 fn roll_e(grid: &mut [u8], size: usize) {
     for y in 0..size {
         let mut o = size;
-        let row = &mut grid[y * ROW..][..size];
+        let row = &mut grid[y * size..][..size];
         for x in (0..size).rev() {
             match row[x] {
                 b'.' => {}
@@ -57,18 +61,18 @@ fn roll_e(grid: &mut [u8], size: usize) {
 
 fn roll_s(grid: &mut [u8], size: usize) {
     for x in 0..size {
-        let mut o = size * ROW + x;
+        let mut o = size * size + x;
         for y in (0..size).rev() {
-            let i = y * ROW + x;
+            let i = y * size + x;
             match grid[i] {
                 b'.' => {}
                 b'O' => {
-                    o -= ROW;
+                    o -= size;
                     grid[i] = b'.';
                     grid[o] = b'O';
                 }
                 b'#' => {
-                    o = y * ROW + x;
+                    o = y * size + x;
                 }
                 _ => panic!("bad contents"),
             }
@@ -79,7 +83,7 @@ fn roll_s(grid: &mut [u8], size: usize) {
 fn roll_w(grid: &mut [u8], size: usize) {
     for y in 0..size {
         let mut o = 0;
-        let row = &mut grid[y * ROW..][..size];
+        let row = &mut grid[y * size..][..size];
         for x in 0..size {
             match row[x] {
                 b'.' => {}
@@ -97,65 +101,138 @@ fn roll_w(grid: &mut [u8], size: usize) {
     }
 }
 
-fn cycle(grid: &mut [u8], size: usize) {
+pub fn cycle(grid: &mut [u8], size: usize) {
     roll_n(grid, size);
     roll_w(grid, size);
     roll_s(grid, size);
     roll_e(grid, size);
 }
 
-fn main() {
-    let mut buffer = [0; MAX_SIZE * ROW];
-    let length = io::stdin().read(&mut buffer).unwrap();
-    if length == 0 || buffer[length - 1] != b'\n' {
-        panic!("no newline")
-    }
-    let size = buffer[0..length].iter().position(|b| *b == b'\n').unwrap();
-    if size > MAX_SIZE {
-        panic!("too wide")
-    }
-    // Verify that the input is a grid.
-    let mut height = 0;
-    for line in buffer[0..length - 1].split(|b| *b == b'\n') {
-        if line.len() != size {
-            panic!("not a grid")
-        }
-        height += 1;
-    }
-    if height != size {
-        panic!("not square")
-    }
-    // Align the grid.
-    for y in (0..size).rev() {
-        let from = y * (size + 1);
-        buffer.copy_within(from..from + size, y * ROW);
-    }
+pub fn read_input(input: &str) -> Result<Grid<&[u8]>> {
+    let grid = Grid::parse(input.as_bytes())?;
+    grid.validate(|c| matches!(c, b'.' | b'O' | b'#'), "'.', 'O', or '#'")?;
+    grid.check_square()?;
+    return Ok(grid);
+}
 
-    // Roll all the stones North.
-    roll_n(&mut buffer, size);
-    let part1 = load(&buffer, size);
-    roll_w(&mut buffer, size);
-    roll_s(&mut buffer, size);
-    roll_e(&mut buffer, size);
-
-    let mut hare = buffer;
-    cycle(&mut hare, size);
-
-    for i in 1..1000000000 {
-        if buffer == hare {
-            // Cycles i and 2i are the same, so the 1e9'th cycle will look the
-            // same as the (i + 1e9 % i)th.
-            for _ in 0 .. 1000000000 % i {
-                cycle(&mut buffer, size);
+// The size of the buffer that `tilted_load` and `spun_load` need for the copies of the platform.
+pub fn buffer_len<T: AsRef<[u8]>>(grid: &Grid<T>) -> usize {
+    return 2 * grid.width() * grid.height();
+}
+
+// Rolls all the stones North and returns the load.
+pub fn tilted_load<T: AsRef<[u8]>>(grid: &Grid<T>, cells: &mut [u8]) -> usize {
+    let size = grid.width();
+    let mut grid = grid.copy_into(cells);
+    roll_n(grid.cells_mut(), size);
+    return load(grid.cells(), size);
+}
+
+// Returns the load after the given number of spin cycles, using Floyd's
+// algorithm to detect where the cycles start repeating.
+pub fn spun_load<T: AsRef<[u8]>>(grid: &Grid<T>, cycles: usize, cells: &mut [u8]) -> usize {
+    let size = grid.width();
+    let (grid_cells, hare_cells) = cells.split_at_mut(size * size);
+    let mut grid = grid.copy_into(grid_cells);
+    if cycles == 0 { return load(grid.cells(), size) }
+    cycle(grid.cells_mut(), size);
+
+    let mut hare = grid.copy_into(hare_cells);
+    cycle(hare.cells_mut(), size);
+
+    for i in 1..cycles {
+        if grid == hare {
+            // Cycles i and 2i are the same, so the nth cycle will look the
+            // same as the (i + n % i)th.
+            for _ in 0 .. cycles % i {
+                cycle(grid.cells_mut(), size);
             }
             break;
         }
 
-        cycle(&mut buffer, size);
-        cycle(&mut hare, size);
-        cycle(&mut hare, size);
+        cycle(grid.cells_mut(), size);
+        cycle(hare.cells_mut(), size);
+        cycle(hare.cells_mut(), size);
+    }
+
+    return load(grid.cells(), size);
+}
+
+#[cfg(feature = "std")]
+const PALETTE: Palette = Palette::new(&[
+    (b'O', [230, 120, 30]),
+    (b'#', [110, 110, 110]),
+    (b'.', [20, 20, 20]),
+]);
+
+// Animates the spin cycles, with a frame after each tilt, until the platform returns to a state
+// that it has been in before.
+#[cfg(feature = "std")]
+pub fn draw(input: &str, frames: &mut Frames) -> Result<()> {
+    let mut grid = read_input(input)?.to_owned();
+    let size = grid.width();
+    frames.emit(&grid, &PALETTE);
+    let mut seen = HashSet::new();
+    while seen.insert(grid.cells().to_vec()) {
+        for roll in [roll_n, roll_w, roll_s, roll_e] {
+            roll(grid.cells_mut(), size);
+            frames.emit(&grid, &PALETTE);
+        }
     }
+    return Ok(());
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    let grid = read_input(input)?;
+    return Ok(tilted_load(&grid, &mut vec![0; buffer_len(&grid)]).into());
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    let grid = read_input(input)?;
+    return Ok(spun_load(&grid, 1000000000, &mut vec![0; buffer_len(&grid)]).into());
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let cells = &mut vec![0; buffer_len(&read_input(input)?)];
+    return solve_bytes(input.as_bytes(), cells);
+}
+
+// Solves both parts, using a buffer of the size from `buffer_len` for the copies of the platform.
+pub fn solve_bytes(input: &[u8], cells: &mut [u8]) -> Result<(Answer, Answer)> {
+    let grid = read_input(utf8(input)?)?;
+    check_len(input, cells, buffer_len(&grid))?;
+    phase!("parse");
+    let part1 = tilted_load(&grid, cells);
+    phase!("part 1");
+    let part2 = spun_load(&grid, 1000000000, cells);
+    phase!("part 2");
+    return Ok((part1.into(), part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let part2 = load(&buffer, size);
-    print!("{}\n{}\n", part1, part2);
+    const EXAMPLE: &str = "\
+O....#....
+O.OO#....#
+.....##...
+OO.#O....O
+.O.....O#.
+O.#..O.#.#
+..O..#O..O
+.......O..
+#....###..
+#OO..#....
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(136));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(64));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(136), Answer::from(64)));
+    }
 }
@@ -1,7 +1,9 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::Buffer;
+use crate::error::{strip_newline, Error, Result};
+use crate::phase;
 
-fn hash(a: &[u8]) -> usize {
+pub fn hash(a: &[u8]) -> usize {
     let mut value: u8 = 0;
     for x in a {
         value = value.wrapping_add(*x);
@@ -10,62 +12,156 @@ fn hash(a: &[u8]) -> usize {
     return value as usize;
 }
 
-const BUCKET_SIZE: usize = 8;
-
 type Power = u8;
-type Record<'a> = (&'a [u8], Power);
-
-fn set<'a>(bucket: &mut [Record<'a>; BUCKET_SIZE], label: &'a [u8], power: Power) {
-    for i in 0..BUCKET_SIZE {
-        if bucket[i].0 == label {
-            bucket[i].1 = power;
-            return;
-        } else if bucket[i].0.len() == 0 {
-            bucket[i] = (label, power);
-            return;
+
+const NONE: usize = usize::MAX;
+
+// A lens in one of the boxes, with the index of the next lens in the same box.
+#[derive(Clone, Copy, Default)]
+pub struct Lens<'a> {
+    label: &'a [u8],
+    power: Power,
+    next: usize,
+}
+
+// Each box is a linked list of the lenses in it, which are kept in a pool provided by the caller.
+// Removed lenses are unlinked but not reused, so the pool needs a lens for each step.
+struct Boxes<'a, 'b> {
+    heads: [usize; 256],
+    lenses: Buffer<'b, Lens<'a>>,
+}
+
+impl<'a, 'b> Boxes<'a, 'b> {
+    fn new(lenses: &'b mut [Lens<'a>]) -> Boxes<'a, 'b> {
+        return Boxes{heads: [NONE; 256], lenses: Buffer::new(lenses)};
+    }
+
+    // Points the link after `previous` (or the head of the box if there isn't one) at `lens`.
+    fn link(&mut self, h: usize, previous: usize, lens: usize) {
+        if previous == NONE { self.heads[h] = lens } else { self.lenses[previous].next = lens }
+    }
+
+    // Sets the power of the lens with the given label, adding it to the end of the box if it isn't
+    // there already.
+    fn set(&mut self, label: &'a [u8], power: Power, entry: &[u8]) -> Result<()> {
+        let h = hash(label);
+        let (mut previous, mut i) = (NONE, self.heads[h]);
+        while i != NONE {
+            if self.lenses[i].label == label {
+                self.lenses[i].power = power;
+                return Ok(());
+            }
+            (previous, i) = (i, self.lenses[i].next);
+        }
+        let lens = self.lenses.len();
+        self.lenses.push(Lens{label, power, next: NONE}, entry)?;
+        self.link(h, previous, lens);
+        return Ok(());
+    }
+
+    fn remove(&mut self, label: &[u8]) {
+        let h = hash(label);
+        let (mut previous, mut i) = (NONE, self.heads[h]);
+        while i != NONE {
+            let next = self.lenses[i].next;
+            if self.lenses[i].label == label {
+                self.link(h, previous, next);
+                return;
+            }
+            (previous, i) = (i, next);
         }
     }
-    panic!("bucket full");
-}
 
-fn remove(bucket: &mut [Record; BUCKET_SIZE], label: &[u8]) {
-    if let Some(i) = bucket.iter().position(|r| r.0 == label) {
-        for j in i + 1..BUCKET_SIZE {
-            bucket[j - 1] = bucket[j];
+    fn apply(&mut self, entry: &'a [u8]) -> Result<()> {
+        match parse_step(entry)? {
+            (label, Some(power)) => self.set(label, power, entry)?,
+            (label, None) => self.remove(label),
+        }
+        return Ok(());
+    }
+
+    fn focusing_power(&self) -> usize {
+        let mut total = 0;
+        for (b, head) in self.heads.iter().enumerate() {
+            let (mut s, mut i) = (0, *head);
+            while i != NONE {
+                total += (1 + b) * (1 + s) * self.lenses[i].power as usize;
+                (s, i) = (s + 1, self.lenses[i].next);
+            }
         }
-        bucket[BUCKET_SIZE - 1] = (&[], 0);
+        return total;
     }
 }
 
-fn main() {
-    let mut buffer = [0; 24 * 1024];
-    let length = io::stdin().read(&mut buffer).unwrap();
-    if length == 0 || buffer[length - 1] != b'\n' {
-        panic!("bad input")
+fn is_label(label: &[u8]) -> bool {
+    return !label.is_empty() && label.iter().all(|b| b.is_ascii_lowercase());
+}
+
+// Splits a step into its label and the focal length it sets, if any.
+fn parse_step(entry: &[u8]) -> Result<(&[u8], Option<Power>)> {
+    match entry {
+        [label @ .., b'=', x @ b'1'..=b'9'] if is_label(label) => Ok((label, Some(x - b'0'))),
+        [label @ .., b'-'] if is_label(label) => Ok((label, None)),
+        _ => Err(Error::new(entry, "a step such as \"rn=1\" or \"cm-\"")),
     }
-    let input = &buffer[0..length - 1];
+}
+
+// The size of the pool of lenses, which is the number of steps.
+pub fn buffer_len(input: &str) -> usize {
+    return input.split(',').count();
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    let input = strip_newline(input.as_bytes())?;
+    let mut total = 0;
+    for entry in input.split(|b| *b == b',') {
+        parse_step(entry)?;
+        total += hash(entry);
+    }
+    return Ok(total.into());
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    let mut lenses = vec![Lens::default(); buffer_len(input)];
+    let mut boxes = Boxes::new(&mut lenses);
+    let input = strip_newline(input.as_bytes())?;
+    for entry in input.split(|b| *b == b',') { boxes.apply(entry)? }
+    return Ok(boxes.focusing_power().into());
+}
 
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    return solve_bytes(input.as_bytes(), &mut vec![Lens::default(); buffer_len(input)]);
+}
+
+// Solves both parts, using a pool of lenses of the size from `buffer_len`.
+pub fn solve_bytes<'a>(input: &'a [u8], lenses: &mut [Lens<'a>]) -> Result<(Answer, Answer)> {
+    let input = strip_newline(input)?;
     let mut part1 = 0;
-    let mut buckets: [[Record; BUCKET_SIZE]; 256] = [[(&[], 0); BUCKET_SIZE]; 256];
+    let mut boxes = Boxes::new(lenses);
     for entry in input.split(|b| *b == b',') {
+        boxes.apply(entry)?;
         part1 += hash(entry);
-        match entry {
-            [label @ .., b'=', x] => {
-                let h = hash(label);
-                set(&mut buckets[h], label, x - b'0');
-            }
-            [label @ .., b'-'] => {
-                let h = hash(label);
-                remove(&mut buckets[h], label);
-            }
-            _ => panic!("bad entry"),
-        }
     }
-    let mut part2 = 0;
-    for b in 0..256 {
-        for s in 0..BUCKET_SIZE {
-            part2 += (1 + b) * (1 + s) * buckets[b][s].1 as usize;
-        }
+    phase!("steps");
+    let part2 = boxes.focusing_power();
+    phase!("focusing power");
+    return Ok((part1.into(), part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(1320));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(145));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(1320), Answer::from(145)));
     }
-    print!("{}\n{}\n", part1, part2);
 }
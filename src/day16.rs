@@ -1,11 +1,13 @@
-use std::io;
-use std::io::Read;
-
-const MAX_SIZE: usize = 110;
-const BUFFER_SIZE: usize = (MAX_SIZE + 1) * MAX_SIZE;
+use crate::Answer;
+use crate::buffer::check_len;
+use crate::error::{utf8, Result};
+use crate::grid::Grid;
+use crate::phase;
+#[cfg(feature = "std")]
+use crate::render::{Frames, Palette};
 
 #[derive(Copy, Clone)]
-enum Direction {
+pub enum Direction {
     Up = 1,
     Down = 2,
     Left = 4,
@@ -66,33 +68,42 @@ fn energise(
     }
 }
 
-fn energised(grid: &[u8], size: usize, start: (usize, usize), direction: Direction) -> usize {
-    let mut seen = [0; BUFFER_SIZE];
-    energise(grid, size, &mut seen, start, direction);
+// Returns the number of tiles energised by a beam, using `seen` (which must hold `size * size`
+// bytes) to record the directions in which the beam has passed through each one.
+pub fn energised(
+    grid: &[u8],
+    size: usize,
+    seen: &mut [u8],
+    start: (usize, usize),
+    direction: Direction,
+) -> usize {
+    let seen = &mut seen[..size * size];
+    seen.fill(0);
+    energise(grid, size, seen, start, direction);
     return seen.iter().filter(|d| **d != 0).count();
 }
 
-fn main() {
-    let mut buffer = [0; BUFFER_SIZE];
-    let length = io::stdin().read(&mut buffer).unwrap();
-    if length == 0 || buffer[length - 1] != b'\n' { panic!("no newline") }
-    let size = buffer[0..length].iter().position(|b| *b == b'\n').unwrap();
-    if size > MAX_SIZE { panic!("too wide") }
-    // Verify that the input is a grid.
-    let mut height = 0;
-    for line in buffer[0..length - 1].split(|b| *b == b'\n') {
-        if line.len() != size { panic!("not a grid") }
-        height += 1;
-    }
-    if height != size { panic!("not square") }
-    // Align the grid.
-    for y in 0..size {
-        let from = y * (size + 1);
-        buffer.copy_within(from..from + size, y * size);
-    }
+pub fn read_input(input: &str) -> Result<Grid<&[u8]>> {
+    let grid = Grid::parse(input.as_bytes())?;
+    let valid = |c| matches!(c, b'.' | b'/' | b'\\' | b'|' | b'-');
+    grid.validate(valid, "'.', '/', '\\', '|', or '-'")?;
+    grid.check_square()?;
+    return Ok(grid);
+}
 
-    let part1 = energised(&buffer, size, (0, 0), Direction::Right);
-    let mut part2 = 0;
+// The size of the buffer for a copy of the grid without its newlines and the directions seen.
+pub fn buffer_len<T: AsRef<[u8]>>(grid: &Grid<T>) -> usize {
+    return 2 * grid.width() * grid.height();
+}
+
+// Returns the tiles energised by the beam from part 1, and the most tiles that can be energised by
+// a beam entering from any edge. `cells` must hold `buffer_len` bytes.
+fn energised_tiles(grid: &Grid<&[u8]>, cells: &mut [u8]) -> (usize, usize) {
+    let size = grid.width();
+    let (copy, seen) = cells.split_at_mut(size * size);
+    let grid = grid.copy_into(copy);
+    let grid = grid.cells();
+    let mut best = 0;
     for i in 0..size {
         for (start, direction) in [
             ((0, i), Direction::Right),
@@ -100,9 +111,87 @@ fn main() {
             ((size - 1, i), Direction::Left),
             ((i, size - 1), Direction::Up),
         ] {
-            let e = energised(&buffer, size, start, direction);
-            if e > part2 { part2 = e }
+            let e = energised(grid, size, seen, start, direction);
+            if e > best { best = e }
         }
     }
-    print!("{}\n{}\n", part1, part2);
+    return (energised(grid, size, seen, (0, 0), Direction::Right), best);
+}
+
+#[cfg(feature = "std")]
+const PALETTE: Palette = Palette::new(&[
+    (b'.', [20, 20, 40]),
+    (b'#', [255, 230, 120]),
+    (b'm', [90, 90, 140]),
+    (b'M', [255, 120, 40]),
+]);
+
+// Draws the tiles energised by the beam from part 1, with mirrors and splitters highlighted
+// where the beam passes through them.
+#[cfg(feature = "std")]
+pub fn draw(input: &str, frames: &mut Frames) -> Result<()> {
+    let grid = read_input(input)?.to_owned();
+    let size = grid.width();
+    let mut seen = vec![0; size * size];
+    energise(grid.cells(), size, &mut seen, (0, 0), Direction::Right);
+    let mut picture = grid.map(|c| if c == b'.' { b'.' } else { b'm' });
+    for (cell, seen) in picture.cells_mut().iter_mut().zip(seen) {
+        if seen == 0 { continue }
+        *cell = if *cell == b'.' { b'#' } else { b'M' };
+    }
+    frames.emit(&picture, &PALETTE);
+    return Ok(());
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    let grid = read_input(input)?.to_owned();
+    let seen = &mut vec![0; grid.width() * grid.height()];
+    return Ok(energised(grid.cells(), grid.width(), seen, (0, 0), Direction::Right).into());
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    return Ok(solve(input)?.1);
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let cells = &mut vec![0; buffer_len(&read_input(input)?)];
+    return solve_bytes(input.as_bytes(), cells);
+}
+
+// Solves both parts, using a buffer of the size from `buffer_len`.
+pub fn solve_bytes(input: &[u8], cells: &mut [u8]) -> Result<(Answer, Answer)> {
+    let grid = read_input(utf8(input)?)?;
+    check_len(input, cells, buffer_len(&grid))?;
+    phase!("parse");
+    let (part1, part2) = energised_tiles(&grid, cells);
+    phase!("beams");
+    return Ok((part1.into(), part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+.|...\\....
+|.-.\\.....
+.....|-...
+........|.
+..........
+.........\\
+..../.\\\\..
+.-.-/..|..
+.|....-|.\\
+..//.|....
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(46));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(51));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(46), Answer::from(51)));
+    }
 }
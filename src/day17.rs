@@ -1,13 +1,16 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::check_len;
+use crate::error::{utf8, Error, Result};
+use crate::grid::Grid;
+use crate::phase;
+#[cfg(feature = "std")]
+use crate::render::{Frames, Palette};
 
-const MAX_SIZE: usize = 141;
-const BUFFER_SIZE: usize = (MAX_SIZE + 1) * MAX_SIZE;
-const QUEUE_SIZE: usize = 64000;
+// Positions are stored as a pair of 16-bit coordinates.
+const MAX_SIZE: usize = 1 << 16;
 
-#[derive(Copy, Clone, Default)]
+#[derive(Copy, Clone)]
 enum Direction {
-    #[default]
     Up = 1,
     Down = 2,
     Left = 4,
@@ -15,6 +18,10 @@ enum Direction {
 }
 
 impl Direction {
+    // The position of the direction in `DIRECTIONS`.
+    fn index(&self) -> usize {
+        return (*self as u8).trailing_zeros() as usize;
+    }
     fn left(&self) -> Direction {
         match self {
             Direction::Up => Direction::Left,
@@ -31,7 +38,7 @@ impl Direction {
             Direction::Right => Direction::Down,
         }
     }
-    fn go(&self, n: i16, (x, y): (i16, i16)) -> (i16, i16) {
+    fn go(&self, n: i32, (x, y): (i32, i32)) -> (i32, i32) {
         match self {
             Direction::Up => (x, y - n),
             Direction::Down => (x, y + n),
@@ -41,140 +48,302 @@ impl Direction {
     }
 }
 
-#[derive(Copy, Clone, Default)]
-struct QueueEntry {
-    heuristic_cost: u16,
-    cost: u16,
-    position: (u8, u8),
-    direction: Direction,
+const DIRECTIONS: [Direction; 4] =
+    [Direction::Up, Direction::Down, Direction::Left, Direction::Right];
+
+const NONE: usize = usize::MAX;
+
+// The queue has a slot for each state: a cell, and the direction in which the crucible leaves it.
+// The slot holds the least cost found for the state and, while it is queued, its neighbours in its
+// bucket's list.
+#[derive(Copy, Clone)]
+pub struct Slot {
+    cost: u32,
+    previous: usize,
+    next: usize,
 }
 
-type Queue = [QueueEntry; QUEUE_SIZE];
+impl Default for Slot {
+    fn default() -> Slot {
+        return Slot{cost: u32::MAX, previous: NONE, next: NONE};
+    }
+}
 
-fn pop(queue: &mut Queue, queue_size: &mut usize) -> QueueEntry {
-    let result = queue[0];
-    *queue_size -= 1;
-    let x = queue[*queue_size];
-    let mut i = 0;
-    loop {
-        let l = 2 * i + 1;
-        let r = 2 * i + 2;
-        if l >= *queue_size { break }
-        let mut c = l;
-        if r < *queue_size && queue[l].heuristic_cost > queue[r].heuristic_cost { c = r }
-        if x.heuristic_cost <= queue[c].heuristic_cost { break }
-        queue[i] = queue[c];
-        i = c;
+// States are popped in order of heuristic cost. The heuristic is consistent, so that cost never
+// decreases from one pop to the next, and a move of up to 10 steps raises it by at most 100. This
+// means the states can be kept in a ring of buckets indexed by heuristic cost. Each state is queued
+// at most once: finding a cheaper path to a queued state moves it to a cheaper bucket.
+const NUM_BUCKETS: usize = 128;
+
+struct Queue<'a> {
+    heads: [usize; NUM_BUCKETS],
+    slots: &'a mut [Slot],
+    // The heuristic cost of the states in the bucket which is being emptied.
+    current: u32,
+    len: usize,
+}
+
+impl<'a> Queue<'a> {
+    fn new(slots: &'a mut [Slot], current: u32) -> Queue<'a> {
+        slots.fill(Slot::default());
+        return Queue{heads: [NONE; NUM_BUCKETS], slots, current, len: 0};
     }
-    queue[i] = x;
-    return result;
-}
-
-fn push(queue: &mut Queue, queue_size: &mut usize, entry: QueueEntry) {
-    let mut i = *queue_size;
-    *queue_size += 1;
-    while i != 0 {
-        let parent = (i - 1) / 2;
-        if queue[parent].heuristic_cost <= entry.heuristic_cost { break }
-        queue[i] = queue[parent];
-        i = parent;
+
+    fn unlink(&mut self, state: usize, bucket: usize) {
+        let Slot{previous, next, ..} = self.slots[state];
+        if previous == NONE { self.heads[bucket] = next } else { self.slots[previous].next = next }
+        if next != NONE { self.slots[next].previous = previous }
     }
-    queue[i] = entry;
-}
-
-fn manhattan_distance((ax, ay): (u8, u8), (bx, by): (u8, u8)) -> u16 {
-    let dx = if ax < bx { bx - ax } else { ax - bx };
-    let dy = if ay < by { by - ay } else { ay - by };
-    return dx as u16 + dy as u16;
-}
-
-fn solve(
-    grid: &[u8],
-    size: usize,
-    min_steps: i16,
-    max_steps: i16,
-) -> u16 {
-    let mut seen = [0; BUFFER_SIZE];
-    let mut queue: Queue = [Default::default(); QUEUE_SIZE];
-    let end = ((size - 1) as u8, (size - 1) as u8);
-    let end_index = size * size - 1;
-    queue[0] = QueueEntry{
-        heuristic_cost: manhattan_distance((0, 0), end),
-        cost: 0,
-        position: (0, 0),
-        direction: Direction::Right,
-    };
-    queue[1] = queue[0];
-    queue[1].direction = Direction::Down;
-    let mut queue_size = 2;
+
+    // Pops a state with the least heuristic cost, returning it along with its cost.
+    fn pop(&mut self) -> Option<(usize, u32)> {
+        if self.len == 0 { return None }
+        loop {
+            let bucket = self.current as usize % NUM_BUCKETS;
+            let state = self.heads[bucket];
+            if state != NONE {
+                self.unlink(state, bucket);
+                self.len -= 1;
+                return Some((state, self.slots[state].cost));
+            }
+            self.current += 1;
+        }
+    }
+
+    // Queues a state with the given cost, unless it is already queued with one at least as low.
+    fn push(&mut self, state: usize, cost: u32, heuristic_cost: u32) {
+        let old_cost = self.slots[state].cost;
+        if old_cost <= cost { return }
+        if old_cost == u32::MAX {
+            self.len += 1;
+        } else {
+            self.unlink(state, (old_cost - cost + heuristic_cost) as usize % NUM_BUCKETS);
+        }
+        let bucket = heuristic_cost as usize % NUM_BUCKETS;
+        let next = self.heads[bucket];
+        if next != NONE { self.slots[next].previous = state }
+        self.slots[state] = Slot{cost, previous: NONE, next};
+        self.heads[bucket] = state;
+    }
+}
+
+// Costs are kept in 32 bits, below `u32::MAX`, which marks the slots of states not yet reached.
+const TOO_HOT: &str = "a grid whose heat losses add up to less than 2^32";
+
+// Adds heat loss to a cost, failing if the total doesn't fit.
+fn add(cost: u32, heat_loss: u32) -> Result<u32, &'static str> {
+    return cost.checked_add(heat_loss).filter(|total| *total < u32::MAX).ok_or(TOO_HOT);
+}
+
+fn manhattan_distance((ax, ay): (u16, u16), (bx, by): (u16, u16)) -> u32 {
+    let dx = ax.abs_diff(bx);
+    let dy = ay.abs_diff(by);
+    return dx as u32 + dy as u32;
+}
+
+// The sizes of the buffers that `solve_bytes` needs: bytes for the heat loss and the directions
+// seen at each cell, and the queue's slots.
+pub fn buffer_lens<T: AsRef<[u8]>>(grid: &Grid<T>) -> (usize, usize) {
+    let cells = grid.width() * grid.height();
+    return (2 * cells, 4 * cells);
+}
+
+// Returns the least heat loss on the way to the bottom-right corner, or what the search needed
+// from the grid if it couldn't find one. `grid` holds the heat loss of each cell, and the
+// directions in which each cell has been left are recorded in `seen`, which must hold a byte for
+// each cell. `slots` must hold four for each cell.
+pub fn search(
+    grid: &Grid<&mut [u8]>,
+    min_steps: i32,
+    max_steps: i32,
+    seen: &mut [u8],
+    slots: &mut [Slot],
+) -> Result<u32, &'static str> {
+    let (w, h) = (grid.width(), grid.height());
+    let cells = grid.cells();
+    let seen = &mut seen[..w * h];
+    seen.fill(0);
+    let end = ((w - 1) as u16, (h - 1) as u16);
+    let end_index = w * h - 1;
+    let mut queue = Queue::new(&mut slots[..4 * w * h], manhattan_distance((0, 0), end));
+    queue.push(Direction::Right.index(), 0, queue.current);
+    queue.push(Direction::Down.index(), 0, queue.current);
     loop {
-        // Pop the lowest cost entry from the queue.
-        if queue_size == 0 { panic!("not found") }
-        let entry = pop(&mut queue, &mut queue_size);
-        let (x, y) = (entry.position.0 as i16, entry.position.1 as i16);
-        let index = y as usize * size + x as usize;
-        if index == end_index { return entry.cost }
-        if seen[index] & entry.direction as u8 != 0 { continue }
-        seen[index] |= entry.direction as u8;
-
-        // We are at the position indicated by `entry.index`, facing in the direction indicated by
-        // `entry.direction`, and we must now make a number of forward steps within the range
+        // Pop the lowest cost state from the queue.
+        let Some((state, cost)) = queue.pop() else {
+            return Err("a path to the bottom-right corner");
+        };
+        let index = state / 4;
+        let direction = DIRECTIONS[state % 4];
+        let (x, y) = ((index % w) as i32, (index / w) as i32);
+        if index == end_index { return Ok(cost) }
+        seen[index] |= direction as u8;
+
+        // We are at the position indicated by `index`, facing in the direction indicated by
+        // `direction`, and we must now make a number of forward steps within the range
         // `steps`.
-        let (x2, y2) = entry.direction.go(min_steps, (x, y));
-        if x2 < 0 || size as i16 <= x2 || y2 < 0 || size as i16 <= y2 { continue }
-        let mut cost = entry.cost;
+        let (x2, y2) = direction.go(min_steps, (x, y));
+        if x2 < 0 || w as i32 <= x2 || y2 < 0 || h as i32 <= y2 { continue }
+        let mut cost = cost;
         // Calculate the unconditionally paid cost from taking the minimum acceptable number of
         // steps.
         for i in 1..min_steps {
-            let (x2, y2) = entry.direction.go(i, (x, y));
-            cost += grid[y2 as usize * size + x2 as usize] as u16;
+            let (x2, y2) = direction.go(i, (x, y));
+            cost = add(cost, cells[y2 as usize * w + x2 as usize] as u32)?;
         }
         // Enqueue left and right turns at all possible stopping locations.
         for i in min_steps..max_steps+1 {
-            let (x2, y2) = entry.direction.go(i, (x, y));
-            if x2 < 0 || size as i16 <= x2 || y2 < 0 || size as i16 <= y2 { break }
-            let next_index = y2 as usize * size + x2 as usize;
-            cost += grid[next_index] as u16;
-            let heuristic_cost = cost + manhattan_distance((x2 as u8, y2 as u8), end);
-            let mut next = QueueEntry{
-                heuristic_cost: heuristic_cost,
-                cost: cost,
-                position: (x2 as u8, y2 as u8),
-                direction: entry.direction.left(),
-            };
-            if seen[next_index] & next.direction as u8 == 0 {
-                push(&mut queue, &mut queue_size, next);
-            }
-            next.direction = entry.direction.right();
-            if seen[next_index] & next.direction as u8 == 0 {
-                push(&mut queue, &mut queue_size, next);
+            let (x2, y2) = direction.go(i, (x, y));
+            if x2 < 0 || w as i32 <= x2 || y2 < 0 || h as i32 <= y2 { break }
+            let next_index = y2 as usize * w + x2 as usize;
+            cost = add(cost, cells[next_index] as u32)?;
+            let heuristic_cost = add(cost, manhattan_distance((x2 as u16, y2 as u16), end))?;
+            for next in [direction.left(), direction.right()] {
+                if seen[next_index] & next as u8 != 0 { continue }
+                queue.push(4 * next_index + next.index(), cost, heuristic_cost);
             }
         }
     }
 }
 
-fn main() {
-    let mut buffer = [0; BUFFER_SIZE];
-    let length = io::stdin().read(&mut buffer).unwrap();
-    if length == 0 || buffer[length - 1] != b'\n' { panic!("no newline") }
-    let size = buffer[0..length].iter().position(|b| *b == b'\n').unwrap();
-    if size > MAX_SIZE { panic!("too wide") }
-    // Verify that the input is a grid.
-    let mut height = 0;
-    for line in buffer[0..length - 1].split(|b| *b == b'\n') {
-        if line.len() != size { panic!("not a grid") }
-        height += 1;
+// Returns the grid, checking that it holds only digits.
+pub fn read_input(input: &str) -> Result<Grid<&[u8]>> {
+    let grid = Grid::parse(input.as_bytes())?;
+    grid.validate(|c| (b'1'..=b'9').contains(&c), "a digit from 1 to 9")?;
+    if grid.width() > MAX_SIZE {
+        return Err(Error::new(&grid.row(0)[MAX_SIZE..], "at most 65536 columns"));
+    }
+    if grid.height() > MAX_SIZE {
+        return Err(Error::new(grid.row(MAX_SIZE), "at most 65536 lines"));
     }
-    if height != size { panic!("not square") }
-    // Compact the grid.
-    for y in 0..size {
-        let from = y * (size + 1);
-        buffer.copy_within(from..from + size, y * size);
+    return Ok(grid);
+}
+
+// Cells are drawn in grey by their heat loss, or in red once the search has visited them.
+#[cfg(feature = "std")]
+const PALETTE: Palette = Palette::new(&[
+    (1, [20, 20, 20]), (2, [40, 40, 40]), (3, [60, 60, 60]),
+    (4, [80, 80, 80]), (5, [100, 100, 100]), (6, [120, 120, 120]),
+    (7, [140, 140, 140]), (8, [160, 160, 160]), (9, [180, 180, 180]),
+    (11, [90, 10, 10]), (12, [110, 20, 15]), (13, [130, 30, 20]),
+    (14, [150, 40, 25]), (15, [170, 50, 30]), (16, [190, 60, 35]),
+    (17, [210, 70, 40]), (18, [230, 80, 45]), (19, [250, 90, 50]),
+]);
+
+// Draws the cells explored by the search for each part.
+#[cfg(feature = "std")]
+pub fn draw(input: &str, frames: &mut Frames) -> Result<()> {
+    let grid = read_input(input)?;
+    let (w, h) = (grid.width(), grid.height());
+    let mut cells = vec![0; w * h];
+    let grid = grid.map_into(&mut cells, |c| c - b'0');
+    let mut slots = vec![Slot::default(); 4 * w * h];
+    for (min_steps, max_steps) in [(1, 3), (4, 10)] {
+        let mut seen = vec![0; w * h];
+        if let Err(expected) = search(&grid, min_steps, max_steps, &mut seen, &mut slots) {
+            return Err(Error::new(&input.as_bytes()[input.len()..], expected));
+        }
+        let mut picture = grid.to_owned();
+        for (cell, seen) in picture.cells_mut().iter_mut().zip(seen) {
+            if seen != 0 { *cell += 10 }
+        }
+        frames.emit(&picture, &PALETTE);
     }
-    // Map all cells to their integer values.
-    for c in &mut buffer[0 .. size * size] { *c -= b'0' }
+    return Ok(());
+}
+
+// Returns the least heat loss with moves of `min_steps` to `max_steps`, using buffers of the sizes
+// from `buffer_lens`.
+fn least_heat_loss(
+    input: &[u8],
+    (min_steps, max_steps): (i32, i32),
+    cells: &mut [u8],
+    slots: &mut [Slot],
+) -> Result<Answer> {
+    let grid = read_input(utf8(input)?)?;
+    let (cells_len, slots_len) = buffer_lens(&grid);
+    check_len(input, cells, cells_len)?;
+    check_len(input, slots, slots_len)?;
+    let (copy, seen) = cells.split_at_mut(cells_len / 2);
+    let grid = grid.map_into(copy, |c| c - b'0');
+    phase!("parse");
+    let result = search(&grid, min_steps, max_steps, seen, slots);
+    phase!(if max_steps == 3 { "part 1" } else { "part 2" });
+    return match result {
+        Ok(cost) => Ok(cost.into()),
+        Err(expected) => Err(Error::new(&input[input.len()..], expected)),
+    };
+}
 
-    let part1 = solve(&buffer, size, 1, 3);
-    let part2 = solve(&buffer, size, 4, 10);
-    print!("{}\n{}\n", part1, part2);
+// Runs `least_heat_loss` with buffers sized from the input.
+#[cfg(feature = "std")]
+fn least_heat_loss_with_buffers(input: &str, steps: (i32, i32)) -> Result<Answer> {
+    let (cells_len, slots_len) = buffer_lens(&read_input(input)?);
+    let (mut cells, mut slots) = (vec![0; cells_len], vec![Slot::default(); slots_len]);
+    return least_heat_loss(input.as_bytes(), steps, &mut cells, &mut slots);
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    return least_heat_loss_with_buffers(input, (1, 3));
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    return least_heat_loss_with_buffers(input, (4, 10));
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let (cells_len, slots_len) = buffer_lens(&read_input(input)?);
+    let (mut cells, mut slots) = (vec![0; cells_len], vec![Slot::default(); slots_len]);
+    return solve_bytes(input.as_bytes(), &mut cells, &mut slots);
+}
+
+// Solves both parts, using buffers of the sizes from `buffer_lens`.
+pub fn solve_bytes(input: &[u8], cells: &mut [u8], slots: &mut [Slot]) -> Result<(Answer, Answer)> {
+    let part1 = least_heat_loss(input, (1, 3), cells, slots)?;
+    let part2 = least_heat_loss(input, (4, 10), cells, slots)?;
+    return Ok((part1, part2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533
+";
+
+    const UNFORTUNATE: &str = "\
+111111111111
+999999999991
+999999999991
+999999999991
+999999999991
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(102));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(94));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(102), Answer::from(94)));
+    }
+
+    #[test]
+    fn unfortunate() {
+        assert_eq!(part2(UNFORTUNATE).unwrap(), Answer::from(71));
+    }
 }
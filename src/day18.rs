@@ -1,84 +1,161 @@
-use std::io;
-use std::io::Read;
+use crate::{Answer, OVERFLOW};
+use crate::error::{strip_newline, Error, Result};
+use crate::phase;
 
-enum Direction {
+pub enum Direction {
     Right = 0,
     Down = 1,
     Left = 2,
     Up = 3,
 }
 
-fn parse(line: &[u8]) -> (Direction, i64, Direction, i64) {
+pub fn parse(line: &[u8]) -> Result<(Direction, i64, Direction, i64)> {
     match line {
-        [direction, b' ', n @ .., b' ', b'(', b'#', a, b, c, d, e, f, b')'] => {
+        [direction, b' ', n @ .., b' ', b'(', b'#', _, _, _, _, _, _, b')'] => {
+            let color = &line[line.len() - 7..][..6];
             let part1_direction = match direction {
                 b'R' => Direction::Right,
                 b'D' => Direction::Down,
                 b'L' => Direction::Left,
                 b'U' => Direction::Up,
-                _ => panic!("bad direction"),
+                _ => return Err(Error::new(line, "a direction ('R', 'D', 'L', or 'U')")),
             };
+            if n.is_empty() || n.len() > 9 || !n.iter().all(|x| x.is_ascii_digit()) {
+                return Err(Error::new(n, "a number"));
+            }
             let mut part1_amount = 0;
             for x in n {
-                if !(b'0' <= *x && *x <= b'9') { panic!("bad amount") }
                 part1_amount = 10 * part1_amount + (x - b'0') as i64;
             }
-            let part2_direction = match f {
+            let part2_direction = match color[5] {
                 b'0' => Direction::Right,
                 b'1' => Direction::Down,
                 b'2' => Direction::Left,
                 b'3' => Direction::Up,
-                _ => panic!("bad color"),
+                _ => return Err(Error::new(&color[5..], "a direction ('0', '1', '2', or '3')")),
             };
             let mut part2_amount = 0;
-            for x in [a, b, c, d, e] {
+            for (i, x) in color[..5].iter().enumerate() {
                 match x {
                     b'0'..=b'9' => part2_amount = 16 * part2_amount + (x - b'0') as i64,
                     b'a'..=b'f' => part2_amount = 16 * part2_amount + 10 + (x - b'a') as i64,
-                    _ => panic!("bad color"),
+                    _ => return Err(Error::new(&color[i..], "a hex digit")),
                 }
             }
-            return (part1_direction, part1_amount, part2_direction, part2_amount);
+            return Ok((part1_direction, part1_amount, part2_direction, part2_amount));
         },
-        _ => panic!("bad line"),
+        _ => return Err(Error::new(line, "a line such as \"R 6 (#70c710)\"")),
     }
 }
 
+// The lagoon is measured in 128 bits. There are fewer than 2^64 lines, each of which digs less than
+// 2^30 metres, so only the integral can overflow, and it is checked.
 #[derive(Default)]
 struct Shoelace {
-    y: i64,
-    integral: i64,
-    perimeter: i64,
+    y: i128,
+    integral: i128,
+    perimeter: i128,
 }
 
 impl Shoelace {
-    fn go(&mut self, direction: Direction, amount: i64) {
+    // Digs `amount` metres in the given direction, or returns `None` if the integral overflows.
+    fn go(&mut self, direction: Direction, amount: i64) -> Option<()> {
+        let amount = amount as i128;
         self.perimeter += amount;
         match direction {
             Direction::Up => self.y -= amount,
             Direction::Down => self.y += amount,
-            Direction::Left => self.integral -= self.y * amount,
-            Direction::Right => self.integral += self.y * amount,
+            Direction::Left => self.integral = self.integral.checked_sub(self.y * amount)?,
+            Direction::Right => self.integral = self.integral.checked_add(self.y * amount)?,
         }
+        return Some(());
     }
 
-    fn area(&self) -> i64 {
-        return self.integral.abs() + self.perimeter / 2 + 1;
+    fn area(&self) -> Option<Answer> {
+        return Answer::from(self.integral.checked_abs()?).checked_add(self.perimeter / 2 + 1);
     }
 }
 
 
-fn main() {
-    let mut buffer = [0; 10 * 1024];
-    let length = io::stdin().read(&mut buffer).unwrap();
-    if length == 0 || buffer[length - 1] != b'\n' { panic!("bad input") }
-    let input = &buffer[0 .. length - 1];
+// Returns the area dug out by following either the plain instructions (part 1)
+// or the ones hidden in the colour codes (part 2).
+fn dig(input: &str, use_colours: bool) -> Result<Answer> {
+    let input = strip_newline(input.as_bytes())?;
+    let mut lagoon: Shoelace = Default::default();
+    for line in input.split(|b| *b == b'\n') {
+        let (p1d, p1a, p2d, p2a) = parse(line)?;
+        let dug = if use_colours { lagoon.go(p2d, p2a) } else { lagoon.go(p1d, p1a) };
+        dug.ok_or(Error::new(line, OVERFLOW))?;
+    }
+    return lagoon.area().ok_or(Error::new(input, OVERFLOW));
+}
+
+pub fn part1(input: &str) -> Result<Answer> {
+    return dig(input, false);
+}
+
+pub fn part2(input: &str) -> Result<Answer> {
+    return dig(input, true);
+}
+
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    return solve_bytes(input.as_bytes());
+}
+
+pub fn solve_bytes(input: &[u8]) -> Result<(Answer, Answer)> {
+    let input = strip_newline(input)?;
 
     let mut part1: Shoelace = Default::default();
     let mut part2: Shoelace = Default::default();
-    for (p1d, p1a, p2d, p2a) in input.split(|b| *b == b'\n').map(parse) {
-        part1.go(p1d, p1a);
-        part2.go(p2d, p2a);
+    for line in input.split(|b| *b == b'\n') {
+        let (p1d, p1a, p2d, p2a) = parse(line)?;
+        let overflow = Error::new(line, OVERFLOW);
+        part1.go(p1d, p1a).ok_or(overflow)?;
+        part2.go(p2d, p2a).ok_or(overflow)?;
+    }
+    phase!("dig plan");
+    let overflow = Error::new(input, OVERFLOW);
+    return Ok((part1.area().ok_or(overflow)?, part2.area().ok_or(overflow)?));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+R 6 (#70c710)
+D 5 (#0dc571)
+L 2 (#5713f0)
+D 2 (#d2c081)
+R 2 (#59c680)
+D 2 (#411b91)
+L 5 (#8ceee2)
+U 2 (#caa173)
+L 1 (#1b58a2)
+U 2 (#caa171)
+R 2 (#7807d2)
+U 3 (#a77fa3)
+L 2 (#015232)
+U 2 (#7a21e3)
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(62));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(952408144115u64));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(62), Answer::from(952408144115u64)));
+    }
+
+    #[test]
+    fn large_area() {
+        // A rectangle a billion metres across and ten billion metres down, whose integral doesn't
+        // fit in an `i64`.
+        let input = [
+            "D 999999999 (#000000)\n".repeat(10),
+            "R 999999999 (#000000)\n".to_string(),
+            "U 999999999 (#000000)\n".repeat(10),
+            "L 999999999 (#000000)\n".to_string(),
+        ].concat();
+        assert_eq!(part1(&input).unwrap(), Answer::from(9999999991000000000u64));
     }
-    print!("{}\n{}\n", part1.area(), part2.area());
 }
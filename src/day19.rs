@@ -1,55 +1,63 @@
-use std::io;
-use std::io::Read;
+use crate::{Answer, OVERFLOW};
+use crate::buffer::Buffer;
+use crate::error::{eat, strip_newline, Error, Result};
+use crate::phase;
 
-const MAX_OPS: usize = 2048;
 const MAX_WORKFLOW_ID: u16 = 26 * (26 * 26 + 26) + 26;  // zzz
 
 #[derive(Copy, Clone)]
-enum Category { X, M, A, S }
+pub enum Category { X, M, A, S }
 #[derive(Copy, Clone)]
-enum Action { Accept(), Reject(), Delegate(u16) }
+pub enum Action { Accept(), Reject(), Delegate(u32) }
 #[derive(Copy, Clone)]
-enum Op { IfLess(Category, u16, Action), IfMore(Category, u16, Action), Unconditionally(Action) }
-type WorkflowId = u16;
+pub enum Op {
+    IfLess(Category, u16, Action),
+    IfMore(Category, u16, Action),
+    Unconditionally(Action),
+}
+pub type WorkflowId = u16;
 
-fn read_workflow_name(input: &mut &[u8]) -> WorkflowId {
+fn read_workflow_name(input: &mut &[u8]) -> Result<WorkflowId> {
     let mut index = 0;
     let mut count = 0;
-    while let c @ b'a'..=b'z' = input[count] {
+    while let Some(c @ b'a'..=b'z') = input.get(count) {
         count += 1;
+        if count > 3 { return Err(Error::new(input, "a workflow name of at most 3 letters")) }
         index = 26 * index + (c - b'a' + 1) as WorkflowId;
     }
-    if count == 0 { panic!("expected workflow name") }
+    if count == 0 { return Err(Error::new(input, "a workflow name")) }
     *input = &input[count..];
-    return index;
+    return Ok(index);
 }
 
-fn read_int(input: &mut &[u8]) -> u16 {
-    let mut result = 0;
+fn read_int(input: &mut &[u8]) -> Result<u16> {
+    let mut result: u16 = 0;
     let mut count = 0;
-    // No need to check for the end of the string (no valid input ends with an int).
-    while let c @ (b'0'..=b'9') = input[count] {
-        result = 10 * result + (c - b'0') as u16;
+    while let Some(c @ b'0'..=b'9') = input.get(count) {
+        let digit = (c - b'0') as u16;
+        let Some(x) = result.checked_mul(10).and_then(|x| x.checked_add(digit)) else {
+            return Err(Error::new(input, "a number below 65536"));
+        };
+        result = x;
         count += 1;
     }
-    if count == 0 { panic!("expected int") }
+    if count == 0 { return Err(Error::new(input, "a number")) }
     *input = &input[count..];
-    return result;
+    return Ok(result);
 }
 
-fn read_action(input: &mut &[u8]) -> Action {
+fn read_action(input: &mut &[u8]) -> Result<Action> {
     match input {
-        [b'A', ..] => { *input = &input[1..]; Action::Accept() },
-        [b'R', ..] => { *input = &input[1..]; Action::Reject() },
-        _ => { Action::Delegate(read_workflow_name(input)) },
+        [b'A', ..] => { *input = &input[1..]; Ok(Action::Accept()) },
+        [b'R', ..] => { *input = &input[1..]; Ok(Action::Reject()) },
+        _ => { Ok(Action::Delegate(read_workflow_name(input)? as u32)) },
     }
 }
 
-fn read_workflow(ops: &mut [Op], num_ops: &mut usize, input: &mut &[u8]) -> (WorkflowId, u16) {
-    let id = read_workflow_name(input);
-    let start = *num_ops;
-    if input[0] != b'{' { panic!("syntax") }
-    *input = &input[1..];
+fn read_workflow(ops: &mut Buffer<Op>, input: &mut &[u8]) -> Result<(WorkflowId, u32)> {
+    let id = read_workflow_name(input)?;
+    let start = ops.len();
+    *input = eat(input, "{")?;
     // Parse the list of operations for the workflow.
     loop {
         match input {
@@ -60,82 +68,137 @@ fn read_workflow(ops: &mut [Op], num_ops: &mut usize, input: &mut &[u8]) -> (Wor
                     b'm' => Category::M,
                     b'a' => Category::A,
                     b's' => Category::S,
-                    _ => panic!("bad variable"),
+                    _ => return Err(Error::new(input, "a category ('x', 'm', 'a', or 's')")),
                 };
                 *input = tail;
-                let threshold = read_int(input);
-                if input[0] != b':' { panic!("bad workflow") }
-                *input = &input[1..];
-                let action = read_action(input);
-                ops[*num_ops] = match op {
+                let threshold = read_int(input)?;
+                *input = eat(input, ":")?;
+                let action = read_action(input)?;
+                let op = match op {
                     b'<' => Op::IfLess(category, threshold, action),
                     b'>' => Op::IfMore(category, threshold, action),
                     _ => panic!("bad op"),
                 };
-                *num_ops += 1;
+                ops.push(op, input)?;
             },
             // Match an unconditional step.
             _ => {
-                ops[*num_ops] = Op::Unconditionally(read_action(input));
-                *num_ops += 1;
+                let op = Op::Unconditionally(read_action(input)?);
+                ops.push(op, input)?;
             }
         }
-        match input[0] {
-            b',' => {},
-            b'}' => { break },
-            _ => panic!("bad line"),
+        match input.first() {
+            Some(b',') => {},
+            Some(b'}') => { break },
+            _ => return Err(Error::new(input, "',' or '}'")),
         }
         *input = &input[1..];
     }
+    // Every workflow must end by doing something, rather than falling through to the next one.
+    if !matches!(ops[ops.len() - 1], Op::Unconditionally(_)) {
+        return Err(Error::new(input, "an unconditional rule at the end of the workflow"));
+    }
     *input = &input[1..];
-    return (id, start as u16);
+    return Ok((id, start as u32));
 }
 
-fn read_workflows<'a>(ops: &'a mut [Op], input: &mut &[u8]) -> (&'a [Op], usize) {
-    let mut num_ops = 0;
+#[derive(Copy, Clone, PartialEq)]
+enum Visit { New, InProgress, Done }
+
+// Checks that no part can be passed around the workflows starting with `id` forever.
+fn is_acyclic(ops: &[Op], workflows: &[u32], id: usize, visited: &mut [Visit]) -> bool {
+    match visited[id] {
+        Visit::New => visited[id] = Visit::InProgress,
+        Visit::InProgress => return false,
+        Visit::Done => return true,
+    }
+    let mut i = workflows[id] as usize;
+    loop {
+        let (action, is_last) = match ops[i] {
+            Op::IfLess(_, _, action) | Op::IfMore(_, _, action) => (action, false),
+            Op::Unconditionally(action) => (action, true),
+        };
+        if let Action::Delegate(j) = action {
+            if !is_acyclic(ops, workflows, j as usize, visited) { return false }
+        }
+        if is_last { break }
+        i += 1;
+    }
+    visited[id] = Visit::Done;
+    return true;
+}
+
+fn action(op: &mut Op) -> &mut Action {
+    match op {
+        Op::IfLess(_, _, a) => a,
+        Op::IfMore(_, _, a) => a,
+        Op::Unconditionally(a) => a,
+    }
+}
+
+// Returns the number of operations in the workflows, which is the size of the buffer which
+// `read_workflows` needs for them.
+pub fn buffer_len(input: &str) -> usize {
+    let workflows = input.split("\n\n").next().unwrap_or("");
+    return workflows.bytes().filter(|b| matches!(b, b'{' | b',')).count();
+}
+
+pub fn read_workflows<'a>(ops: &'a mut [Op], input: &mut &[u8]) -> Result<(&'a [Op], usize)> {
+    let mut ops = Buffer::new(ops);
 
     // `workflows[id]` is a value `offset` where `id` is derived from the workflow name and `offset`
     // is the index of the first operation of the workflow in `ops`.
     let mut workflows = [0; MAX_WORKFLOW_ID as usize + 1];
+    let mut defined = [false; MAX_WORKFLOW_ID as usize + 1];
 
-    while input[0] != b'\n' {
-        let (id, offset) = read_workflow(ops, &mut num_ops, input);
+    while input.first() != Some(&b'\n') {
+        let (id, offset) = read_workflow(&mut ops, input)?;
         workflows[id as usize] = offset;
-        if input[0] != b'\n' { panic!("trailing bytes") }
-        *input = &input[1..];
+        defined[id as usize] = true;
+        *input = eat(input, "\n")?;
     }
+    let end = *input;
     *input = &input[1..];
 
-    // Rewrite all `Delegate(id)` entries to `Delegate(offset)` entries.
-    for op in &mut ops[0..num_ops] {
-        let action = match op {
-            Op::IfLess(_, _, a) => a,
-            Op::IfMore(_, _, a) => a,
-            Op::Unconditionally(a) => a,
-        };
-        if let Action::Delegate(x) = action { *x = workflows[*x as usize] }
+    for op in ops.iter_mut() {
+        if let Action::Delegate(x) = *action(op) {
+            if !defined[x as usize] {
+                return Err(Error::new(end, "a definition for every workflow which is used"));
+            }
+        }
     }
 
     // Identify the starting position.
-    let start_id: WorkflowId = read_workflow_name(&mut "in.".as_bytes());
-    let start = workflows[start_id as usize];
-    return (&ops[0..num_ops], start as usize);
+    let start_id: WorkflowId = read_workflow_name(&mut "in.".as_bytes())?;
+    if !defined[start_id as usize] { return Err(Error::new(end, "a workflow named \"in\"")) }
+    let mut visited = [Visit::New; MAX_WORKFLOW_ID as usize + 1];
+    if !is_acyclic(&ops, &workflows, start_id as usize, &mut visited) {
+        return Err(Error::new(end, "workflows which never send a part back to an earlier one"));
+    }
+
+    // Rewrite all `Delegate(id)` entries to `Delegate(offset)` entries.
+    for op in ops.iter_mut() {
+        if let Action::Delegate(x) = action(op) {
+            *x = workflows[*x as usize];
+        }
+    }
+    return Ok((ops.into_slice(), workflows[start_id as usize] as usize));
 }
 
-fn parse_part(mut text: &[u8]) -> [u16; 4] {
-    text = text.strip_prefix(b"{x=").unwrap();
-    let x = read_int(&mut text);
-    text = text.strip_prefix(b",m=").unwrap();
-    let m = read_int(&mut text);
-    text = text.strip_prefix(b",a=").unwrap();
-    let a = read_int(&mut text);
-    text = text.strip_prefix(b",s=").unwrap();
-    let s = read_int(&mut text);
-    if text != b"}" { panic!("bad part") }
-    return [x, m, a, s];
+pub fn parse_part(mut text: &[u8]) -> Result<[u16; 4]> {
+    text = eat(text, "{x=")?;
+    let x = read_int(&mut text)?;
+    text = eat(text, ",m=")?;
+    let m = read_int(&mut text)?;
+    text = eat(text, ",a=")?;
+    let a = read_int(&mut text)?;
+    text = eat(text, ",s=")?;
+    let s = read_int(&mut text)?;
+    if text != b"}" { return Err(Error::token(text, "}")) }
+    return Ok([x, m, a, s]);
 }
 
-fn run(ops: &[Op], start: usize, part: [u16; 4]) -> bool {
+pub fn run(ops: &[Op], start: usize, part: [u16; 4]) -> bool {
     let mut i = start;
     loop {
         let (should_act, action) = match ops[i] {
@@ -158,6 +221,7 @@ fn run(ops: &[Op], start: usize, part: [u16; 4]) -> bool {
 fn count(part: [(u16, u16); 4]) -> u64 {
     let mut total = 1;
     for (a, b) in part {
+        if a > b { return 0 }
         total *= (b - a) as u64 + 1;
     }
     return total;
@@ -171,7 +235,7 @@ fn act(ops: &[Op], part: [(u16, u16); 4], action: Action) -> u64 {
     }
 }
 
-fn eval(ops: &[Op], mut i: usize, mut part: [(u16, u16); 4]) -> u64 {
+pub fn eval(ops: &[Op], mut i: usize, mut part: [(u16, u16); 4]) -> u64 {
     let mut accepted = 0;
     loop {
         match ops[i] {
@@ -201,28 +265,95 @@ fn eval(ops: &[Op], mut i: usize, mut part: [(u16, u16); 4]) -> u64 {
     }
 }
 
-fn main() {
-    // Read the input into a buffer.
-    let mut buffer = [0; 20 * 1024];
-    let len = io::stdin().read(&mut buffer).unwrap();
-    if len == 0 || buffer[len - 1] != b'\n' { panic!("bad input") }
-    let mut input = &buffer[0 .. len - 1];
-
-    // Compile the workflows.
-    let mut op_buffer = [Op::Unconditionally(Action::Accept()); MAX_OPS];
-    let (ops, start) = read_workflows(&mut op_buffer, &mut input);
-
-    // Process the parts for part 1.
-    let mut part1: u32 = 0;
-    for part_text in input.split(|b| *b == b'\n') {
-        let part = parse_part(part_text);
+// Part 1: returns the total rating of all the parts which are accepted.
+fn total_rating(ops: &[Op], start: usize, parts: &[u8]) -> Result<Answer> {
+    let mut total = Answer::default();
+    for part_text in parts.split(|b| *b == b'\n') {
+        let part = parse_part(part_text)?;
         if run(ops, start, part) {
-            part1 += part[0] as u32 + part[1] as u32 + part[2] as u32 + part[3] as u32;
+            let rating = part[0] as u32 + part[1] as u32 + part[2] as u32 + part[3] as u32;
+            total = total.checked_add(rating).ok_or(Error::new(part_text, OVERFLOW))?;
         }
     }
+    return Ok(total);
+}
 
-    // Calculate the hypothetical part count for part 2.
-    let part2 = eval(ops, start, [(1, 4000); 4]);
+// Part 2: calculates the hypothetical part count.
+fn combinations(ops: &[Op], start: usize) -> u64 {
+    return eval(ops, start, [(1, 4000); 4]);
+}
+
+// Allocates the buffer for the workflows' operations.
+#[cfg(feature = "std")]
+fn op_buffer(input: &str) -> Vec<Op> {
+    return vec![Op::Unconditionally(Action::Reject()); buffer_len(input)];
+}
 
-    print!("{}\n{}\n", part1, part2);
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    let mut op_buffer = op_buffer(input);
+    let mut input = strip_newline(input.as_bytes())?;
+    let (ops, start) = read_workflows(&mut op_buffer, &mut input)?;
+    return total_rating(ops, start, input);
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    let mut op_buffer = op_buffer(input);
+    let mut input = strip_newline(input.as_bytes())?;
+    let (ops, start) = read_workflows(&mut op_buffer, &mut input)?;
+    return Ok(combinations(ops, start).into());
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    return solve_bytes(input.as_bytes(), &mut op_buffer(input));
+}
+
+// Solves both parts, using a buffer of the size from `buffer_len` for the workflows' operations.
+pub fn solve_bytes(input: &[u8], op_buffer: &mut [Op]) -> Result<(Answer, Answer)> {
+    let mut input = strip_newline(input)?;
+
+    // Compile the workflows.
+    let (ops, start) = read_workflows(op_buffer, &mut input)?;
+    phase!("workflows");
+
+    let part1 = total_rating(ops, start, input)?;
+    phase!("part 1");
+    let part2 = combinations(ops, start);
+    phase!("part 2");
+    return Ok((part1, part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+px{a<2006:qkq,m>2090:A,rfg}
+pv{a>1716:R,A}
+lnx{m>1548:A,A}
+rfg{s<537:gd,x>2440:R,A}
+qs{s>3448:A,lnx}
+qkq{x<1416:A,crn}
+crn{x>2662:A,R}
+in{s<1351:px,qqz}
+qqz{s>2770:qs,m<1801:hdj,R}
+gd{a>3333:R,R}
+hdj{m>838:A,pv}
+
+{x=787,m=2655,a=1222,s=2876}
+{x=1679,m=44,a=2067,s=496}
+{x=2036,m=264,a=79,s=2244}
+{x=2461,m=1339,a=466,s=1291}
+{x=2127,m=1623,a=2188,s=1013}
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(19114));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(167409079868000u64));
+        let expected = (Answer::from(19114), Answer::from(167409079868000u64));
+        assert_eq!(solve(EXAMPLE).unwrap(), expected);
+    }
 }
@@ -1,74 +1,88 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::{check_len, Buffer};
+use crate::error::{eat, strip_newline, Error, Result};
+use crate::explain;
+use crate::graph::{name_key, Graph, Names, NodeId, NO_NODE};
+#[cfg(feature = "std")]
+use crate::lint::Assumption;
+use crate::phase;
 use num_integer;
 
-const MAX_EDGES: usize = 256;
-const MAX_NODES: usize = 64;
+// Every module other than the broadcaster has a name of 2 letters, so there are at most this many.
+pub const MAX_NODES: usize = 26 * 26 + 1;
 
-type NodeId = u8;
-const NULL_NODE: NodeId = MAX_NODES as NodeId;
+// The broadcaster has the name key 0, and the others have keys below this.
+const MAX_KEYS: usize = 27 * 27;
 
-#[derive(Copy, Clone)]
-struct Node<'a> {
-    kind: u8,
-    outs: &'a [NodeId],
+// The modules, with the kind of each (`%`, `&`, `b` for the broadcaster, `r` for `rx`, or `?` for
+// any other which isn't defined), and a graph of where each sends its pulses.
+pub struct Modules<'a, 'i> {
+    pub names: Names<'a, 'i>,
+    pub kinds: &'a [u8],
+    pub graph: Graph<'a, ()>,
+    pub rx: NodeId,
 }
 
-fn get_index(name: &[u8]) -> usize {
+pub type Edge = (NodeId, NodeId, ());
+
+fn parse_id<'i>(names: &mut Names<'_, 'i>, name: &'i [u8]) -> Result<NodeId> {
     match name {
-        b"broadcaster" => 0,
-        [a @ b'a'..=b'z', b @ b'a'..=b'z'] => {
-            ((a - b'a') as usize + 1) * 26 + ((b - b'a') as usize + 1)
-        }
-        _ => panic!("bad id"),
+        b"broadcaster" => names.intern(0, name),
+        [b'a'..=b'z', b'a'..=b'z'] => names.intern(name_key(name).unwrap(), name),
+        _ => Err(Error::new(name, "a module name of 2 letters")),
     }
 }
 
-fn get_or_alloc_id(name: &[u8], node_ids: &mut [NodeId], num_nodes: &mut usize) -> NodeId {
-    let index = get_index(name);
-    if node_ids[index] == NULL_NODE {
-        node_ids[index] = *num_nodes as NodeId;
-        *num_nodes += 1;
-    }
-    return node_ids[index];
+// Returns an upper bound on the number of connections in the input, which has at most one more
+// than its number of commas and line breaks. This is the length of the buffers that `solve_bytes`
+// needs for the connections, and for the edges of the graph.
+pub fn num_edges(input: &str) -> usize {
+    return input.bytes().filter(|b| *b == b',' || *b == b'\n').count() + 1;
 }
 
-fn read_input<'a, 'b>(
-    nodes: &'a mut [Node<'b>],
-    mut edges: &'b mut [NodeId],
-) -> (&'a [Node<'b>], NodeId) {
-    let mut buffer = [0; 1024];
-    let len = io::stdin().read(&mut buffer).unwrap();
-    if len == 0 || buffer[len - 1] != b'\n' { panic!("bad input") }
-    let input = &buffer[0 .. len - 1];
-
-    let mut node_ids = [NULL_NODE; 27 * 26 + 1];
-    let mut num_nodes = 0;
-    get_or_alloc_id(b"broadcaster", &mut node_ids, &mut num_nodes);
+// Reads the modules, using buffers with an entry for each module for the names and kinds, and
+// buffers of the length from `num_edges` for the connections and the edges of the graph.
+pub fn read_input<'a, 'i>(
+    input: &'i [u8],
+    ids: &'a mut [NodeId; MAX_KEYS],
+    names: &'a mut [&'i [u8]; MAX_NODES],
+    kinds: &'a mut [u8; MAX_NODES],
+    edges: &'a mut [Edge],
+    offsets: &'a mut [u32; MAX_NODES + 1],
+    adjacent: &'a mut [(NodeId, ())],
+) -> Result<Modules<'a, 'i>> {
+    let input = strip_newline(input)?;
+    let mut names = Names::new(ids, names);
+    let mut edges = Buffer::new(edges);
+    kinds.fill(b'?');
+    parse_id(&mut names, b"broadcaster")?;
 
     for line in input.split(|b| *b == b'\n') {
         let (kind, name, tail): (u8, &[u8], &[u8]) = if line.starts_with(b"broadcaster") {
-            (b'b', b"broadcaster", &line[11..])
+            (b'b', &line[..11], &line[11..])
+        } else if let [kind @ (b'%' | b'&'), _, _, ..] = line {
+            (*kind, &line[1..3], &line[3..])
         } else {
-            (line[0], &line[1..3], &line[3..])
+            return Err(Error::new(line, "'%', '&', or \"broadcaster\""));
         };
-        let id = get_or_alloc_id(name, &mut node_ids, &mut num_nodes);
-        let mut num_outs = 0;
-        for out in tail.strip_prefix(b" ->").unwrap().split(|b| *b == b',') {
-            if out[0] != b' ' { panic!("bad line") }
-            let out = &out[1..];
-            edges[num_outs] = get_or_alloc_id(out, &mut node_ids, &mut num_nodes);
-            num_outs += 1;
+        let id = parse_id(&mut names, name)?;
+        if kinds[id as usize] != b'?' {
+            return Err(Error::new(line, "a single definition of each module"));
+        }
+        kinds[id as usize] = kind;
+        for out in eat(tail, " ->")?.split(|b| *b == b',') {
+            let out = eat(out, " ")?;
+            edges.push((id, parse_id(&mut names, out)?, ()), out)?;
         }
-        let (outs, free) = edges.split_at_mut(num_outs);
-        edges = free;
-        nodes[id as usize] = Node{kind: kind, outs: outs};
     }
 
-    let rx = node_ids[get_index(b"rx")];
-    if rx == NULL_NODE { panic!("no rx node") }
-    nodes[rx as usize].kind = b'r';
-    return (&nodes[0..num_nodes], rx);
+    let Some(rx) = names.get(name_key(b"rx").unwrap()) else {
+        return Err(Error::new(&input[input.len()..], "a module which sends pulses to \"rx\""));
+    };
+    kinds[rx as usize] = b'r';
+    check_len(input, adjacent, edges.len())?;
+    let graph = Graph::directed(names.len(), edges.iter().copied(), offsets, adjacent);
+    return Ok(Modules{kinds: &kinds[..names.len()], names, graph, rx});
 }
 
 fn ham(mut x: u16) -> u64 {
@@ -83,23 +97,27 @@ fn ham(mut x: u16) -> u64 {
     return x as u64;
 }
 
-fn main() {
-    let mut node_buffer = [Node{kind: b'?', outs: &[]}; MAX_NODES];
-    let mut edge_buffer = [0; MAX_EDGES];
-    let (nodes, rx) = read_input(&mut node_buffer, &mut edge_buffer);
-
+// Checks that the graph has the structure described below, and returns the
+// target number for each of the four counters that it contains.
+fn find_targets(modules: &Modules, end: &[u8]) -> Result<[u64; 4]> {
+    let (graph, rx) = (&modules.graph, modules.rx);
+    let kind = |id: NodeId| modules.kinds[id as usize];
+    let outs = |id: NodeId| graph.edges(id);
     // The input graph follows a very strict format:
     //
     //   * Nothing sends pulses to the broadcaster (except the button).
-    for node in nodes {
-        for out in node.outs {
-            if *out == 0 { panic!("something sends pulses to the broadcaster") }
+    for node in 0..graph.num_nodes() as NodeId {
+        for (out, _) in outs(node) {
+            if *out != 0 { continue }
+            return Err(Error::new(end, "nothing sending pulses to the broadcaster"));
         }
     }
     //   * The broadcaster sends pulses to four "root" flip-flop module.
-    if nodes[0].outs.len() != 4 { panic!("broadcaster does not have 4 outputs") }
-    for out in nodes[0].outs {
-        if nodes[*out as usize].kind != b'%' { panic!("broadcaster recipient is not a flip-flop") }
+    if outs(0).len() != 4 { return Err(Error::new(end, "a broadcaster with 4 outputs")) }
+    for (out, _) in outs(0) {
+        if kind(*out) != b'%' {
+            return Err(Error::new(end, "a broadcaster which sends pulses to flip-flops"));
+        }
     }
     //   * Each "root" flip-flop is the start of a chain of 12 consecutive flip-flops, each one
     //     feeding into the next.
@@ -108,60 +126,62 @@ fn main() {
     //       each other).
     //     * Either sends pulses to, or receives pulses from, every other flip-flop in the chain.
     let mut chains = [(0, [0; 12]); 4];
-    for (root, (comparator, chain)) in nodes[0].outs.iter().zip(chains.iter_mut()) {
+    for ((root, _), (comparator, chain)) in outs(0).iter().zip(chains.iter_mut()) {
         chain[0] = *root;
         let mut i = 1;
-        let mut node = &nodes[*root as usize];
-        if let Some(c) = node.outs.iter().find(|i| nodes[**i as usize].kind == b'&') {
+        let mut node = *root;
+        if let Some((c, _)) = outs(node).iter().find(|(i, _)| kind(*i) == b'&') {
             *comparator = *c;
         } else {
-            panic!("chain root not connected to a comparator");
+            return Err(Error::new(end, "each chain root connected to a comparator"));
         }
         while i < 12 {
-            let next_id = match node.outs {
-                [a] => *a,
-                [a, b] if *a == *comparator => *b,
-                [a, b] if *b == *comparator => *a,
-                _ => panic!(
-                    "chain node should output to next flip-flop and optionally to the comparator"
-                ),
+            let next_id = match outs(node) {
+                [(a, _)] => *a,
+                [(a, _), (b, _)] if *a == *comparator => *b,
+                [(a, _), (b, _)] if *b == *comparator => *a,
+                _ => return Err(Error::new(end, "chain nodes which only output to the next one")),
             };
             chain[i] = next_id;
             i += 1;
-            node = &nodes[next_id as usize];
-            if node.kind != b'%' { panic!("chain node is not a flip-flop") }
+            node = next_id;
+            if kind(node) != b'%' { return Err(Error::new(end, "chains of 12 flip-flops")) }
         }
     }
     //   * Each "comparator" module feeds into a separate inverter (a conjunction module with only
     //     one input).
     let mut inverters = [0; 4];
     for ((comparator, _), inverter) in chains.iter().zip(inverters.iter_mut()) {
-        let comparator = &nodes[*comparator as usize];
-        if let Some(i) = comparator.outs.iter().find(|i| nodes[**i as usize].kind == b'&') {
+        if let Some((i, _)) = outs(*comparator).iter().find(|(i, _)| kind(*i) == b'&') {
             *inverter = *i;
         } else {
-            panic!("comparator not connected to an inverter");
+            return Err(Error::new(end, "each comparator connected to an inverter"));
         }
     }
     for i in 1..4 {
         for j in 0..i {
-            if inverters[i] == inverters[j] { panic!("two comparators connected to one inverter") }
+            if inverters[i] == inverters[j] {
+                return Err(Error::new(end, "a separate inverter for each comparator"));
+            }
         }
     }
     //   * The four inverters are the inputs for a final conjunction module, which feeds into `rx`.
-    if nodes[inverters[0] as usize].outs.len() != 1 { panic!("inverter should have one output") }
-    let fc = nodes[inverters[0] as usize].outs[0];
-    if nodes[fc as usize].kind != b'&' {
-        panic!("inverter not connected to final conjunction module");
+    let [(fc, _)] = outs(inverters[0]) else {
+        return Err(Error::new(end, "inverters with one output"));
+    };
+    if kind(*fc) != b'&' {
+        return Err(Error::new(end, "inverters connected to a final conjunction module"));
     }
-    match nodes[fc as usize].outs {
-        [x] => if *x != rx { panic!("final conjunction module not connected to rx") },
-        _ => panic!("final conjunction module should have exactly one output"),
+    match outs(*fc) {
+        [(x, _)] if *x == rx => {}
+        [_] => return Err(Error::new(end, "a final conjunction module connected to rx")),
+        _ => return Err(Error::new(end, "a final conjunction module with one output")),
     }
     for inverter in inverters {
-        match nodes[inverter as usize].outs {
-            [x] => if *x != fc { panic!("inverter not connected to final conjunction module") },
-            _ => panic!("inverter should have one output"),
+        match outs(inverter) {
+            [(x, _)] if x == fc => {}
+            [_] => return Err(Error::new(end, "inverters connected to a final conjunction module")),
+            _ => return Err(Error::new(end, "inverters with one output")),
         }
     }
 
@@ -175,13 +195,22 @@ fn main() {
     let mut targets: [u64; 4] = [0; 4];
     for ((comparator, chain), target) in chains.iter().zip(targets.iter_mut()) {
         for i in 0..12 {
-            if let Some(_) = nodes[chain[i] as usize].outs.iter().find(|x| **x == *comparator) {
+            if outs(chain[i]).iter().any(|(out, _)| out == comparator) {
                 *target |= 1 << i;
             }
         }
     }
-    //   * The number of pulses created for each increment is fairly predictable:
-    //     * a different number if we're at the target number, but that never happens in part 1.
+    for (i, target) in targets.iter().enumerate() {
+        explain!("chain {} counts up to {} ({:012b}) before resetting", i, target, target);
+    }
+    return Ok(targets);
+}
+
+// Returns the product of the number of high and low pulses sent during the
+// first 1000 button presses.
+fn pulses(targets: [u64; 4]) -> u64 {
+    // The number of pulses created for each increment is fairly predictable:
+    //   * a different number if we're at the target number, but that never happens in part 1.
     let mut high = 0;
     let mut low = 0;
     for i in 0..1000 {
@@ -214,12 +243,182 @@ fn main() {
             high += compares;
         }
     }
-    let part1 = high * low;
+    return high * low;
+}
+
+// Returns the number of button presses before a low pulse is sent to `rx`.
+fn presses(targets: [u64; 4]) -> u64 {
+    // The inverters and final conjunction module ensure that `rx` only gets a pulse when all
+    // four counters reset to 0 at the same time, so we know that we need the least common
+    // multiple of the reset value for each counter to make this happen.
+    return targets.iter().fold(1, |x, t| num_integer::lcm(x, *t));
+}
+
+// The structure that `find_targets` checks is a single assumption here, since each part of it only
+// makes sense once the earlier parts hold. Part 1 also needs every counter to stay below its target
+// for the first 1000 presses, which can only be checked once the targets are known.
+#[cfg(feature = "std")]
+pub fn lint(input: &str) -> Result<Vec<Assumption>> {
+    return with_modules(input, |modules| {
+        let structure = "the broadcaster starts four 12-bit counters whose resets meet at rx";
+        let targets = match find_targets(modules, &input.as_bytes()[input.len()..]) {
+            Ok(targets) => targets,
+            Err(error) => {
+                let violation = format!("expected {}", error.expected);
+                return Ok(vec![Assumption::new(structure, Some(violation))]);
+            }
+        };
+        let low = targets.iter().position(|target| *target <= 1000).map(|i| {
+            return format!("counter {} resets after {} presses", i, targets[i]);
+        });
+        return Ok(vec![
+            Assumption::new(structure, None),
+            Assumption::new("no counter resets during the first 1000 presses", low),
+        ]);
+    });
+}
 
-    //   * The inverters and final conjunction module ensure that `rx` only gets a pulse when all
-    //     four counters reset to 0 at the same time, so we know that we need the least common
-    //     multiple of the reset value for each counter to make this happen.
-    let part2 = targets.iter().fold(1, |x, t| num_integer::lcm(x, *t));
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    return with_modules(input, |modules| {
+        return Ok(pulses(find_targets(modules, &input.as_bytes()[input.len()..])?).into());
+    });
+}
 
-    print!("{}\n{}\n", part1, part2);
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    return with_modules(input, |modules| {
+        return Ok(presses(find_targets(modules, &input.as_bytes()[input.len()..])?).into());
+    });
+}
+
+// Reads the modules with buffers sized from the input, and passes them to `f`.
+#[cfg(feature = "std")]
+fn with_modules<T>(input: &str, f: impl FnOnce(&Modules) -> Result<T>) -> Result<T> {
+    let (mut ids, mut names) = ([NO_NODE; MAX_KEYS], [&input.as_bytes()[..0]; MAX_NODES]);
+    let (mut kinds, mut offsets) = ([b'?'; MAX_NODES], [0; MAX_NODES + 1]);
+    let len = num_edges(input);
+    let (mut edges, mut adjacent) = (vec![(0, 0, ()); len], vec![(0, ()); len]);
+    let modules = read_input(input.as_bytes(), &mut ids, &mut names, &mut kinds, &mut edges,
+                             &mut offsets, &mut adjacent)?;
+    return f(&modules);
+}
+
+// Writes the modules in the DOT language, labelled with their kinds and names.
+#[cfg(feature = "std")]
+pub fn graph(input: &str) -> Result<String> {
+    return with_modules(input, |modules| {
+        let label = |id: NodeId| {
+            let name = String::from_utf8_lossy(modules.names.name(id));
+            return match modules.kinds[id as usize] {
+                kind @ (b'%' | b'&') => format!("{}{}", kind as char, name),
+                _ => name.into_owned(),
+            };
+        };
+        return Ok(modules.graph.dot("day20", label, |_| String::new()));
+    });
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let len = num_edges(input);
+    let (mut edges, mut adjacent) = (vec![(0, 0, ()); len], vec![(0, ()); len]);
+    return solve_bytes(input.as_bytes(), &mut edges, &mut adjacent);
+}
+
+// Solves both parts, using buffers of the length from `num_edges` for the connections and the
+// edges of the graph.
+pub fn solve_bytes(
+    input: &[u8],
+    edges: &mut [Edge],
+    adjacent: &mut [(NodeId, ())],
+) -> Result<(Answer, Answer)> {
+    let (mut ids, mut names) = ([NO_NODE; MAX_KEYS], [&input[..0]; MAX_NODES]);
+    let (mut kinds, mut offsets) = ([b'?'; MAX_NODES], [0; MAX_NODES + 1]);
+    let modules =
+        read_input(input, &mut ids, &mut names, &mut kinds, edges, &mut offsets, adjacent)?;
+    phase!("parse");
+    let targets = find_targets(&modules, &input[input.len()..])?;
+    phase!("targets");
+    let part1 = pulses(targets);
+    phase!("part 1");
+    let part2 = presses(targets);
+    phase!("part 2");
+    return Ok((part1.into(), part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The examples in the puzzle don't have the structure that `find_targets` relies on, so this
+    // is a generated network which does: four 12-bit counters whose resets meet at `rx`.
+    const EXAMPLE: &str = "\
+%ma -> ud, un
+&ib -> kd, wk, yt, yo, gx, ou, lu
+%la -> er, ib
+%jm -> hy, rd
+%du -> lt
+%tf -> un, kj
+%fp -> pb, is
+%oa -> wk, ib
+%xm -> mw, pb
+%gy -> un, bq
+%ht -> ej, hy
+%dz -> rr
+%lu -> yo
+%kj -> un
+%mt -> tq
+%yu -> ht
+%ad -> ox, un
+%ou -> ie
+%vo -> un, tf
+&dg -> mv
+%is -> rj
+%ep -> vo
+&hy -> ci, gn, mt, ze, yu, qz
+%eo -> ib, la
+%ox -> un, za
+%gn -> hy, sh
+%ze -> jh
+%rj -> dz
+broadcaster -> yt, gn, ad, xm
+%vk -> pb
+%wk -> ou
+%tq -> hy, qz
+&ci -> mv
+%bq -> ma, un
+%zc -> gy, un
+%er -> ib
+%rd -> mt, hy
+%sx -> vk
+%ej -> ze, hy
+%gx -> ms
+%mw -> du
+%rr -> sx
+%ie -> ib, eo
+&kd -> mv
+&un -> ep, ud, ad, sk, za
+&pb -> du, xm, dg, mw, rr, sx, dz, rj, uk, is
+%uk -> fp
+%qz -> yu
+%yo -> gx
+%jh -> hy
+%ud -> ep
+%lt -> uk, pb
+%ms -> oa, ib
+&mv -> rx
+%sh -> jm, hy
+%yt -> ib, lu
+%za -> zc
+&sk -> mv
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(930317115));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(86222441674061u64));
+        let expected = (Answer::from(930317115), Answer::from(86222441674061u64));
+        assert_eq!(solve(EXAMPLE).unwrap(), expected);
+    }
 }
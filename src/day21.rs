@@ -1,73 +1,83 @@
-use std::io;
-use std::io::Read;
-
-const SIZE: usize = 131;
-type Grid<T> = [[T; SIZE]; SIZE];
-
-fn read_input() -> Grid<bool> {
-    let mut buffer = [0; (SIZE + 1) * SIZE];
-    let len = io::stdin().read(&mut buffer).unwrap();
-
-    // The input should be a perfect grid occupying the whole buffer.
-    if len != buffer.len() { panic!("truncated") }
-    let mut result = [[false; SIZE]; SIZE];
-    const CENTER: usize = SIZE / 2 * (SIZE + 2);
-    if buffer[CENTER] != b'S' { panic!("center is not S") }
-    buffer[CENTER] = b'.';
-    for (line, row) in buffer.split(|b| *b == b'\n').zip(result.iter_mut()) {
-        if line.len() != row.len() { panic!("not a grid") }
-        for (c, cell) in line.iter().zip(row.iter_mut()) {
-            *cell = match c {
-                b'.' => false,
-                b'#' => true,
-                _ => panic!("bad grid"),
-            };
-        }
+use crate::Answer;
+use crate::buffer::check_len;
+use crate::error::{Error, Result};
+use crate::explain;
+use crate::grid::Grid;
+#[cfg(feature = "std")]
+use crate::lint::{self, Assumption};
+use crate::phase;
+#[cfg(feature = "std")]
+use crate::render::{Frames, Palette};
+
+// Reads the map. The `S` in the center is a garden plot like any other.
+pub fn read_input(input: &[u8]) -> Result<Grid<&[u8]>> {
+    // The input should be a square grid of odd size with `S` in the center.
+    let grid = Grid::parse(input)?;
+    grid.validate(|c| matches!(c, b'.' | b'#' | b'S'), "'.', '#', or 'S'")?;
+    grid.check_square()?;
+    let size = grid.width();
+    if size.is_multiple_of(2) {
+        return Err(Error::new(&grid.row(0)[size - 1..], "an odd number of columns"));
+    }
+    let center = (size / 2, size / 2);
+    if grid[center] != b'S' { return Err(Error::byte(&grid.row(center.1)[center.0..], b'S')) }
+    let stray = |(i, c): &(usize, &u8)| **c == b'S' && *i != grid.index(center);
+    if let Some((i, _)) = grid.cells().iter().enumerate().find(stray) {
+        let (x, y) = grid.position(i);
+        return Err(Error::new(&grid.row(y)[x..], "'S' only in the center"));
     }
-    return result;
+    return Ok(grid);
 }
 
-fn try_add(
-    (x, y): (u8, u8),
-    grid: &Grid<bool>,
-    seen: &mut Grid<bool>,
-    queue: &mut [(u8, u8)],
-    back: &mut usize,
-) {
-    if grid[y as usize][x as usize] || seen[y as usize][x as usize] { return }
-    seen[y as usize][x as usize] = true;
-    queue[*back] = (x, y);
-    *back += 1;
+// The size of the buffer that `distances` needs: one for each cell's distance, and one for each
+// cell in the queue.
+pub fn buffer_len<T: AsRef<[u8]>>(grid: &Grid<T>) -> usize {
+    return 2 * grid.cells().len();
 }
 
-fn reachable(grid: &Grid<bool>) -> Grid<bool> {
-    let mut seen = [[false; SIZE]; SIZE];
-    let mut queue: [(u8, u8); SIZE * SIZE] = [(0, 0); SIZE * SIZE];
-    queue[0] = (65, 65);
-    let mut front = 0;
-    let mut back = 1;
+// Returns the number of steps needed to reach each cell (indexed as in `grid.cells()`) from the
+// center, or `u32::MAX` if it can't be reached. `buffer` must hold `buffer_len` values.
+pub fn distances<'a, T: AsRef<[u8]>>(grid: &Grid<T>, buffer: &'a mut [u32]) -> &'a [u32] {
+    let (distances, queue) = buffer.split_at_mut(grid.cells().len());
+    distances.fill(u32::MAX);
+    let center = (grid.width() / 2, grid.height() / 2);
+    distances[grid.index(center)] = 0;
+    queue[0] = grid.index(center) as u32;
+    let (mut front, mut back) = (0, 1);
     while front != back {
-        let (x, y) = queue[front];
+        let position = grid.position(queue[front] as usize);
         front += 1;
-        if x > 0 {
-            try_add((x - 1, y), &grid, &mut seen, &mut queue, &mut back);
-        }
-        if x < (SIZE - 1) as u8 {
-            try_add((x + 1, y), &grid, &mut seen, &mut queue, &mut back);
-        }
-        if y > 0 {
-            try_add((x, y - 1), &grid, &mut seen, &mut queue, &mut back);
-        }
-        if y < (SIZE - 1) as u8 {
-            try_add((x, y + 1), &grid, &mut seen, &mut queue, &mut back);
+        let distance = distances[grid.index(position)] + 1;
+        for next in grid.neighbours(position) {
+            let i = grid.index(next);
+            if grid[next] == b'#' || distances[i] != u32::MAX { continue }
+            distances[i] = distance;
+            queue[back] = i as u32;
+            back += 1;
         }
     }
-    return seen;
+    return distances;
+}
+
+// Part 1: Counts the positions which can be reached in exactly `steps` steps. Any position which
+// can be reached in fewer steps of the same parity can be returned to by stepping back and forth.
+pub fn plots(distances: &[u32], steps: u32) -> usize {
+    return distances.iter().filter(|d| **d <= steps && **d % 2 == steps % 2).count();
+}
+
+// The number of reachable positions with each parity of `x + y`, in the whole grid and in each of
+// its corners (split along the diagonals).
+pub struct Counts {
+    size: usize,
+    total: [usize; 2],
+    tl: [usize; 2],
+    tr: [usize; 2],
+    bl: [usize; 2],
+    br: [usize; 2],
 }
 
-fn main() {
-    let grid = read_input();
-    let reachable = reachable(&grid);
+pub fn count<T: AsRef<[u8]>>(grid: &Grid<T>, distances: &[u32]) -> Counts {
+    let size = grid.width();
 
     // The input grid has some specific properties which ensure that there is symmetry in how the
     // visited set expands outwards in the tiled grid:
@@ -79,7 +89,7 @@ fn main() {
     //   * There's a big diamond of uncluttered space connecting the centers of each edge. This,
     //     combined with the sparsity of obstacles in the grid, seems to be sufficient to ensure
     //     that the exploration frontier always forms a perfectly straight line down this channel.
-    //   * The required step count is 26501365, which is `100 * 2023 * SIZE + SIZE / 2`, so the
+    //   * The required step count is 26501365, which is `100 * 2023 * size + size / 2`, so the
     //     perimeter of the explored space will be in the diamond-shaped channel.
     //
     //                                                             +--+--+--+
@@ -108,15 +118,15 @@ fn main() {
     let mut bl = [0, 0];
     let mut br = [0, 0];
     let mut total = [0, 0];
-    for y in 0..SIZE {
-        for x in 0..SIZE {
-            if !reachable[y][x] { continue }
-            let ix = SIZE - x - 1;
-            let iy = SIZE - y - 1;
-            let is_tl = x + y < SIZE / 2;
-            let is_tr = ix + y < SIZE / 2;
-            let is_bl = x + iy < SIZE / 2;
-            let is_br = ix + iy < SIZE / 2;
+    for y in 0..size {
+        for x in 0..size {
+            if distances[grid.index((x, y))] == u32::MAX { continue }
+            let ix = size - x - 1;
+            let iy = size - y - 1;
+            let is_tl = x + y < size / 2;
+            let is_tr = ix + y < size / 2;
+            let is_bl = x + iy < size / 2;
+            let is_br = ix + iy < size / 2;
             let parity = (x + y) % 2;
             total[parity] += 1;
             if is_tl { tl[parity] += 1 }
@@ -125,22 +135,25 @@ fn main() {
             if is_br { br[parity] += 1 }
         }
     }
+    explain!("reachable plots by parity of x + y: total {:?}, tl {:?}, tr {:?}, bl {:?}, br {:?}",
+             total, tl, tr, bl, br);
+    return Counts{size, total, tl, tr, bl, br};
+}
 
-    let part1 = total[0] - tl[0] - tr[0] - bl[0] - br[0];
-
-    // Part 2: Infer the result after `100 * 2023 * SIZE + SIZE / 2` steps. To do this, we need the
-    // state of the grid after 130 steps and 131 steps. We can construct the final state by
-    // inspection.
-    const N: usize = 26501365;
-    const INDEX: usize = (N - SIZE) / SIZE;
+// Part 2: Infer the result after `n` steps in the infinitely tiled grid, where `n` is an even
+// number of tiles plus half a tile. To do this, we need the state of the grid after `size - 1`
+// steps and `size` steps. We can construct the final state by inspection.
+pub fn far_plots(counts: &Counts, n: usize) -> usize {
+    let Counts{size, total, tl, tr, bl, br} = *counts;
+    let index = (n - size) / size;
     // The number of full tiles grows according to this series (derived by inspection):
     //
-    //   INDEX  0  1  2  3  4  5
-    //   A      0  4  4 16 16 36  NUM_FULL_A(INDEX)
-    //   B      1  1  9  9 25 25  NUM_FULL_B(INDEX)
-    const NUM_FULL_A: usize = ((INDEX + 1) / 2 * 2) * ((INDEX + 1) / 2 * 2);
-    const NUM_FULL_B: usize = (INDEX / 2 * 2 + 1) * (INDEX / 2 * 2 + 1);
-    let full = NUM_FULL_A * total[0] + NUM_FULL_B * total[1];
+    //   index  0  1  2  3  4  5
+    //   A      0  4  4 16 16 36  num_full_a(index)
+    //   B      1  1  9  9 25 25  num_full_b(index)
+    let num_full_a = (index.div_ceil(2) * 2) * (index.div_ceil(2) * 2);
+    let num_full_b = (index / 2 * 2 + 1) * (index / 2 * 2 + 1);
+    let full = num_full_a * total[0] + num_full_b * total[1];
     // Each pointy tile consists of odd positions, excluding the two corners on the opposite side.
     let left = total[1] - tr[1] - br[1];
     let right = total[1] - tl[1] - bl[1];
@@ -148,8 +161,8 @@ fn main() {
     let bottom = total[1] - tl[1] - tr[1];
     let points = left + right + top + bottom;
     // Each diagonal has two types of tile. The number of each scales linearly with the iteration.
-    const NUM_SLOPE_A: usize = (N - 1) / (2 * SIZE) * 2;
-    const NUM_SLOPE_B: usize = 1 + (N - SIZE - 1) / (2 * SIZE) * 2;
+    let num_slope_a = (n - 1) / (2 * size) * 2;
+    let num_slope_b = 1 + (n - size - 1) / (2 * size) * 2;
     let tla = tl[0];
     let tra = tr[0];
     let bla = bl[0];
@@ -158,8 +171,157 @@ fn main() {
     let trb = total[1] - bl[1];
     let blb = total[1] - tr[1];
     let brb = total[1] - tl[1];
-    let slopes = NUM_SLOPE_A * (tla + tra + bla + bra) + NUM_SLOPE_B * (tlb + trb + blb + brb);
-    let part2 = full + points + slopes;
+    let slopes = num_slope_a * (tla + tra + bla + bra) + num_slope_b * (tlb + trb + blb + brb);
+    return full + points + slopes;
+}
+
+// The number of steps in part 2, which is `100 * 2023 * size + size / 2` for the real inputs.
+pub const STEPS: usize = 26501365;
+
+// Returns the first rock among the given cells, if there is one.
+#[cfg(feature = "std")]
+fn find_rock(
+    grid: &Grid<&[u8]>,
+    mut cells: impl Iterator<Item = (usize, usize)>,
+) -> Option<String> {
+    let rock = cells.find(|cell| grid[*cell] == b'#')?;
+    return Some(format!("'#' at {}", lint::position(rock)));
+}
+
+// Checks the properties of the map which `count` relies on, and that the number of steps for part
+// 2 lines up with the size of the map in the way that `far_plots` expects.
+#[cfg(feature = "std")]
+pub fn lint(input: &str) -> Result<Vec<Assumption>> {
+    let grid = read_input(input.as_bytes())?;
+    let size = grid.width();
+    let c = size / 2;
+    let lanes = find_rock(&grid, (0..size).map(|i| (i, c)).chain((0..size).map(|i| (c, i))));
+    let last = size - 1;
+    let border = (0..size).flat_map(|i| [(i, 0), (i, last), (0, i), (last, i)]);
+    let diamond = (0..size).flat_map(|i| [(c.abs_diff(i), i), (last - c.abs_diff(i), i)]);
+    let steps = if STEPS % size != c || !(STEPS / size).is_multiple_of(2) {
+        Some(format!("{} steps is {} whole maps plus {} steps", STEPS, STEPS / size, STEPS % size))
+    } else {
+        None
+    };
+    return Ok(vec![
+        Assumption::new("the row and column through 'S' are clear", lanes),
+        Assumption::new("the border of the map is clear", find_rock(&grid, border)),
+        Assumption::new("the diamond joining the middles of the edges is clear",
+                        find_rock(&grid, diamond)),
+        Assumption::new("part 2 takes an even number of whole maps plus half a map of steps",
+                        steps),
+    ]);
+}
 
-    print!("{}\n{}\n", part1, part2);
+#[cfg(feature = "std")]
+const PALETTE: Palette = Palette::new(&[
+    (b'#', [100, 100, 100]),
+    (b'.', [20, 40, 20]),
+    (b'r', [40, 90, 40]),
+    (b'O', [120, 255, 120]),
+]);
+
+// Animates the first 64 steps. After each step, the plots which the elf could be standing on are
+// highlighted, and the other plots which have been reached are shaded.
+#[cfg(feature = "std")]
+pub fn draw(input: &str, frames: &mut Frames) -> Result<()> {
+    let grid = read_input(input.as_bytes())?;
+    let mut buffer = vec![0; buffer_len(&grid)];
+    let distances = distances(&grid, &mut buffer);
+    let mut picture = grid.map(|c| if c == b'S' { b'.' } else { c });
+    for steps in 0..=64 {
+        for (cell, distance) in picture.cells_mut().iter_mut().zip(distances) {
+            if *distance > steps { continue }
+            *cell = if distance % 2 == steps % 2 { b'O' } else { b'r' };
+        }
+        frames.emit(&picture, &PALETTE);
+    }
+    return Ok(());
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    let grid = read_input(input.as_bytes())?;
+    return Ok(plots(distances(&grid, &mut vec![0; buffer_len(&grid)]), 64).into());
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    let grid = read_input(input.as_bytes())?;
+    let mut buffer = vec![0; buffer_len(&grid)];
+    let distances = distances(&grid, &mut buffer);
+    return Ok(far_plots(&count(&grid, distances), STEPS).into());
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let buffer = &mut vec![0; buffer_len(&read_input(input.as_bytes())?)];
+    return solve_bytes(input.as_bytes(), buffer);
+}
+
+// Solves both parts, using a buffer of the size from `buffer_len`.
+pub fn solve_bytes(input: &[u8], buffer: &mut [u32]) -> Result<(Answer, Answer)> {
+    let grid = read_input(input)?;
+    check_len(input, buffer, buffer_len(&grid))?;
+    phase!("parse");
+    let distances = distances(&grid, buffer);
+    phase!("distances");
+    let part1 = plots(distances, 64);
+    phase!("part 1");
+    let part2 = far_plots(&count(&grid, distances), STEPS);
+    phase!("part 2");
+    return Ok((part1.into(), part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........
+";
+
+    // With no rocks, the plots reachable in an odd number of steps `n` form a diamond of
+    // `(n + 1)^2` plots.
+    const EMPTY: &str = "\
+...
+.S.
+...
+";
+
+    #[test]
+    fn example() {
+        let grid = read_input(EXAMPLE.as_bytes()).unwrap();
+        let mut buffer = vec![0; buffer_len(&grid)];
+        assert_eq!(plots(distances(&grid, &mut buffer), 6), 16);
+    }
+
+    #[test]
+    fn lint() {
+        assert!(super::lint(EMPTY).unwrap().iter().all(|a| a.violation.is_none()));
+        let violations: Vec<_> =
+            super::lint(EXAMPLE).unwrap().into_iter().map(|a| a.violation).collect();
+        assert_eq!(violations, [
+            Some("'#' at line 6, column 2".to_string()),
+            None,
+            Some("'#' at line 2, column 7".to_string()),
+            Some("26501365 steps is 2409215 whole maps plus 0 steps".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn empty() {
+        assert_eq!(part2(EMPTY).unwrap(), Answer::from(702322399865956u64));
+    }
 }
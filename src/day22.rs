@@ -1,69 +1,97 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::{check_len, Buffer};
+use crate::error::{strip_newline, Error, Result};
+use crate::phase;
 
-const MAX_BRICKS: usize = 2048;
-const MAX_CONTACTS: usize = 2048;
-
-fn eat(c: u8, s: &mut &[u8]) {
-    if s[0] != c { panic!("expected {}", c as char) }
+fn eat(c: u8, s: &mut &[u8]) -> Result<()> {
+    if s.first() != Some(&c) { return Err(Error::byte(s, c)) }
     *s = &s[1..];
+    return Ok(());
 }
 
-fn read_u8(s: &mut &[u8]) -> u8 {
-    match s[0] {
-        b'0'..=b'9' => {
-            let value = s[0] - b'0';
+fn read_u8(s: &mut &[u8]) -> Result<u8> {
+    match s.first() {
+        Some(x @ b'0'..=b'9') => {
+            let value = x - b'0';
             *s = &s[1..];
-            return value;
+            return Ok(value);
         }
-        _ => panic!("not a digit"),
+        _ => return Err(Error::new(s, "a digit")),
     }
 }
 
-fn read_u16(s: &mut &[u8]) -> u16 {
-    let mut value = 0;
+fn read_u16(s: &mut &[u8]) -> Result<u16> {
+    let mut value: u16 = 0;
     let mut count = 0;
     while let [x @ b'0'..=b'9', ..] = &s[count..] {
-        value = 10 * value + (x - b'0') as u16;
+        let Some(v) = value.checked_mul(10).and_then(|v| v.checked_add((x - b'0') as u16)) else {
+            return Err(Error::new(s, "a number below 65535"));
+        };
+        value = v;
         count += 1;
     }
-    if count == 0 { panic!("not a number") }
+    if count == 0 || value == u16::MAX { return Err(Error::new(s, "a number below 65535")) }
     *s = &s[count..];
-    return value;
+    return Ok(value);
+}
+
+// A brick's corners: `(x1, y1, z1, x2, y2, z2)`.
+pub type Brick = (u8, u8, u16, u8, u8, u16);
+
+fn read_brick(mut line: &[u8]) -> Result<Brick> {
+    let start = line;
+    let x1 = read_u8(&mut line)?;
+    eat(b',', &mut line)?;
+    let y1 = read_u8(&mut line)?;
+    eat(b',', &mut line)?;
+    let z1 = read_u16(&mut line)?;
+    eat(b'~', &mut line)?;
+    let x2 = read_u8(&mut line)?;
+    eat(b',', &mut line)?;
+    let y2 = read_u8(&mut line)?;
+    eat(b',', &mut line)?;
+    let z2 = read_u16(&mut line)?;
+    if !line.is_empty() { return Err(Error::byte(line, b'\n')) }
+    if x1 > x2 || y1 > y2 || z1 > z2 {
+        return Err(Error::new(start, "a brick whose first corner is its lowest"));
+    }
+    return Ok((x1, y1, z1, x2, y2, z2));
+}
+
+// Returns the sizes of the buffers that `solve_bytes` needs: one for the bricks, one for the
+// contacts, and one for a flag for each brick and the ground. A brick has at most one contact
+// below it for each cell of its footprint.
+pub fn buffer_lens(input: &str) -> (usize, usize, usize) {
+    let (mut bricks, mut contacts) = (0, 0);
+    for line in input.lines() {
+        let Ok((x1, y1, _, x2, y2, _)) = read_brick(line.as_bytes()) else { continue };
+        bricks += 1;
+        contacts += (x2 - x1 + 1) as usize * (y2 - y1 + 1) as usize;
+    }
+    return (bricks, contacts, bricks + 1);
 }
 
-fn read_input<'a>(contacts: &'a mut [(u16, u16)]) -> (usize, &'a [(u16, u16)]) {
-    let mut buffer = [0; 24 * 1024];
-    let len = io::stdin().read(&mut buffer).unwrap();
-    if len == 0 || buffer[len - 1] != b'\n' { panic!("bad input") }
-    let input = &buffer[0..len - 1];
+// Finds all points of contact `(a, b)` where brick `a` directly supports brick `b`, storing them
+// at the start of `contacts`. Bricks are numbered from 1 in ascending order of their settled
+// height, and the contacts are sorted by `b`. Returns the number of bricks along with the contacts.
+pub fn find_contacts<'a>(
+    input: &[u8],
+    bricks: &mut [Brick],
+    contacts: &'a mut [(u32, u32)],
+) -> Result<(usize, &'a [(u32, u32)])> {
+    let input = strip_newline(input)?;
 
     // Parse all the bricks.
-    let mut bricks = [(0, 0, 0, 0, 0, 0); MAX_BRICKS];
-    let mut num_bricks = 0;
-    for mut line in input.split(|b| *b == b'\n') {
-        let x1 = read_u8(&mut line);
-        eat(b',', &mut line);
-        let y1 = read_u8(&mut line);
-        eat(b',', &mut line);
-        let z1 = read_u16(&mut line);
-        eat(b'~', &mut line);
-        let x2 = read_u8(&mut line);
-        eat(b',', &mut line);
-        let y2 = read_u8(&mut line);
-        eat(b',', &mut line);
-        let z2 = read_u16(&mut line);
-        if line != b"" { panic!("trailing characters") }
-        bricks[num_bricks] = (x1, y1, z1, x2, y2, z2);
-        num_bricks += 1;
+    let mut bricks = Buffer::new(bricks);
+    for line in input.split(|b| *b == b'\n') {
+        bricks.push(read_brick(line)?, line)?;
     }
-    let bricks = &mut bricks[0..num_bricks];
 
     // Sort them by ascending Z.
     bricks.sort_unstable_by_key(|(_, _, z, _, _, _)| *z);
 
     // Identify all the points of contact between bricks.
-    let mut num_contacts = 0;
+    let mut contacts = Buffer::new(contacts);
     let mut z = [[(0, 0); 10]; 10];  // 2d map of (z, id)
     for (id, (x1, y1, z1, x2, y2, z2)) in (1..).zip(bricks.iter()) {
         // Calculate the height which the brick will rest at.
@@ -74,48 +102,47 @@ fn read_input<'a>(contacts: &'a mut [(u16, u16)]) -> (usize, &'a [(u16, u16)]) {
             }
         }
         // Identify all bricks which support this brick.
-        let first = num_contacts;
+        let first = contacts.len();
         for y in *y1 as usize..=*y2 as usize {
             for x in *x1 as usize..=*x2 as usize {
                 if z[y][x].0 != support_z { continue }
                 let support_id = z[y][x].1;
-                let seen = &contacts[first..num_contacts];
-                if let Some(_) = seen.iter().find(|(i, _)| *i == support_id) { continue }
-                contacts[num_contacts] = (support_id, id as u16);
-                num_contacts += 1;
+                if contacts[first..].iter().any(|(i, _)| *i == support_id) { continue }
+                contacts.push((support_id, id), &input[input.len()..])?;
             }
         }
         // Update the z buffer.
-        let h = support_z + z2 + 1 - z1;
+        let h = support_z + (z2 + 1 - z1) as usize;
         for y in *y1 as usize..=*y2 as usize {
             for x in *x1 as usize..=*x2 as usize {
-                z[y][x] = (h, id as u16);
+                z[y][x] = (h, id);
             }
         }
     }
 
-    return (num_bricks, &contacts[0..num_contacts]);
+    return Ok((bricks.len(), contacts.into_slice()));
 }
 
-fn main() {
-    let mut contact_buffer = [(0, 0); MAX_CONTACTS];
-    let (num_bricks, contacts) = read_input(&mut contact_buffer);
-
-    // `supports[i]` is the number of bricks directly supporting brick `i`.
-    let mut supports = [0; MAX_BRICKS];
-    for (_, b) in contacts { supports[*b as usize] += 1 }
+// Counts the number of bricks which can safely be removed, using `removable` (which must hold
+// `num_bricks + 1` flags) to mark the ones which can't.
+fn removable(num_bricks: usize, contacts: &[(u32, u32)], removable: &mut [bool]) -> usize {
     // `removable[i]` is true if it is safe to remove brick `i`.
-    let mut removable = [true; MAX_BRICKS];
-    // It is safe to remove a brick if every brick supported by it has at least 2 supports.
-    for (a, b) in contacts {
-        if supports[*b as usize] == 1 { removable[*a as usize] = false }
+    let removable = &mut removable[..=num_bricks];
+    removable.fill(true);
+    // It is safe to remove a brick if every brick supported by it has at least 2 supports. The
+    // contacts are sorted by the brick that they support, so each brick's supports are adjacent.
+    for supports in contacts.chunk_by(|x, y| x.1 == y.1) {
+        if let [(a, _)] = supports { removable[*a as usize] = false }
     }
-    let part1 = &removable[1..=num_bricks].iter().filter(|x: &&bool| **x).count();
+    return removable[1..].iter().filter(|x: &&bool| **x).count();
+}
 
-    // Count the number of other bricks that fall if we remove each brick.
-    let mut part2 = 0;
+// Counts the number of other bricks that fall if we remove each brick, using `supported` (which
+// must hold `num_bricks + 1` flags) to track which are still supported.
+fn falling(num_bricks: usize, contacts: &[(u32, u32)], supported: &mut [bool]) -> usize {
+    let mut total = 0;
     // `supported[i]` is true if brick `i` is still supported without `brick`.
-    let mut supported = [false; MAX_BRICKS];
+    let supported = &mut supported[..=num_bricks];
     supported[0] = true;
     // `contacts[j]` is the first contact `(a, b)` where `b > brick`.
     let mut j = 0;
@@ -129,9 +156,79 @@ fn main() {
         for (a, b) in &contacts[j..] {
             supported[*b as usize] |= supported[*a as usize];
         }
-        part2 += supported[brick + 1..=num_bricks].iter().filter(|x| !**x).count();
+        total += supported[brick + 1..=num_bricks].iter().filter(|x| !**x).count();
         supported[brick] = true;
     }
+    return total;
+}
+
+// Runs `find_contacts` with buffers sized from the input.
+#[cfg(feature = "std")]
+fn find_contacts_with_buffers(input: &str) -> Result<(usize, Vec<(u32, u32)>)> {
+    let (bricks_len, contacts_len, _) = buffer_lens(input);
+    let mut bricks = vec![Brick::default(); bricks_len];
+    let mut contacts = vec![(0, 0); contacts_len];
+    let (num_bricks, found) = find_contacts(input.as_bytes(), &mut bricks, &mut contacts)?;
+    let len = found.len();
+    contacts.truncate(len);
+    return Ok((num_bricks, contacts));
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    let (num_bricks, contacts) = find_contacts_with_buffers(input)?;
+    return Ok(removable(num_bricks, &contacts, &mut vec![false; num_bricks + 1]).into());
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    let (num_bricks, contacts) = find_contacts_with_buffers(input)?;
+    return Ok(falling(num_bricks, &contacts, &mut vec![false; num_bricks + 1]).into());
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let (bricks_len, contacts_len, flags_len) = buffer_lens(input);
+    let mut bricks = vec![Brick::default(); bricks_len];
+    let mut contacts = vec![(0, 0); contacts_len];
+    return solve_bytes(input.as_bytes(), &mut bricks, &mut contacts, &mut vec![false; flags_len]);
+}
+
+// Solves both parts, using buffers of the sizes from `buffer_lens`.
+pub fn solve_bytes(
+    input: &[u8],
+    bricks: &mut [Brick],
+    contacts: &mut [(u32, u32)],
+    flags: &mut [bool],
+) -> Result<(Answer, Answer)> {
+    let (num_bricks, contacts) = find_contacts(input, bricks, contacts)?;
+    check_len(input, flags, num_bricks + 1)?;
+    phase!("contacts");
+    let part1 = removable(num_bricks, contacts, flags);
+    phase!("part 1");
+    let part2 = falling(num_bricks, contacts, flags);
+    phase!("part 2");
+    return Ok((part1.into(), part2.into()));
+}
 
-    print!("{}\n{}\n", part1, part2);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+1,0,1~1,2,1
+0,0,2~2,0,2
+0,2,3~2,2,3
+0,0,4~0,2,4
+2,0,5~2,2,5
+0,1,6~2,1,6
+1,1,8~1,1,9
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(5));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(7));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(5), Answer::from(7)));
+    }
 }
@@ -1,84 +1,141 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::{check_len, Buffer};
+use crate::error::{Error, Result};
+use crate::graph::{self, Graph, NodeId};
+use crate::grid::Grid;
+#[cfg(feature = "std")]
+use crate::lint::Assumption;
+use crate::phase;
+#[cfg(feature = "std")]
+use crate::render::{Frames, Palette};
 
-const GRID_SIZE: usize = 141;
-const MAX_EDGES: usize = 128;
-
-const ROW: usize = GRID_SIZE + 1;
-const START: usize = 1;                  // First few bytes are "# ###"
-const END: usize = ROW * GRID_SIZE - 3;  // Last few bytes are "# #\n"
-
-type Node = u16;
+pub type Node = NodeId;
 const START_NODE: Node = 0;
 const END_NODE: Node = 1;
-const MAX_NODES: usize = 36;
 
 const UPHILL: u8 = 1;
 const DOWNHILL: u8 = 2;
-type Edge = (Node, Node, u8, u16);
-
-fn read_input<'a>(edges: &'a mut [Edge]) -> &'a [Edge] {
-    // Load the grid into a buffer.
-    let mut buffer = [0; (GRID_SIZE + 1) * GRID_SIZE];
-    let len = io::stdin().read(&mut buffer).unwrap();
-    if len != buffer.len() { panic!("bad input") }
-    for line in buffer[0..len-1].split(|b| *b == b'\n') {
-        if line.len() != GRID_SIZE { panic!("not a grid") }
+pub type Edge = (Node, Node, u8, u16);
+
+// The paths between the crossroads, with the length of each.
+pub type Paths<'a> = Graph<'a, u16>;
+
+// A node's edges, copied from the graph for the search: `row[i]` is a pair `(b, n)` indicating an
+// edge of length `n` to node `b`. If `n` is `0`, it means that no such edge exists.
+pub type Row = [(Node, u16); 4];
+
+// An entry on the path being explored by `search`.
+pub type Step = (Node, u16, usize);
+
+// Returns the number of cells in the map and an upper bound on the number of nodes in its graph,
+// which has a node for each crossroads as well as the start and the end. For `n` nodes,
+// `solve_bytes` needs a node for each cell, `2 * n` edges and rows, `n` steps, `n.div_ceil(64)`
+// words, and buffers of the lengths from `graph::buffer_lens` for an undirected graph of `n` nodes
+// and `2 * n` edges.
+pub fn buffer_lens(input: &[u8]) -> (usize, usize) {
+    let Ok(grid) = Grid::parse(input) else { return (0, 0) };
+    let mut nodes = 2;
+    for y in 1..grid.height().saturating_sub(1) {
+        for x in 1..grid.width().saturating_sub(1) {
+            if grid[(x, y)] == b'#' { continue }
+            if grid.neighbours((x, y)).filter(|n| grid[*n] != b'#').count() > 2 { nodes += 1 }
+        }
+    }
+    return (grid.cells().len(), nodes);
+}
+
+// Stores the paths between the crossroads of the map at the start of `edges`, using `nodes` (which
+// must hold a node for each cell) to number the crossroads.
+pub fn read_input<'a>(
+    input: &[u8],
+    nodes: &mut [Node],
+    edges: &'a mut [Edge],
+) -> Result<&'a [Edge]> {
+    // Verify that the input is a grid surrounded by forest, except for the entrance in the top row
+    // and the exit in the bottom row.
+    let grid = Grid::parse(input)?;
+    grid.validate(|c| matches!(c, b'#' | b'.' | b'>' | b'v' | b'<' | b'^'),
+                  "a path ('.'), forest ('#'), or slope ('>', 'v', '<', '^')")?;
+    let (w, h) = (grid.width(), grid.height());
+    if w < 3 { return Err(Error::new(&grid.row(0)[w..], "at least 3 columns")) }
+    if h < 3 { return Err(Error::new(&grid.cells()[grid.cells().len()..], "at least 3 lines")) }
+    let (start, end) = ((1, 0), (w - 2, h - 1));
+    for y in 0..h {
+        for x in 0..w {
+            let expected = if (x, y) == start || (x, y) == end {
+                b'.'
+            } else if x == 0 || x == w - 1 || y == 0 || y == h - 1 {
+                b'#'
+            } else {
+                continue;
+            };
+            if grid[(x, y)] != expected { return Err(Error::byte(&grid.row(y)[x..], expected)) }
+        }
     }
-    // Close the entrance. This makes the graph exploration simpler since we don't have to check for
-    // falling out of the start.
-    buffer[1] = b'#';
+
+    // Bound the length of the longest path so that it fits in 16 bits.
+    let open = grid.cells().iter().filter(|c| !matches!(c, b'#' | b'\n')).count();
+    if open > u16::MAX as usize {
+        return Err(Error::new(&grid.cells()[grid.cells().len()..], "at most 65535 open cells"));
+    }
+
     // Convert the grid into a list of edges with nodes at each crossroads.
     // nodes[i] is the ID of the node at grid[i].
-    let mut nodes = [0; (GRID_SIZE + 1) * GRID_SIZE];
+    check_len(input, nodes, grid.cells().len())?;
+    let nodes = &mut nodes[..grid.cells().len()];
+    nodes.fill(0);
     let mut num_nodes = 2;  // START_NODE and END_NODE are predefined as 0 and 1.
-    let mut num_edges = 0;
-    graphify(&mut buffer, &mut nodes, &mut num_nodes, edges, &mut num_edges, START, &[START + ROW]);
-    return &edges[0..num_edges];
+    let mut edges = Buffer::new(edges);
+    let start = grid.index(start);
+    let below = start + grid.stride();
+    graphify(&grid, nodes, &mut num_nodes, &mut edges, start, &[below])?;
+    return Ok(edges.into_slice());
 }
 
 fn graphify(
-    grid: &mut [u8],
+    grid: &Grid<&[u8]>,
     nodes: &mut [Node],
     num_nodes: &mut usize,
-    edges: &mut [Edge],
-    num_edges: &mut usize,
+    edges: &mut Buffer<Edge>,
     i: usize,
     neighbors: &[usize],
-) {
-    const ROW: usize = GRID_SIZE + 1;
+) -> Result<()> {
+    let row = grid.stride();
+    let cells = grid.cells();
+    let start = grid.index((1, 0));
+    let end = grid.index((grid.width() - 2, grid.height() - 1));
     let id = nodes[i];
     for n in neighbors {
-        if grid[*n] == b'#' { continue }
+        if cells[*n] == b'#' { continue }
         // Follow this path until we find another crossroad.
         let mut prev = i;
         let mut pos = *n;
         let mut len = 0;
         let mut hills = 0;
         loop {
-            match grid[pos] {
+            match cells[pos] {
                 b'.' => {},
                 b'>' if prev + 1 == pos => hills |= DOWNHILL,
-                b'v' if prev + ROW == pos => hills |= DOWNHILL,
+                b'v' if prev + row == pos => hills |= DOWNHILL,
                 b'<' if prev - 1 == pos => hills |= DOWNHILL,
-                b'^' if prev - ROW == pos => hills |= DOWNHILL,
+                b'^' if prev - row == pos => hills |= DOWNHILL,
                 b'>' if prev - 1 == pos => hills |= UPHILL,
-                b'v' if prev - ROW == pos => hills |= UPHILL,
+                b'v' if prev - row == pos => hills |= UPHILL,
                 b'<' if prev + 1 == pos => hills |= UPHILL,
-                b'^' if prev + ROW == pos => hills |= UPHILL,
-                _ => panic!("bad grid"),
+                b'^' if prev + row == pos => hills |= UPHILL,
+                _ => return Err(Error::new(&cells[pos..], "a slope along the path")),
             }
             len += 1;
-            if pos == END {
-                edges[*num_edges] = (id, END_NODE, hills, len);
-                *num_edges += 1;
+            if pos == end {
+                edges.push((id, END_NODE, hills, len), &cells[pos..])?;
                 break;
             }
-            // Enumerate the neighbors of the current cell, excluding the one we came from.
+            // Enumerate the neighbors of the current cell, excluding the one we came from. The
+            // entrance counts as forest, so that paths never lead back out of the map.
             let mut next = [0; 3];
             let mut num_next = 0;
-            for n in [pos - ROW, pos - 1, pos + 1, pos + ROW] {
-                if n == prev || grid[n] == b'#' { continue }
+            for n in [pos - row, pos - 1, pos + 1, pos + row] {
+                if n == prev || n == start || cells[n] == b'#' { continue }
                 next[num_next] = n;
                 num_next += 1;
             }
@@ -91,13 +148,10 @@ fn graphify(
                 if nodes[pos] == 0 {
                     nodes[pos] = *num_nodes as Node;
                     *num_nodes += 1;
-                    graphify(grid, nodes, num_nodes, edges, num_edges, pos, &next[0..num_next]);
+                    graphify(grid, nodes, num_nodes, edges, pos, &next[0..num_next])?;
                 }
                 let end_id = nodes[pos];
-                if id < end_id {
-                    edges[*num_edges] = (id, end_id, hills, len);
-                    *num_edges += 1;
-                }
+                if id < end_id { edges.push((id, end_id, hills, len), &cells[pos..])? }
                 break;
             } else {
                 // Dead end. No edges to add.
@@ -105,41 +159,79 @@ fn graphify(
             }
         }
     }
+    return Ok(());
 }
 
-// `m[a][i]` is a pair `(b, n)` indicating an edge of length `n` between nodes `a` and `b`. If `n`
-// is `0`, it means that no such edge exists.
-type AdjacencyMatrix = [[(Node, u16); 4]; MAX_NODES];
+// Returns the number of nodes which appear in `edges`, including the start and the end.
+fn num_nodes(edges: &[Edge]) -> usize {
+    return edges.iter().map(|(a, b, _, _)| *a.max(b) as usize + 1).max().unwrap_or(0).max(2);
+}
 
-fn part1(edges: &[Edge]) -> AdjacencyMatrix {
-    let mut neighbors = [0; MAX_NODES];
-    let mut result = [[(0, 0); 4]; MAX_NODES];
-    for (a, b, hills, n) in edges {
-        if hills & UPHILL == 0 {
-            let i = neighbors[*a as usize];
-            neighbors[*a as usize] += 1;
-            result[*a as usize][i] = (*b, *n);
-        }
-        if hills & DOWNHILL == 0 {
-            let i = neighbors[*b as usize];
-            neighbors[*b as usize] += 1;
-            result[*b as usize][i] = (*a, *n);
-        }
+// Returns the number of edges from a node.
+fn degree(row: &Row) -> usize {
+    return row.iter().filter(|(_, n)| *n != 0).count();
+}
+
+// Builds the graph of the paths, in which each path leads both ways, or if `downhill` is set, only
+// the ways which don't walk up a slope. The buffers must have the lengths from `graph::buffer_lens`
+// for an undirected graph, which has room for either.
+pub fn paths<'a>(
+    edges: &[Edge],
+    downhill: bool,
+    offsets: &'a mut [u32],
+    adjacent: &'a mut [(Node, u16)],
+) -> Paths<'a> {
+    if !downhill {
+        let both_ways = edges.iter().map(|(a, b, _, n)| (*a, *b, *n));
+        return Graph::undirected(num_nodes(edges), both_ways, offsets, adjacent);
     }
-    return result;
+    let downhill = edges.iter().flat_map(|(a, b, hills, n)| {
+        let forwards = (hills & UPHILL == 0).then_some((*a, *b, *n));
+        let backwards = (hills & DOWNHILL == 0).then_some((*b, *a, *n));
+        return forwards.into_iter().chain(backwards);
+    });
+    return Graph::directed(num_nodes(edges), downhill, offsets, adjacent);
 }
 
-fn part2(edges: &[Edge]) -> AdjacencyMatrix {
-    let mut neighbors = [0; MAX_NODES];
-    let mut result = [[(0, 0); 4]; MAX_NODES];
-    for (a, b, _, n) in edges {
-        let i = neighbors[*a as usize];
-        neighbors[*a as usize] += 1;
-        result[*a as usize][i] = (*b, *n);
-        let j = neighbors[*b as usize];
-        neighbors[*b as usize] += 1;
-        result[*b as usize][j] = (*a, *n);
+// Copies the edges from each node of the graph into its row, at the start of `rows`.
+fn to_rows<'a>(graph: &Paths, rows: &'a mut [Row]) -> &'a mut [Row] {
+    let rows = &mut rows[..graph.num_nodes()];
+    for (u, row) in rows.iter_mut().enumerate() {
+        *row = [(0, 0); 4];
+        for (i, edge) in graph.edges(u as Node).iter().enumerate() { row[i] = *edge }
     }
+    return rows;
+}
+
+// Builds the rows for part 1, where slopes can only be walked downhill, at the start of `rows`.
+pub fn directed<'a>(
+    edges: &[Edge],
+    offsets: &mut [u32],
+    adjacent: &mut [(Node, u16)],
+    rows: &'a mut [Row],
+) -> &'a [Row] {
+    return to_rows(&paths(edges, true, offsets, adjacent), rows);
+}
+
+// Builds the rows for part 2, where slopes can be walked in either direction. `rows` must hold two
+// rows for each node: one for each copy of the graph, before and after pruning.
+pub fn undirected<'a>(
+    edges: &[Edge],
+    offsets: &mut [u32],
+    adjacent: &mut [(Node, u16)],
+    rows: &'a mut [Row],
+) -> &'a [Row] {
+    let n = num_nodes(edges);
+    let (result, pruned) = rows.split_at_mut(n);
+    to_rows(&paths(edges, false, offsets, adjacent), result);
+    if prune_perimeter(result, &mut pruned[..n]) { return &pruned[..n] }
+    return result;
+}
+
+// Stores a copy of `m` in `result` without the edges which lead backwards around the perimeter of
+// the graph. Returns false if the graph doesn't have the expected shape.
+fn prune_perimeter(m: &[Row], result: &mut [Row]) -> bool {
+    result.copy_from_slice(m);
     // Find the unique node connected to the exit.
     let s = result[START_NODE as usize][0].0 as usize;
     let e = result[END_NODE as usize][0].0 as usize;
@@ -151,33 +243,70 @@ fn part2(edges: &[Edge]) -> AdjacencyMatrix {
     // backwards around the perimeter,          . \ |   |   |
     // back towards S, because that will        . . N - N - N - E
     // always result in a dead-end.
-    for i in 0..2 {
+    'sides: for i in 0..2 {
         let mut pos = result[s][i].0 as usize;
-        loop {
+        for _ in 0..m.len() {
             // Find the next node around the perimeter.
-            let next = result[pos]
-                .iter()
-                .find(|(next, _)| neighbors[*next as usize] == 3)
-                .unwrap().0 as usize;
+            let next = result[pos].iter().find(|(next, _)| degree(&m[*next as usize]) == 3);
+            let Some(&(next, _)) = next else { return false };
+            let next = next as usize;
             // Remove the backwards edge.
-            let b = result[next as usize].iter().position(|(x, _)| *x as usize == pos).unwrap();
-            result[next as usize].copy_within(b + 1..4, b);
-            result[next as usize][3] = (0, 0);
-            if next == e { break }
+            let Some(b) = result[next].iter().position(|(x, _)| *x as usize == pos) else {
+                return false;
+            };
+            result[next].copy_within(b + 1..4, b);
+            result[next][3] = (0, 0);
+            if next == e { continue 'sides }
             pos = next;
         }
+        return false;
     }
-    return result;
+    return true;
+}
+
+// The set of nodes on the path being explored.
+trait NodeSet {
+    fn contains(&self, node: Node) -> bool;
+    fn toggle(&mut self, node: Node);
 }
 
-fn longest_path(m: &AdjacencyMatrix) -> u16 {
-    // `path[i]` is a tuple `(x, n, i)` where `x` is the node at the end of the path, `n` is the
-    // length of the path up until that point, and `i` is the index of the next neighbor of `x` to
-    // explore when recursing downwards.
-    let mut path = [(0, 0, 0); 36];
+impl NodeSet for u64 {
+    fn contains(&self, node: Node) -> bool { self & (1 << node) != 0 }
+    fn toggle(&mut self, node: Node) { *self ^= 1 << node }
+}
+
+impl NodeSet for &mut [u64] {
+    fn contains(&self, node: Node) -> bool { self[node as usize / 64] & (1 << (node % 64)) != 0 }
+    fn toggle(&mut self, node: Node) { self[node as usize / 64] ^= 1 << (node % 64) }
+}
+
+// Returns the length of the longest path from the start to the end, using `path` (which must hold
+// a step for each node) and `visited` (which must hold a bit for each node).
+pub fn longest_path(m: &[Row], path: &mut [Step], visited: &mut [u64]) -> u16 {
+    // The puzzle inputs have 36 nodes, so the search can usually run on fixed-size arrays with the
+    // set of visited nodes in a single word. Larger graphs use the same search on the buffers.
+    if m.len() <= 64 {
+        let mut fixed = [[(0, 0); 4]; 64];
+        fixed[..m.len()].copy_from_slice(m);
+        return search(fixed, 0u64, [(0, 0, 0); 64]);
+    }
+    let visited = &mut visited[..m.len().div_ceil(64)];
+    visited.fill(0);
+    return search(m, visited, &mut path[..m.len()]);
+}
+
+// `path[i]` is a tuple `(x, n, i)` where `x` is the node at the end of the path, `n` is the length
+// of the path up until that point, and `i` is the index of the next neighbor of `x` to explore when
+// recursing downwards.
+fn search(
+    m: impl AsRef<[Row]>,
+    mut visited: impl NodeSet,
+    mut path: impl AsMut<[Step]>,
+) -> u16 {
+    let (m, path) = (m.as_ref(), path.as_mut());
     path[0] = (START_NODE, 0, 0);
     let mut path_nodes = 1;
-    let mut visited: u64 = 1 << START_NODE;
+    visited.toggle(START_NODE);
     let mut best = 0;
     while path_nodes > 0 {
         let (pos, path_len, i) = &mut path[path_nodes - 1];
@@ -186,22 +315,214 @@ fn longest_path(m: &AdjacencyMatrix) -> u16 {
             *i += 1;
             if next == END_NODE {
                 best = best.max(*path_len + n);
-            } else if visited & (1 << next) == 0 {
-                visited |= 1 << next;
+            } else if !visited.contains(next) {
+                visited.toggle(next);
                 path[path_nodes] = (next, *path_len + n, 0);
                 path_nodes += 1;
             }
         } else {
-            visited ^= 1 << *pos;
+            visited.toggle(*pos);
             path_nodes -= 1;
         }
     }
     return best;
 }
 
-fn main() {
-    let mut edge_buffer = [(0, 0, 0, 0); MAX_EDGES];
-    let edges = read_input(&mut edge_buffer);
+#[cfg(feature = "std")]
+const PALETTE: Palette = Palette::new(&[
+    (b'#', [20, 60, 20]),
+    (b'.', [200, 180, 130]),
+    (b'>', [120, 160, 230]),
+    (b'v', [120, 160, 230]),
+    (b'<', [120, 160, 230]),
+    (b'^', [120, 160, 230]),
+    (b'N', [230, 40, 40]),
+]);
+
+// Reads the paths between the crossroads, with buffers sized from the input.
+#[cfg(feature = "std")]
+fn read_edges(input: &str) -> Result<Vec<Edge>> {
+    let (cells, nodes) = buffer_lens(input.as_bytes());
+    let mut edges = vec![(0, 0, 0, 0); 2 * nodes];
+    let len = read_input(input.as_bytes(), &mut vec![0; cells], &mut edges)?.len();
+    edges.truncate(len);
+    return Ok(edges);
+}
+
+// A function which builds the rows for one of the parts, such as `directed`.
+#[cfg(feature = "std")]
+type BuildRows = for<'a> fn(&[Edge], &mut [u32], &mut [(Node, u16)], &'a mut [Row]) -> &'a [Row];
+
+// Runs `longest_path` on the rows which `rows` builds from the edges, with buffers sized from the
+// number of nodes.
+#[cfg(feature = "std")]
+fn longest_path_with_buffers(edges: &[Edge], rows: BuildRows) -> u16 {
+    let n = num_nodes(edges);
+    let (offsets_len, adjacent_len) = graph::buffer_lens(n, edges.len(), false);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    let mut buffer = vec![[(0, 0); 4]; 2 * n];
+    let (mut path, mut visited) = (vec![(0, 0, 0); n], vec![0; n.div_ceil(64)]);
+    let rows = rows(edges, &mut offsets, &mut adjacent, &mut buffer);
+    return longest_path(rows, &mut path, &mut visited);
+}
+
+// Writes the graph of the paths in the DOT language, in the directions they can be walked in for
+// part 1, with each labelled with its length.
+#[cfg(feature = "std")]
+pub fn graph(input: &str) -> Result<String> {
+    let edges = read_edges(input)?;
+    let (offsets_len, adjacent_len) = graph::buffer_lens(num_nodes(&edges), edges.len(), false);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    let label = |node| match node {
+        START_NODE => "start".to_string(),
+        END_NODE => "end".to_string(),
+        node => node.to_string(),
+    };
+    let paths = paths(&edges, true, &mut offsets, &mut adjacent);
+    return Ok(paths.dot("day23", label, |n| n.to_string()));
+}
+
+// Draws the map with the nodes of the graph (the start, the end, and each crossroads) in red.
+#[cfg(feature = "std")]
+pub fn draw(input: &str, frames: &mut Frames) -> Result<()> {
+    read_edges(input)?;
+    let grid = Grid::parse(input.as_bytes())?;
+    let mut picture = grid.to_owned();
+    for y in 0..grid.height() {
+        for x in 0..grid.width() {
+            if grid[(x, y)] == b'#' { continue }
+            let open = grid.neighbours((x, y)).filter(|n| grid[*n] != b'#').count();
+            if open > 2 || y == 0 || y == grid.height() - 1 { picture[(x, y)] = b'N' }
+        }
+    }
+    frames.emit(&picture, &PALETTE);
+    return Ok(());
+}
 
-    print!("{}\n{}\n", longest_path(&part1(&edges)), longest_path(&part2(&edges)));
+// Part 2 is only fast if the perimeter can be pruned. Without that it still finds the right answer,
+// but it has to search a much larger number of paths.
+#[cfg(feature = "std")]
+pub fn lint(input: &str) -> Result<Vec<Assumption>> {
+    let edges = read_edges(input)?;
+    let n = num_nodes(&edges);
+    let (offsets_len, adjacent_len) = graph::buffer_lens(n, edges.len(), false);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    let mut rows = vec![[(0, 0); 4]; 2 * n];
+    let (m, pruned) = rows.split_at_mut(n);
+    let m = to_rows(&paths(&edges, false, &mut offsets, &mut adjacent), m);
+    let violation = match prune_perimeter(m, pruned) {
+        true => None,
+        false => Some("can't follow the perimeter from the entrance to the exit".to_string()),
+    };
+    return Ok(vec![Assumption::new(
+        "the crossroads form a grid, so the paths back around its perimeter can be pruned",
+        violation,
+    )]);
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    return Ok(longest_path_with_buffers(&read_edges(input)?, directed).into());
+}
+
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    return Ok(longest_path_with_buffers(&read_edges(input)?, undirected).into());
+}
+
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    let (cells, n) = buffer_lens(input.as_bytes());
+    let (mut nodes, mut edges) = (vec![0; cells], vec![(0, 0, 0, 0); 2 * n]);
+    let (mut rows, mut path) = (vec![[(0, 0); 4]; 2 * n], vec![(0, 0, 0); n]);
+    let mut visited = vec![0; n.div_ceil(64)];
+    let (offsets_len, adjacent_len) = graph::buffer_lens(n, 2 * n, false);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    return solve_bytes(
+        input.as_bytes(),
+        &mut nodes,
+        &mut edges,
+        &mut rows,
+        &mut path,
+        &mut visited,
+        &mut offsets,
+        &mut adjacent,
+    );
+}
+
+// Solves both parts, using buffers of the sizes given by `buffer_lens`.
+#[allow(clippy::too_many_arguments)]
+pub fn solve_bytes(
+    input: &[u8],
+    nodes: &mut [Node],
+    edges: &mut [Edge],
+    rows: &mut [Row],
+    path: &mut [Step],
+    visited: &mut [u64],
+    offsets: &mut [u32],
+    adjacent: &mut [(Node, u16)],
+) -> Result<(Answer, Answer)> {
+    let edges = read_input(input, nodes, edges)?;
+    let n = num_nodes(edges);
+    let (offsets_len, adjacent_len) = graph::buffer_lens(n, edges.len(), false);
+    check_len(input, rows, 2 * n)?;
+    check_len(input, path, n)?;
+    check_len(input, visited, n.div_ceil(64))?;
+    check_len(input, offsets, offsets_len)?;
+    check_len(input, adjacent, adjacent_len)?;
+    phase!("graph");
+    let part1 = longest_path(directed(edges, offsets, adjacent, rows), path, visited);
+    phase!("part 1");
+    let part2 = longest_path(undirected(edges, offsets, adjacent, rows), path, visited);
+    phase!("part 2");
+    return Ok((part1.into(), part2.into()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+#.#####################
+#.......#########...###
+#######.#########.#.###
+###.....#.>.>.###.#.###
+###v#####.#v#.###.#.###
+###.>...#.#.#.....#...#
+###v###.#.#.#########.#
+###...#.#.#.......#...#
+#####.#.#.#######.#.###
+#.....#.#.#.......#...#
+#.#####.#.#.#########v#
+#.#...#...#...###...>.#
+#.#.#v#######v###.###v#
+#...#.>.#...>.>.#.###.#
+#####v#.#.###v#.#.###.#
+#.....#...#...#.#.#...#
+#.#########.###.#.#.###
+#...###...#...#...#.###
+###.###.#.###v#####v###
+#...#...#.#.>.>.#.>.###
+#.###.###.#.###.#.#v###
+#.....###...###...#...#
+#####################.#
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(94));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(154));
+        assert_eq!(solve(EXAMPLE).unwrap(), (Answer::from(94), Answer::from(154)));
+    }
+
+    #[test]
+    fn many_crossroads() {
+        // A corridor with a dead end below every other cell, so that it has 100 crossroads but only
+        // one path from the entrance to the exit.
+        let w = 203;
+        let spurs: String = (1..w - 2).map(|x| if x % 2 == 0 { '.' } else { '#' }).collect();
+        let input = format!("#.{0}\n#{1}#\n#{2}.#\n{0}.#\n",
+                            "#".repeat(w - 2), ".".repeat(w - 2), spurs);
+        assert_eq!(solve(&input).unwrap(), (Answer::from(w), Answer::from(w)));
+    }
 }
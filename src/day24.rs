@@ -1,51 +1,64 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::Buffer;
+use crate::error::{eat, Error, Result};
+use crate::explain;
+use crate::phase;
 
-const MAX_HAILSTONES: usize = 300;
-type Vec3 = (f64, f64, f64);
+pub type Vec3 = (f64, f64, f64);
 type Plane = (Vec3, f64);
-type Hailstone = (Vec3, Vec3);
+pub type Hailstone = (Vec3, Vec3);
 
-fn read_num(input: &mut &[u8]) -> f64 {
-    let neg = input[0] == b'-';
+fn read_num(input: &mut &[u8]) -> Result<f64> {
+    let start = *input;
+    let neg = input.first() == Some(&b'-');
     if neg { *input = &input[1..] }
-    let mut x = 0;
+    let mut x: i64 = 0;
     let mut count = 0;
     while let [d @ b'0'..=b'9', ..] = &input[count..] {
-        x = 10 * x + (d - b'0') as i64;
+        let Some(v) = x.checked_mul(10).and_then(|x| x.checked_add((d - b'0') as i64)) else {
+            return Err(Error::new(start, "a number that fits in 64 bits"));
+        };
+        x = v;
         count += 1;
     }
-    if count == 0 { panic!("bad u64") }
+    if count == 0 { return Err(Error::new(start, "a number")) }
     *input = &input[count..];
-    return (if neg { -x } else { x }) as f64;
-}
-
-fn read_input<'a>(hailstones: &'a mut [Hailstone]) -> &'a [Hailstone] {
-    let mut buffer = [0; 20 * 1024];
-    let len = io::stdin().read(&mut buffer).unwrap();
-    if len == 0 || buffer[len - 1] != b'\n' { panic!("bad input") }
-    let mut input = &buffer[0..len];
-    let mut num_hailstones = 0;
-    while input.len() > 0 {
-        let x = read_num(&mut input);
-        input = input.strip_prefix(b", ").unwrap();
-        let y = read_num(&mut input);
-        input = input.strip_prefix(b", ").unwrap();
-        let z = read_num(&mut input);
-        input = input.strip_prefix(b" @ ").unwrap();
-        let dx = read_num(&mut input);
-        input = input.strip_prefix(b", ").unwrap();
-        let dy = read_num(&mut input);
-        input = input.strip_prefix(b", ").unwrap();
-        let dz = read_num(&mut input);
-        input = input.strip_prefix(b"\n").unwrap();
-        hailstones[num_hailstones] = ((x, y, z), (dx, dy, dz));
-        num_hailstones += 1;
+    return Ok((if neg { -x } else { x }) as f64);
+}
+
+// Returns the number of hailstones, which is the size of the buffer for `read_input`.
+pub fn buffer_len(input: &str) -> usize {
+    return input.lines().count();
+}
+
+// Reads the hailstones in the input into the start of `hailstones`.
+pub fn read_input<'a>(
+    mut input: &[u8],
+    hailstones: &'a mut [Hailstone],
+) -> Result<&'a [Hailstone]> {
+    let mut hailstones = Buffer::new(hailstones);
+    loop {
+        let line = input;
+        let x = read_num(&mut input)?;
+        input = eat(input, ", ")?;
+        let y = read_num(&mut input)?;
+        input = eat(input, ", ")?;
+        let z = read_num(&mut input)?;
+        input = eat(input, " @ ")?;
+        let dx = read_num(&mut input)?;
+        input = eat(input, ", ")?;
+        let dy = read_num(&mut input)?;
+        input = eat(input, ", ")?;
+        let dz = read_num(&mut input)?;
+        input = eat(input, "\n")?;
+        hailstones.push(((x, y, z), (dx, dy, dz)), line)?;
+        if input.is_empty() { break }
     }
-    return &hailstones[0..num_hailstones];
+    return Ok(hailstones.into_slice());
 }
 
-fn part1(hailstones: &[Hailstone]) -> u64 {
+// Counts the pairs of hailstones whose paths cross within the test area `[min, max]^2`.
+pub fn crossings(hailstones: &[Hailstone], min: f64, max: f64) -> u64 {
     let mut total = 0;
     for i in 0..hailstones.len() {
         let ((ax, ay, _), (vax, vay, _)) = hailstones[i];
@@ -67,16 +80,18 @@ fn part1(hailstones: &[Hailstone]) -> u64 {
             let tb = (-vay * (bx - ax) + vax * (by - ay)) / det;
             // We're only interested in future solutions, i.e. `ta > 0` and `tb > 0`.
             if ta < 0.0 || tb < 0.0 { continue }
-            // We're only interested in crossings that fall within the range [2e14, 4e14]^2.
+            // We're only interested in crossings that fall within the test area.
             let x = ax + vax * ta;
             let y = ay + vay * ta;
-            if 2e14 <= x && x <= 4e14 && 2e14 <= y && y <= 4e14 { total += 1 }
+            if (min..=max).contains(&x) && (min..=max).contains(&y) { total += 1 }
         }
     }
     return total;
 }
 
-fn part2(hailstones: &[Hailstone]) -> u64 {
+// Returns the sum of the coordinates of the rock's starting position, or what the calculation
+// needed from the hailstones if it couldn't find one.
+pub fn throw(hailstones: &[Hailstone]) -> Result<Answer, &'static str> {
     // Two objects will collide if their relative position vector is parallel to their relative
     // velocity vector and point in opposite directions. Our goal is to throw a rock which hits
     // every hailstone, so we know that the velocity of the stone relative to each hailstone must be
@@ -111,8 +126,12 @@ fn part2(hailstones: &[Hailstone]) -> u64 {
     //
     // By intersecting this plane with two others, we can find the unique velocity which satisfies
     // all three constraints.
-    let [a, b, c, ..] = hailstones else { panic!("need at least 3 hailstones") };
-    let velocity = intersect(plane(*a, *b), plane(*b, *c), plane(*a, *c));
+    let [a, b, c, ..] = hailstones else { return Err("at least 3 hailstones") };
+    let unsolvable = "hailstones whose paths determine a single throw";
+    let velocity = intersect(plane(*a, *b), plane(*b, *c), plane(*a, *c)).ok_or(unsolvable)?;
+    // The velocity is a whole number, so rounding it removes the error from the large products.
+    let velocity = (round(velocity.0), round(velocity.1), round(velocity.2));
+    explain!("the rock's velocity is {:?}", velocity);
     // Once we know the stone's velocity, we can find the starting position from two hailstones by
     // finding their collision times:
     //
@@ -136,13 +155,32 @@ fn part2(hailstones: &[Hailstone]) -> u64 {
     let d = vsub(b.0, a.0);
     let (px, py, _) = vsub(a.1, velocity);
     let (qx, qy, _) = vsub(velocity, b.1);
-    let (ta, _, _) = solve((px, py, 0.0), (qx, qy, 0.0), (0.0, 0.0, 1.0), d);
+    let (ta, _, _) =
+        solve_linear((px, py, 0.0), (qx, qy, 0.0), (0.0, 0.0, 1.0), d).ok_or(unsolvable)?;
+    // Likewise for the collision time, after which the remaining arithmetic is exact.
+    let ta = round(ta);
     // Knowing ta, we can derive the initial position of the stone:
     //
     //   p + ta * v = a + ta * va
     //   p = a + ta * (va - v)
     let position = vadd(a.0, vmul(ta, vsub(a.1, velocity)));
-    return (position.0 + position.1 + position.2) as u64;
+    explain!("it hits the first hailstone at t = {}, so it starts at {:?}", ta, position);
+    // The coordinates are only exact below 2^53, beyond which an `f64` skips whole numbers. They
+    // are added up exactly, as `Answer`s.
+    let exact = |x: f64| if x.abs() < 9007199254740992.0 { Some(x as i64) } else { None };
+    let too_far = "a throw which starts less than 2^53 from the origin";
+    let (x, y, z) = (exact(position.0), exact(position.1), exact(position.2));
+    let (Some(x), Some(y), Some(z)) = (x, y, z) else { return Err(too_far) };
+    return Ok(Answer::from(x as i128 + y as i128 + z as i128));
+}
+
+// Rounds half way cases away from zero, like `f64::round`, which needs `std`.
+fn round(x: f64) -> f64 {
+    // Every `f64` of magnitude 2^52 or more is a whole number already.
+    if x.is_nan() || x.abs() >= 4503599627370496.0 { return x }
+    let whole = x.abs() as u64 as f64;
+    let rounded = if x.abs() - whole >= 0.5 { whole + 1.0 } else { whole };
+    return rounded.copysign(x);
 }
 
 fn vadd((ax, ay, az): Vec3, (bx, by, bz): Vec3) -> Vec3 { (ax + bx, ay + by, az + bz) }
@@ -160,23 +198,72 @@ fn plane((a, va): Hailstone, (b, vb): Hailstone) -> Plane {
     return (n, d);
 }
 
-fn solve(c0: Vec3, c1: Vec3, c2: Vec3, x: Vec3) -> Vec3 {
+fn solve_linear(c0: Vec3, c1: Vec3, c2: Vec3, x: Vec3) -> Option<Vec3> {
     let det = vdot(c0, vcross(c1, c2));
-    if det == 0.0 { panic!("unsolvable") }
-    return (vdot(x, vcross(c1, c2)) / det,
-            vdot(x, vcross(c2, c0)) / det,
-            vdot(x, vcross(c0, c1)) / det);
+    if det == 0.0 || !det.is_finite() { return None }
+    return Some((vdot(x, vcross(c1, c2)) / det,
+                 vdot(x, vcross(c2, c0)) / det,
+                 vdot(x, vcross(c0, c1)) / det));
 }
 
-fn intersect((r0, x0): Plane, (r1, x1): Plane, (r2, x2): Plane) -> Vec3 {
+fn intersect((r0, x0): Plane, (r1, x1): Plane, (r2, x2): Plane) -> Option<Vec3> {
     let (c0, c1, c2) = ((r0.0, r1.0, r2.0), (r0.1, r1.1, r2.1), (r0.2, r1.2, r2.2));
     let x = (x0, x1, x2);
-    return solve(c0, c1, c2, x);
+    return solve_linear(c0, c1, c2, x);
+}
+
+fn throw_rock(input: &[u8], hailstones: &[Hailstone]) -> Result<Answer> {
+    return throw(hailstones).map_err(|expected| Error::new(&input[input.len()..], expected));
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    let mut buffer = vec![Hailstone::default(); buffer_len(input)];
+    let hailstones = read_input(input.as_bytes(), &mut buffer)?;
+    return Ok(crossings(hailstones, 2e14, 4e14).into());
 }
 
-fn main() {
-    let mut hailstone_buffer = [((0.0, 0.0, 0.0), (0.0, 0.0, 0.0)); MAX_HAILSTONES];
-    let hailstones = read_input(&mut hailstone_buffer);
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer> {
+    let mut buffer = vec![Hailstone::default(); buffer_len(input)];
+    let hailstones = read_input(input.as_bytes(), &mut buffer)?;
+    return throw_rock(input.as_bytes(), hailstones);
+}
 
-    print!("{}\n{}\n", part1(hailstones), part2(hailstones));
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<(Answer, Answer)> {
+    return solve_bytes(input.as_bytes(), &mut vec![Hailstone::default(); buffer_len(input)]);
+}
+
+// Solves both parts, using a buffer of the size from `buffer_len` for the hailstones.
+pub fn solve_bytes(input: &[u8], buffer: &mut [Hailstone]) -> Result<(Answer, Answer)> {
+    let hailstones = read_input(input, buffer)?;
+    phase!("parse");
+    let part1 = crossings(hailstones, 2e14, 4e14);
+    phase!("part 1");
+    let part2 = throw_rock(input, hailstones)?;
+    phase!("part 2");
+    return Ok((part1.into(), part2));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+19, 13, 30 @ -2, 1, -2
+18, 19, 22 @ -1, -1, -2
+20, 25, 34 @ -2, -2, -4
+12, 31, 28 @ -1, -2, -1
+20, 19, 15 @ 1, -5, -3
+";
+
+    #[test]
+    fn example() {
+        let mut buffer = vec![Hailstone::default(); buffer_len(EXAMPLE)];
+        let hailstones = read_input(EXAMPLE.as_bytes(), &mut buffer).unwrap();
+        assert_eq!(crossings(hailstones, 7.0, 27.0), 2);
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(0));
+        assert_eq!(part2(EXAMPLE).unwrap(), Answer::from(47));
+    }
 }
@@ -1,62 +1,99 @@
-use std::io;
-use std::io::Read;
+use crate::Answer;
+use crate::buffer::{check_len, Buffer};
+use crate::error::{eat, Error, Result};
+use crate::graph::{self, name_key, Graph, Names, NodeId, NAME_KEYS, NO_NODE};
+use crate::phase;
 use rand::distributions::{Distribution, Uniform};
-use rand::rngs::ThreadRng;
+use rand::Rng;
 
-const MAX_NODES: usize = 4096;
-const MAX_EDGES: usize = 8192;
-const NO_ID: u16 = MAX_NODES as u16;
+// Each attempt finds the minimum cut with a probability of roughly `1 / log(n)`, so after this many
+// failures it's worth checking that there really is a cut of 3 wires to find.
+const ATTEMPTS_BEFORE_CHECK: usize = 10;
 
-fn read_id(input: &mut &[u8], id_buf: &mut[u16], next_id: &mut u16) -> u16 {
+fn read_id<'i>(input: &mut &'i [u8], names: &mut Names<'_, 'i>) -> Result<NodeId> {
     match *input {
-        [a @ b'a'..=b'z', b @ b'a'..=b'z', c @ b'a'..=b'z', tail @ ..] => {
+        [b'a'..=b'z', b'a'..=b'z', b'a'..=b'z', tail @ ..] => {
+            let name = &input[..3];
             *input = tail;
-            let index = ((a - b'a') as usize * 26 + (b - b'a') as usize) * 26 + (c - b'a') as usize;
-            if id_buf[index] == NO_ID {
-                id_buf[index] = *next_id;
-                *next_id += 1;
-            }
-            return id_buf[index];
+            return names.intern(name_key(name).unwrap(), name);
         }
-        _ => panic!("bad input"),
+        _ => return Err(Error::new(input, "a component name of 3 letters")),
     }
 }
 
-fn read_input<'a>(edges: &'a mut[(u16, u16)]) -> (u16, &'a [(u16, u16)]) {
-    let mut buffer = [0; 20 * 1024];
-    let len = io::stdin().read(&mut buffer).unwrap();
-    let mut input = &buffer[0..len];
-    let mut id_buf = [NO_ID; 26 * 26 * 26];
-    let mut next_id = 0;
-    let mut num_edges = 0;
-    while input.len() > 0 {
-        let a = read_id(&mut input, &mut id_buf, &mut next_id);
-        input = input.strip_prefix(b": ").unwrap();
+// Reads the connections in the input into the start of `edges`, using `ids` (which must have an
+// entry for each name key) and `names` (which must have an entry for each component) to number the
+// components, and returns their names and the number of connections.
+pub fn read_input<'a, 'i>(
+    mut input: &'i [u8],
+    ids: &'a mut [NodeId],
+    names: &'a mut [&'i [u8]],
+    edges: &mut [(NodeId, NodeId)],
+) -> Result<(Names<'a, 'i>, usize)> {
+    let mut names = Names::new(ids, names);
+    let mut edges = Buffer::new(edges);
+    loop {
+        let a = read_id(&mut input, &mut names)?;
+        input = eat(input, ": ")?;
         loop {
-            let b = read_id(&mut input, &mut id_buf, &mut next_id);
-            edges[num_edges] = (a, b);
-            num_edges += 1;
+            let start = input;
+            let b = read_id(&mut input, &mut names)?;
+            if a == b { return Err(Error::new(start, "a connection to a different component")) }
+            edges.push((a, b), start)?;
             match input {
                 [b' ', tail @ ..] => input = tail,
                 [b'\n', tail @ ..] => {
                     input = tail;
                     break;
                 }
-                _ => panic!("bad input"),
+                _ => return Err(Error::new(input, "' ' or '\\n'")),
             }
         }
+        if input.is_empty() { break }
+    }
+    return Ok((names, edges.len()));
+}
+
+// Returns the number of levels at which `karger_stein` recurses on a copy of the graph.
+fn depth(mut num_nodes: usize) -> usize {
+    let mut depth = 0;
+    while num_nodes >= 24 {
+        num_nodes = num_nodes * 2 / 3;
+        depth += 1;
     }
-    return (next_id, &mut edges[0..num_edges]);
+    return depth;
+}
+
+// Returns the sizes of the buffers for a graph with `n` components and `e` connections. The first
+// holds the connections, a copy for each attempt, and a copy for each level of `karger_stein`. The
+// second is shared by the sizes of the merged components in `karger_stein`, which also need a copy
+// for each level, and the search in `is_min_cut_3`. The third holds the edges of the graph that
+// the search runs on.
+fn lens(n: usize, e: usize) -> (usize, usize, usize) {
+    let depth = depth(n);
+    let (offsets_len, adjacent_len) = graph::buffer_lens(n, e, false);
+    return (e * (depth + 2), (n * (depth + 1)).max(offsets_len + e + 2 * n), adjacent_len);
+}
+
+// Returns the sizes of the buffers that `solve_bytes` needs, from the number of names in the input
+// (which bounds the number of components) and the number of connections: one for the names, and
+// the three from `lens`.
+pub fn buffer_lens(input: &str) -> (usize, usize, usize, usize) {
+    let names = input.split_ascii_whitespace().count();
+    let (edges_len, scratch_len, adjacent_len) =
+        lens(names, names.saturating_sub(input.lines().count()));
+    return (names, edges_len, scratch_len, adjacent_len);
 }
 
 fn contract<'a>(
-    rng: &mut ThreadRng,
+    rng: &mut impl Rng,
     size: &mut [u32],
-    mut edges: &'a mut [(u16, u16)],
+    mut edges: &'a mut [(NodeId, NodeId)],
     mut num_nodes: u16,
     target_num_nodes: u16,
-) -> &'a mut [(u16, u16)] {
-    while num_nodes > target_num_nodes {
+) -> &'a mut [(NodeId, NodeId)] {
+    // The graph might not be connected, in which case we run out of edges to pick.
+    while num_nodes > target_num_nodes && !edges.is_empty() {
         // Pick a random edge.
         let i = Uniform::from(0..edges.len()).sample(rng);
         let (a, b) = edges[i];
@@ -79,51 +116,193 @@ fn contract<'a>(
     return edges;
 }
 
+// Finds a small cut of the graph, using `sizes` and `edge_copies` to hold the copies of the graph
+// for each level of the recursion.
 fn karger_stein(
-    rng: &mut ThreadRng,
+    rng: &mut impl Rng,
     num_nodes: u16,
     size: &mut [u32],
-    edges: &mut [(u16, u16)],
+    edges: &mut [(NodeId, NodeId)],
+    sizes: &mut [u32],
+    edge_copies: &mut [(NodeId, NodeId)],
 ) -> (usize, u32, u32) {
     if num_nodes < 24 {
         let edges = contract(rng, size, edges, num_nodes, 2);
         let cut_size = edges.len();
-        let (a, b) = edges[0];
+        let Some(&(a, b)) = edges.first() else { return (0, 0, 0) };
         return (cut_size, size[a as usize], size[b as usize]);
     } else {
         let t = num_nodes * 2 / 3;
 
         let (n1, a1, b1) = {
-            let mut size_copy_buffer = [0; MAX_NODES];
-            let size_copy = &mut size_copy_buffer[0..size.len()];
+            let (size_copy, sizes) = sizes.split_at_mut(size.len());
             size_copy.copy_from_slice(size);
-            let mut edge_copy_buffer = [(0, 0); MAX_EDGES];
-            let edges_copy = &mut edge_copy_buffer[0..edges.len()];
+            let (edges_copy, edge_copies) = edge_copies.split_at_mut(edges.len());
             edges_copy.copy_from_slice(edges);
             let edges_copy = contract(rng, size_copy, edges_copy, num_nodes, t);
-            karger_stein(rng, t, size_copy, edges_copy)
+            karger_stein(rng, t, size_copy, edges_copy, sizes, edge_copies)
         };
 
         let edges = contract(rng, size, edges, num_nodes, t);
-        let (n2, a2, b2) = karger_stein(rng, t, size, edges);
+        let (n2, a2, b2) = karger_stein(rng, t, size, edges, sizes, edge_copies);
 
         return if n1 < n2 { (n1, a1, b1) } else { (n2, a2, b2) };
     }
 }
 
-fn main() {
-    let mut edge_buffer = [(0, 0); MAX_EDGES];
-    let (num_nodes, edges) = read_input(&mut edge_buffer);
-    let mut rng = rand::thread_rng();
-    loop {
-        let mut size = [1; MAX_NODES];
-        let mut edge_copy_buffer = [(0, 0); MAX_EDGES];
-        let edges_copy = &mut edge_copy_buffer[0..edges.len()];
-        edges_copy.copy_from_slice(edges);
-        let (n, a, b) = karger_stein(&mut rng, num_nodes, &mut size, edges_copy);
-        if n == 3 {
-            print!("{}\n", a * b);
-            break;
+const NONE: u32 = u32::MAX;
+
+// Checks that the minimum cut of the graph is exactly 3 wires. Node 0 is on one side of every cut,
+// so this is the smallest maximum flow from node 0 to any other node. With unit capacities, the
+// flow is the number of edge-disjoint paths, and we can stop looking after 4 of them. `scratch`
+// must hold `3 * n + 1 + edges.len()` values, and `adjacent` an entry for each end of each edge.
+fn is_min_cut_3(
+    num_nodes: u16,
+    edges: &[(NodeId, NodeId)],
+    scratch: &mut [u32],
+    adjacent: &mut [(NodeId, u32)],
+) -> bool {
+    let n = num_nodes as usize;
+    let (offsets, scratch) = scratch.split_at_mut(n + 1);
+    let (flow, scratch) = scratch.split_at_mut(edges.len());
+    let (via, scratch) = scratch.split_at_mut(n);
+    let queue = &mut scratch[..n];
+    // Each edge in the graph is labelled with its index in `edges`, which its flow is kept under.
+    let numbered = edges.iter().enumerate().map(|(e, (a, b))| (*a, *b, e as u32));
+    let graph = Graph::undirected(n, numbered, offsets, adjacent);
+    let mut min_flow = 4;
+    for t in 1..n {
+        // `flow[e]` is 1 if edge `e` carries flow from `a` to `b`, 2 for the other direction, or 0
+        // if it carries none.
+        flow.fill(0);
+        let mut total = 0;
+        while total < min_flow {
+            // Find an augmenting path with a breadth-first search, recording the edge used to
+            // reach each node.
+            via.fill(NONE);
+            queue[0] = 0;
+            let (mut i, mut len) = (0, 1);
+            while i < len && via[t] == NONE {
+                let u = queue[i] as usize;
+                i += 1;
+                for (v, e) in graph.edges(u as NodeId) {
+                    let f = if edges[*e as usize].0 as usize == u { 1 } else { 2 };
+                    let v = *v as usize;
+                    if v == 0 || via[v] != NONE || flow[*e as usize] == f { continue }
+                    via[v] = *e;
+                    queue[len] = v as u32;
+                    len += 1;
+                }
+            }
+            if via[t] == NONE { break }
+            let mut v = t;
+            while v != 0 {
+                let e = via[v] as usize;
+                let (a, b) = edges[e];
+                let (u, f) = if b as usize == v { (a as usize, 1) } else { (b as usize, 2) };
+                // Flow in the opposite direction cancels out.
+                flow[e] = if flow[e] == 0 { f } else { 0 };
+                v = u;
+            }
+            total += 1;
         }
+        min_flow = min_flow.min(total);
+    }
+    return min_flow == 3;
+}
+
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer> {
+    let (names_len, edges_len, scratch_len, adjacent_len) = buffer_lens(input);
+    let input = input.as_bytes();
+    let (mut names, mut edges) = (vec![&input[..0]; names_len], vec![(0, 0); edges_len]);
+    let (mut scratch, mut adjacent) = (vec![0; scratch_len], vec![(0, 0); adjacent_len]);
+    let rng = &mut rand::thread_rng();
+    return solve_bytes(input, rng, &mut names, &mut edges, &mut scratch, &mut adjacent);
+}
+
+// There is no part 2 on the last day.
+#[cfg(feature = "std")]
+pub fn solve(input: &str) -> Result<Answer> {
+    return part1(input);
+}
+
+// Writes the connections between the components in the DOT language.
+#[cfg(feature = "std")]
+pub fn graph(input: &str) -> Result<String> {
+    let (names_len, edges_len, _, _) = buffer_lens(input);
+    let input = input.as_bytes();
+    let (mut ids, mut names) = (vec![NO_NODE; NAME_KEYS], vec![&input[..0]; names_len]);
+    let mut edges = vec![(0, 0); edges_len];
+    let (names, num_edges) = read_input(input, &mut ids, &mut names, &mut edges)?;
+    let (offsets_len, adjacent_len) = graph::buffer_lens(names.len(), num_edges, false);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, ()); adjacent_len]);
+    let wires = edges[..num_edges].iter().map(|(a, b)| (*a, *b, ()));
+    let graph = Graph::undirected(names.len(), wires, &mut offsets, &mut adjacent);
+    let label = |id| String::from_utf8_lossy(names.name(id)).into_owned();
+    return Ok(graph.dot("day25", label, |_| String::new()));
+}
+
+// Solves part 1 with random numbers from `rng`, using buffers of the sizes from `buffer_lens`.
+pub fn solve_bytes<'i>(
+    input: &'i [u8],
+    rng: &mut impl Rng,
+    names: &mut [&'i [u8]],
+    edges: &mut [(NodeId, NodeId)],
+    scratch: &mut [u32],
+    adjacent: &mut [(NodeId, u32)],
+) -> Result<Answer> {
+    let mut ids = [NO_NODE; NAME_KEYS];
+    let (names, num_edges) = read_input(input, &mut ids, names, edges)?;
+    let num_nodes = names.len() as u16;
+    let end = &input[input.len()..];
+    let (edges_len, scratch_len, adjacent_len) = lens(num_nodes as usize, num_edges);
+    check_len(input, edges, edges_len)?;
+    check_len(input, scratch, scratch_len)?;
+    check_len(input, adjacent, adjacent_len)?;
+    phase!("parse");
+    let (edges, edge_copies) = edges.split_at_mut(num_edges);
+    let (edges_copy, edge_copies) = edge_copies.split_at_mut(num_edges);
+    for attempt in 0.. {
+        if attempt == ATTEMPTS_BEFORE_CHECK {
+            let is_min_cut = is_min_cut_3(num_nodes, edges, scratch, adjacent);
+            phase!("max flow check");
+            if !is_min_cut { break }
+        }
+        let (size, sizes) = scratch.split_at_mut(num_nodes as usize);
+        size.fill(1);
+        edges_copy.copy_from_slice(edges);
+        let (n, a, b) = karger_stein(rng, num_nodes, size, edges_copy, sizes, edge_copies);
+        phase!("karger-stein");
+        if n == 3 { return Ok((a * b).into()) }
+        if n < 3 { break }
+    }
+    return Err(Error::new(end, "components which are split in two by cutting 3 wires"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "\
+jqt: rhn xhk nvd
+rsh: frs pzl lsr
+xhk: hfx
+cmg: qnr nvd lhk bvb
+rhn: xhk bvb hfx
+bvb: xhk hfx
+pzl: lsr hfx nvd
+qnr: nvd
+ntq: jqt hfx bvb xhk
+nvd: lhk
+lsr: lhk
+rzs: qnr cmg lsr rsh
+frs: qnr lhk lsr
+";
+
+    #[test]
+    fn example() {
+        assert_eq!(part1(EXAMPLE).unwrap(), Answer::from(54));
+        assert_eq!(solve(EXAMPLE).unwrap(), Answer::from(54));
     }
 }
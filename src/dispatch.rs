@@ -0,0 +1,280 @@
+use crate::cli::{self, Format};
+use crate::error::ParseError;
+use crate::lint::Assumption;
+use crate::{explain, input, phase, phases, render, Answer, Part, Solution, has_part};
+use crate::{day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12};
+use crate::{day13, day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24};
+use crate::day25;
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+// The `std` layer on top of the solvers: dispatch by day to their `&str` entry points, which
+// allocate whatever buffers the core needs, and the entry point of the per-day binaries.
+
+// Solves the puzzle for the given day (1-25) with the given input. With phase timing on, any time
+// after the solver's last phase ends is counted as "rest".
+pub fn solve(day: u8, input: &str) -> Result<Solution, ParseError> {
+    phases::start();
+    let result = match day {
+        1 => day01::solve(input).map(Solution::from),
+        2 => day02::solve(input).map(Solution::from),
+        3 => day03::solve(input).map(Solution::from),
+        4 => day04::solve(input).map(Solution::from),
+        5 => day05::solve(input).map(Solution::from),
+        6 => day06::solve(input).map(Solution::from),
+        7 => day07::solve(input).map(Solution::from),
+        8 => day08::solve(input).map(Solution::from),
+        9 => day09::solve(input).map(Solution::from),
+        10 => day10::solve(input).map(Solution::from),
+        11 => day11::solve(input).map(Solution::from),
+        12 => day12::solve(input).map(Solution::from),
+        13 => day13::solve(input).map(Solution::from),
+        14 => day14::solve(input).map(Solution::from),
+        15 => day15::solve(input).map(Solution::from),
+        16 => day16::solve(input).map(Solution::from),
+        17 => day17::solve(input).map(Solution::from),
+        18 => day18::solve(input).map(Solution::from),
+        19 => day19::solve(input).map(Solution::from),
+        20 => day20::solve(input).map(Solution::from),
+        21 => day21::solve(input).map(Solution::from),
+        22 => day22::solve(input).map(Solution::from),
+        23 => day23::solve(input).map(Solution::from),
+        24 => day24::solve(input).map(Solution::from),
+        25 => day25::solve(input).map(Solution::from),
+        _ => panic!("no solver for day {}", day),
+    };
+    phase!("rest");
+    return result.map_err(|e| ParseError::new(day, input, e));
+}
+
+// Solves one part of the puzzle for the given day, without doing the work that
+// is only needed for the other part.
+pub fn solve_part(day: u8, part: Part, input: &str) -> Result<Answer, ParseError> {
+    phases::start();
+    let result = match (day, part) {
+        (1, Part::One) => day01::part1(input),
+        (1, Part::Two) => day01::part2(input),
+        (2, Part::One) => day02::part1(input),
+        (2, Part::Two) => day02::part2(input),
+        (3, Part::One) => day03::part1(input),
+        (3, Part::Two) => day03::part2(input),
+        (4, Part::One) => day04::part1(input),
+        (4, Part::Two) => day04::part2(input),
+        (5, Part::One) => day05::part1(input),
+        (5, Part::Two) => day05::part2(input),
+        (6, Part::One) => day06::part1(input),
+        (6, Part::Two) => day06::part2(input),
+        (7, Part::One) => day07::part1(input),
+        (7, Part::Two) => day07::part2(input),
+        (8, Part::One) => day08::part1(input),
+        (8, Part::Two) => day08::part2(input),
+        (9, Part::One) => day09::part1(input),
+        (9, Part::Two) => day09::part2(input),
+        (10, Part::One) => day10::part1(input),
+        (10, Part::Two) => day10::part2(input),
+        (11, Part::One) => day11::part1(input),
+        (11, Part::Two) => day11::part2(input),
+        (12, Part::One) => day12::part1(input),
+        (12, Part::Two) => day12::part2(input),
+        (13, Part::One) => day13::part1(input),
+        (13, Part::Two) => day13::part2(input),
+        (14, Part::One) => day14::part1(input),
+        (14, Part::Two) => day14::part2(input),
+        (15, Part::One) => day15::part1(input),
+        (15, Part::Two) => day15::part2(input),
+        (16, Part::One) => day16::part1(input),
+        (16, Part::Two) => day16::part2(input),
+        (17, Part::One) => day17::part1(input),
+        (17, Part::Two) => day17::part2(input),
+        (18, Part::One) => day18::part1(input),
+        (18, Part::Two) => day18::part2(input),
+        (19, Part::One) => day19::part1(input),
+        (19, Part::Two) => day19::part2(input),
+        (20, Part::One) => day20::part1(input),
+        (20, Part::Two) => day20::part2(input),
+        (21, Part::One) => day21::part1(input),
+        (21, Part::Two) => day21::part2(input),
+        (22, Part::One) => day22::part1(input),
+        (22, Part::Two) => day22::part2(input),
+        (23, Part::One) => day23::part1(input),
+        (23, Part::Two) => day23::part2(input),
+        (24, Part::One) => day24::part1(input),
+        (24, Part::Two) => day24::part2(input),
+        (25, Part::One) => day25::part1(input),
+        _ => panic!("no solver for day {} part {}", day, part),
+    };
+    phase!("rest");
+    return result.map_err(|e| ParseError::new(day, input, e));
+}
+
+// The days which can draw pictures of their working with `draw`.
+pub const DRAWABLE_DAYS: [u8; 6] = [10, 14, 16, 17, 21, 23];
+
+// Draws one or more frames showing how the given day's puzzle is solved. Frames are written as
+// they are produced, so `frames` should be checked for I/O errors afterwards.
+pub fn draw(day: u8, input: &str, frames: &mut render::Frames) -> Result<(), ParseError> {
+    let result = match day {
+        10 => day10::draw(input, frames),
+        14 => day14::draw(input, frames),
+        16 => day16::draw(input, frames),
+        17 => day17::draw(input, frames),
+        21 => day21::draw(input, frames),
+        23 => day23::draw(input, frames),
+        _ => panic!("no renderer for day {}", day),
+    };
+    return result.map_err(|e| ParseError::new(day, input, e));
+}
+
+// The days whose puzzles are networks, which can be written out with `graph`.
+pub const GRAPH_DAYS: [u8; 4] = [8, 20, 23, 25];
+
+// Writes the network in the given day's input in Graphviz's DOT language.
+pub fn graph(day: u8, input: &str) -> Result<String, ParseError> {
+    let result = match day {
+        8 => day08::graph(input),
+        20 => day20::graph(input),
+        23 => day23::graph(input),
+        25 => day25::graph(input),
+        _ => panic!("no graph for day {}", day),
+    };
+    return result.map_err(|e| ParseError::new(day, input, e));
+}
+
+// The days whose solvers make assumptions about their input which can be checked with `lint`.
+pub const LINTABLE_DAYS: [u8; 5] = [3, 8, 20, 21, 23];
+
+// Checks each of the assumptions that the given day's solver makes about its input. The input must
+// still parse, so a parse error is returned as usual.
+pub fn lint(day: u8, input: &str) -> Result<Vec<Assumption>, ParseError> {
+    let result = match day {
+        3 => day03::lint(input),
+        8 => day08::lint(input),
+        20 => day20::lint(input),
+        21 => day21::lint(input),
+        23 => day23::lint(input),
+        _ => panic!("no linter for day {}", day),
+    };
+    return result.map_err(|e| ParseError::new(day, input, e));
+}
+
+// Solves just the given part if there is one, or both parts otherwise.
+pub fn solve_parts(day: u8, part: Option<Part>, input: &str) -> Result<Solution, ParseError> {
+    match part {
+        Some(part) => solve_part(day, part, input).map(|answer| Solution::only(part, answer)),
+        None => solve(day, input),
+    }
+}
+
+// The command line arguments of a per-day binary.
+struct Args {
+    format: Format,
+    // The part to solve, if only one part was requested.
+    part: Option<Part>,
+    explain: bool,
+    // The input files to solve, in order. Without any, the input is read from stdin.
+    files: Vec<PathBuf>,
+}
+
+fn parse_args(day: u8, mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut format = Format::Text;
+    let mut part = None;
+    let mut explain = false;
+    let mut files = Vec::new();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let Some(value) = args.next() else {
+                    return Err("--part needs a value".to_string());
+                };
+                let value = cli::parse_part(&value)?;
+                if !has_part(day, value) {
+                    return Err(format!("day {} has no part {}", day, value));
+                }
+                part = Some(value);
+            }
+            "--format" => {
+                let Some(value) = args.next() else {
+                    return Err("--format needs a value".to_string());
+                };
+                format = cli::parse_format(&value)?;
+            }
+            "--explain" => explain = true,
+            flag if flag.starts_with("--") => return Err(format!("unexpected argument: {}", arg)),
+            _ => files.push(PathBuf::from(arg)),
+        }
+    }
+    return Ok(Args{format, part, explain, files});
+}
+
+// Solves a single input and prints its answers, or a diagnostic to stderr if it is malformed.
+// `file` is where the input came from, if it wasn't stdin, and the answers are labelled with it
+// when `label` is set. Returns false if the input couldn't be solved.
+fn run_input(day: u8, args: &Args, input: &str, file: Option<&Path>, label: bool) -> bool {
+    let start = Instant::now();
+    let result = solve_parts(day, args.part, input);
+    let elapsed = start.elapsed();
+    match (result, file) {
+        (Ok(solution), _) => {
+            match args.format {
+                Format::Json => println!("{}", cli::json(day, file, &solution, elapsed)),
+                Format::Text if label => {
+                    println!("{}: {}", file.unwrap().display(), cli::answers(&solution));
+                }
+                Format::Text => print!("{}", solution),
+            }
+            return true;
+        }
+        (Err(error), Some(file)) => eprintln!("{}: {}", file.display(), error),
+        (Err(error), None) => eprintln!("{}", error),
+    }
+    return false;
+}
+
+// Entry point for the per-day binaries: solves the given day for each input file named on the
+// command line, or for stdin if there are none, and prints the answers to stdout. With more than
+// one file, each line of answers starts with the file's name. Malformed inputs are reported on
+// stderr, and the remaining files are still solved.
+pub fn run(day: u8) -> ExitCode {
+    let usage = format!("usage: day{:02} [--part 1|2] [--format text|json] [--explain] [<file>...]",
+                        day);
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let [flag] = args.as_slice() {
+        if flag == "-h" || flag == "--help" {
+            println!("{}", usage);
+            return ExitCode::SUCCESS;
+        }
+    }
+    let args = match parse_args(day, args.into_iter()) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}\n\n{}", message, usage);
+            return ExitCode::from(2);
+        }
+    };
+    explain::set_enabled(args.explain);
+    if args.files.is_empty() {
+        let input = match input::read(io::stdin().lock()) {
+            Ok(input) => input,
+            Err(error) => {
+                eprintln!("day{:02}: can't read input: {}", day, error);
+                return ExitCode::FAILURE;
+            }
+        };
+        let ok = run_input(day, &args, &input, None, false);
+        return if ok { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+    }
+    let mut ok = true;
+    for file in &args.files {
+        match input::read_file(file) {
+            Ok(input) => ok &= run_input(day, &args, &input, Some(file), args.files.len() > 1),
+            Err(error) => {
+                eprintln!("day{:02}: can't read {}: {}", day, file.display(), error);
+                ok = false;
+            }
+        }
+    }
+    return if ok { ExitCode::SUCCESS } else { ExitCode::FAILURE };
+}
@@ -0,0 +1,135 @@
+use core::fmt;
+use core::str::FromStr;
+
+// A description of what a parser was expecting to find.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Expected {
+    // A specific byte, such as `b','`.
+    Byte(u8),
+    // A specific sequence of characters, such as `" -> "`.
+    Token(&'static str),
+    // A description of a class of input, such as `"a number"`.
+    Text(&'static str),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expected::Byte(b) => write!(f, "{:?}", *b as char),
+            Expected::Token(s) => write!(f, "{:?}", s),
+            Expected::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+// A parse error at some position within the input. Parsers work on subslices of the input, so the
+// position is recorded as the address of the remaining input at the point of failure and is only
+// turned into a line and column once the error reaches `ParseError::new`, which has the full input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Error {
+    address: usize,
+    pub expected: Expected,
+}
+
+impl Error {
+    // Creates an error at the start of `rest`, which must be a subslice of the input.
+    pub fn new(rest: &[u8], expected: &'static str) -> Error {
+        return Error{address: rest.as_ptr() as usize, expected: Expected::Text(expected)};
+    }
+
+    // Creates an error at the start of `rest` for a missing byte.
+    pub fn byte(rest: &[u8], expected: u8) -> Error {
+        return Error{address: rest.as_ptr() as usize, expected: Expected::Byte(expected)};
+    }
+
+    // Creates an error at the start of `rest` for a missing token.
+    pub fn token(rest: &[u8], expected: &'static str) -> Error {
+        return Error{address: rest.as_ptr() as usize, expected: Expected::Token(expected)};
+    }
+
+    // Returns the offset of the error within `input`. Errors which don't point inside the input
+    // are placed at the end.
+    pub fn offset(&self, input: &[u8]) -> usize {
+        let start = input.as_ptr() as usize;
+        if self.address < start || start + input.len() < self.address { return input.len() }
+        return self.address - start;
+    }
+}
+
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+// Parses a number spanning the whole of `s`.
+pub fn parse_num<T: FromStr>(s: &str) -> Result<T> {
+    return s.parse().map_err(|_| Error::new(s.as_bytes(), "a number"));
+}
+
+// Strips `prefix` from `s`, or fails if it isn't there.
+pub fn eat<'a>(s: &'a [u8], prefix: &'static str) -> Result<&'a [u8]> {
+    return s.strip_prefix(prefix.as_bytes()).ok_or(Error::token(s, prefix));
+}
+
+// Like `eat`, but for string slices.
+pub fn eat_str<'a>(s: &'a str, prefix: &'static str) -> Result<&'a str> {
+    return s.strip_prefix(prefix).ok_or(Error::token(s.as_bytes(), prefix));
+}
+
+// Checks that the input is UTF-8, for the parsers which work on string slices.
+pub fn utf8(input: &[u8]) -> Result<&str> {
+    return core::str::from_utf8(input).map_err(|e| Error::new(&input[e.valid_up_to()..], "UTF-8"));
+}
+
+// Strips the trailing newline from the input, or fails if there isn't one.
+pub fn strip_newline(input: &[u8]) -> Result<&[u8]> {
+    return input.strip_suffix(b"\n").ok_or(Error::byte(&input[input.len()..], b'\n'));
+}
+
+// A parse error which has been located within the input of a specific day.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    // The line and column of the error, both starting at 1.
+    pub line: usize,
+    pub column: usize,
+    // The text of the line containing the error.
+    pub snippet: String,
+    pub expected: String,
+}
+
+#[cfg(feature = "std")]
+impl ParseError {
+    pub fn new(day: u8, input: &str, error: Error) -> ParseError {
+        let input = input.as_bytes();
+        let offset = error.offset(input);
+        let start = input[..offset].iter().rposition(|b| *b == b'\n').map(|i| i + 1).unwrap_or(0);
+        let end = input[offset..].iter().position(|b| *b == b'\n');
+        let end = end.map_or(input.len(), |i| offset + i);
+        return ParseError{
+            day,
+            line: input[..start].iter().filter(|b| **b == b'\n').count() + 1,
+            column: offset - start + 1,
+            snippet: String::from_utf8_lossy(&input[start..end]).into_owned(),
+            expected: error.expected.to_string(),
+        };
+    }
+}
+
+// Formats the error as a diagnostic with a caret pointing at the offending column:
+//
+//   day19: line 3, column 6: expected a number
+//     3 | px{a<x006:qkq}
+//       |      ^
+#[cfg(feature = "std")]
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let number = self.line.to_string();
+        let margin = " ".repeat(number.len());
+        writeln!(f, "day{:02}: line {}, column {}: expected {}",
+                 self.day, self.line, self.column, self.expected)?;
+        writeln!(f, "  {} | {}", number, self.snippet)?;
+        write!(f, "  {} | {}^", margin, " ".repeat(self.column - 1))
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
@@ -0,0 +1,41 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Explain mode, where the solvers describe their intermediate results on stderr. The solvers call
+// `explain!` at a handful of points outside their inner loops, and while explain mode is off each
+// call is a single relaxed load and a branch: the message is never formatted.
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    return ENABLED.load(Ordering::Relaxed);
+}
+
+// Returns the last component of a module path, such as `day05` for `aoc2023rs::day05`.
+pub fn module_name(path: &str) -> &str {
+    return path.rsplit("::").next().unwrap_or(path);
+}
+
+// Prints a line to stderr in explain mode, prefixed with the name of the calling module.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! explain {
+    ($($arg:tt)*) => {
+        if $crate::explain::enabled() {
+            eprintln!("{}: {}",
+                      $crate::explain::module_name(module_path!()), format_args!($($arg)*));
+        }
+    };
+}
+
+// Without `std` there is no stderr, so the message is only type checked.
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! explain {
+    ($($arg:tt)*) => {
+        if false { let _ = format_args!($($arg)*); }
+    };
+}
@@ -0,0 +1,1093 @@
+use crate::day13::{column_mismatches, row_mismatches};
+use crate::grid::Grid;
+use crate::NUM_DAYS;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use std::collections::{HashSet, VecDeque};
+use std::fmt::Write;
+
+// A size parameter for one of the generators. The defaults produce inputs of roughly the same size
+// as the real puzzle inputs, and the bounds keep the input within the limits of the solvers.
+pub struct Param {
+    pub name: &'static str,
+    pub default: usize,
+    pub min: usize,
+    pub max: usize,
+    pub description: &'static str,
+}
+
+const fn param(
+    name: &'static str,
+    default: usize,
+    min: usize,
+    max: usize,
+    description: &'static str,
+) -> Param {
+    return Param{name, default, min, max, description};
+}
+
+// The chosen value of each parameter for a single run of a generator.
+struct Values {
+    params: &'static [Param],
+    values: Vec<usize>,
+}
+
+impl Values {
+    fn get(&self, name: &str) -> usize {
+        let Some(i) = self.params.iter().position(|p| p.name == name) else {
+            panic!("no parameter named {}", name);
+        };
+        return self.values[i];
+    }
+}
+
+struct Generator {
+    params: &'static [Param],
+    generate: fn(&mut ChaCha8Rng, &Values) -> Result<String, String>,
+}
+
+const GENERATORS: [Generator; NUM_DAYS as usize] = [
+    Generator{generate: day01, params: &[
+        param("lines", 1000, 1, 100000, "number of lines"),
+        param("length", 40, 1, 1000, "maximum length of each line"),
+    ]},
+    Generator{generate: day02, params: &[
+        param("games", 100, 1, 100000, "number of games"),
+        param("draws", 6, 1, 100, "maximum number of draws in each game"),
+        param("cubes", 20, 1, 1000, "maximum number of cubes of one colour in a draw"),
+    ]},
+    Generator{generate: day03, params: &[
+        param("size", 140, 4, 2000, "width and height of the schematic"),
+        param("numbers", 1200, 0, 1000000, "number of attempts to place a number"),
+        param("symbols", 750, 0, 1000000, "number of attempts to place a symbol"),
+    ]},
+    Generator{generate: day04, params: &[
+        param("cards", 200, 1, 100000, "number of cards"),
+        param("winning", 10, 1, 98, "number of winning numbers on each card"),
+        param("numbers", 25, 1, 89, "number of numbers you have on each card"),
+    ]},
+    Generator{generate: day05, params: &[
+        param("seeds", 10, 1, 1000, "number of ranges of seeds"),
+        param("stages", 7, 1, 100, "number of maps between seeds and locations"),
+        param("ranges", 30, 1, 1000, "number of ranges in each map"),
+    ]},
+    Generator{generate: day06, params: &[
+        param("races", 4, 1, 4, "number of races"),
+    ]},
+    Generator{generate: day07, params: &[
+        param("hands", 1000, 1, 100000, "number of hands"),
+    ]},
+    Generator{generate: day08, params: &[
+        param("steps", 263, 1, 100000, "number of left/right instructions"),
+        param("ghosts", 6, 1, 6, "number of starting nodes (including AAA)"),
+        param("cycle", 1000, 50, 1300, "maximum number of steps for each ghost"),
+    ]},
+    Generator{generate: day09, params: &[
+        param("lines", 200, 1, 1000, "number of sequences"),
+        param("values", 21, 1, 21, "number of values in each sequence"),
+        param("degree", 20, 0, 20, "maximum degree of the polynomial behind each sequence"),
+    ]},
+    Generator{generate: day10, params: &[
+        param("size", 140, 3, 1000, "width and height of the field"),
+        param("length", 13000, 4, 1000000, "target length of the loop"),
+        param("junk", 50, 0, 100, "percentage of other tiles which are pipes"),
+    ]},
+    Generator{generate: day11, params: &[
+        param("size", 140, 1, 10000, "width and height of the image"),
+        param("galaxies", 440, 1, 1000000, "number of galaxies"),
+        param("empty", 10, 0, 10000, "number of empty rows and of empty columns"),
+    ]},
+    Generator{generate: day12, params: &[
+        param("rows", 1000, 1, 100000, "number of rows"),
+        param("length", 20, 1, 30, "maximum length of each row of springs"),
+        param("groups", 6, 1, 20, "maximum number of groups of damaged springs in each row"),
+    ]},
+    Generator{generate: day13, params: &[
+        param("patterns", 100, 1, 10000, "number of patterns"),
+        param("size", 17, 5, 100, "maximum width and height of each pattern"),
+    ]},
+    Generator{generate: day14, params: &[
+        param("size", 100, 1, 1000, "width and height of the platform"),
+        param("round", 20, 0, 100, "percentage of tiles which are round rocks"),
+        param("cube", 15, 0, 100, "percentage of tiles which are cube-shaped rocks"),
+    ]},
+    Generator{generate: day15, params: &[
+        param("steps", 4000, 1, 1000000, "number of steps"),
+        param("labels", 500, 1, 100000, "number of distinct labels"),
+    ]},
+    Generator{generate: day16, params: &[
+        param("size", 110, 1, 1000, "width and height of the contraption"),
+        param("mirrors", 10, 0, 100, "percentage of tiles which are mirrors or splitters"),
+    ]},
+    Generator{generate: day17, params: &[
+        param("size", 141, 1, 1000, "width and height of the map"),
+    ]},
+    Generator{generate: day18, params: &[
+        param("columns", 175, 1, 10000, "number of columns in each lagoon (4 instructions each)"),
+    ]},
+    Generator{generate: day19, params: &[
+        param("workflows", 400, 1, 10000, "number of workflows"),
+        param("depth", 12, 1, 400, "maximum depth of the tree of workflows below `in`"),
+        param("rules", 4, 1, 4, "maximum number of conditional rules in each workflow"),
+        param("parts", 200, 1, 100000, "number of parts"),
+    ]},
+    Generator{generate: day20, params: &[]},
+    Generator{generate: day21, params: &[
+        param("size", 131, 131, 393, "width and height of the map (131 or 393)"),
+        param("rocks", 12, 0, 30, "percentage of tiles away from the open lanes which are rocks"),
+    ]},
+    Generator{generate: day22, params: &[
+        param("bricks", 1200, 1, 20000, "number of bricks"),
+        param("length", 5, 1, 10, "maximum length of each brick"),
+    ]},
+    Generator{generate: day23, params: &[
+        param("size", 141, 115, 10000, "width and height of the map"),
+    ]},
+    Generator{generate: day24, params: &[
+        param("hailstones", 300, 3, 10000, "number of hailstones"),
+    ]},
+    Generator{generate: day25, params: &[
+        param("components", 1500, 15, 10000, "number of components"),
+        param("degree", 5, 4, 8, "average number of connections for each component"),
+    ]},
+];
+
+// The size parameters accepted by the generator for the given day.
+pub fn params(day: u8) -> &'static [Param] {
+    return GENERATORS[day as usize - 1].params;
+}
+
+// Parses a parameter assignment such as `rows=100`.
+pub fn parse_assignment(s: &str) -> Result<(String, usize), String> {
+    let Some((name, value)) = s.split_once('=') else {
+        return Err(format!("expected a parameter such as `size=100`: {:?}", s));
+    };
+    let Ok(value) = value.parse() else {
+        return Err(format!("not a valid value for {}: {:?}", name, value));
+    };
+    return Ok((name.to_string(), value));
+}
+
+// Generates an input for the given day. Any parameters which are not assigned take their default
+// values. The same seed and parameters always produce the same input.
+pub fn generate(day: u8, seed: u64, assignments: &[(String, usize)]) -> Result<String, String> {
+    let generator = &GENERATORS[day as usize - 1];
+    let params = generator.params;
+    let mut values: Vec<usize> = params.iter().map(|p| p.default).collect();
+    for (name, value) in assignments {
+        let Some(i) = params.iter().position(|p| p.name == name) else {
+            let names: Vec<&str> = params.iter().map(|p| p.name).collect();
+            if names.is_empty() {
+                return Err(format!("unknown parameter: {} (there are none)", name));
+            }
+            return Err(format!("unknown parameter: {} (expected one of: {})",
+                               name, names.join(", ")));
+        };
+        let Param{min, max, ..} = params[i];
+        if !(min..=max).contains(value) {
+            return Err(format!("{} must be between {} and {}", name, min, max));
+        }
+        values[i] = *value;
+    }
+    // Unlike `StdRng`, ChaCha8's output for a seed is fixed across versions and platforms, so a
+    // seed always replays the same input.
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    return (generator.generate)(&mut rng, &Values{params, values});
+}
+
+fn digit(rng: &mut ChaCha8Rng) -> char {
+    return char::from(rng.gen_range(b'1'..=b'9'));
+}
+
+fn letters(rng: &mut ChaCha8Rng, alphabet: &[u8], length: usize) -> String {
+    return (0..length).map(|_| char::from(*alphabet.choose(rng).unwrap())).collect();
+}
+
+const LOWERCASE: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+// Fills a grid with `value` at each position with probability `percent / 100`.
+fn scatter(rng: &mut ChaCha8Rng, grid: &mut Grid<Vec<u8>>, value: u8, percent: usize) {
+    for cell in grid.cells_mut() {
+        if rng.gen_range(0..100) < percent { *cell = value }
+    }
+}
+
+fn day01(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    const WORDS: [&str; 9] = [
+        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    ];
+    let mut out = String::new();
+    for _ in 0..values.get("lines") {
+        let length = rng.gen_range(1..=values.get("length"));
+        let mut line = String::new();
+        while line.len() < length {
+            match rng.gen_range(0..8) {
+                0 => line.push(digit(rng)),
+                1 => line.push_str(WORDS.choose(rng).unwrap()),
+                _ => line.push(char::from(*LOWERCASE.choose(rng).unwrap())),
+            }
+        }
+        // Part 1 needs at least one numeric digit on every line.
+        if !line.bytes().any(|b| b.is_ascii_digit()) {
+            let i = rng.gen_range(0..=line.len());
+            line.insert(i, digit(rng));
+        }
+        out.push_str(&line);
+        out.push('\n');
+    }
+    return Ok(out);
+}
+
+fn day02(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let mut out = String::new();
+    for id in 1..=values.get("games") {
+        write!(out, "Game {}:", id).unwrap();
+        for i in 0..rng.gen_range(1..=values.get("draws")) {
+            let mut colours = ["red", "green", "blue"];
+            colours.shuffle(rng);
+            let n = rng.gen_range(1..=colours.len());
+            let cubes: Vec<String> = colours[..n]
+                .iter()
+                .map(|colour| format!("{} {}", rng.gen_range(1..=values.get("cubes")), colour))
+                .collect();
+            write!(out, "{}{}", if i == 0 { " " } else { "; " }, cubes.join(", ")).unwrap();
+        }
+        out.push('\n');
+    }
+    return Ok(out);
+}
+
+fn day03(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let size = values.get("size");
+    let mut grid = Grid::new(size, size, b'.');
+    // `owner[i]` is the index of the number which covers cell `i`, if any.
+    let mut owner = vec![None; size * size];
+    let mut num_numbers = 0;
+    for _ in 0..values.get("numbers") {
+        let length = rng.gen_range(1..=3);
+        let (x, y) = (rng.gen_range(0..=size - length), rng.gen_range(0..size));
+        // Leave a gap between numbers on the same line.
+        let gap = &grid.row(y)[x.saturating_sub(1)..(x + length + 1).min(size)];
+        if gap.iter().any(|c| *c != b'.') { continue }
+        let number = rng.gen_range(10usize.pow(length as u32 - 1)..10usize.pow(length as u32));
+        for (i, c) in number.to_string().bytes().enumerate() {
+            grid[(x + i, y)] = c;
+            owner[grid.index((x + i, y))] = Some(num_numbers);
+        }
+        num_numbers += 1;
+    }
+    // The solver assumes that no number is adjacent to two symbols, and that only `*` symbols are
+    // adjacent to exactly two numbers.
+    let mut has_symbol = vec![false; num_numbers];
+    for _ in 0..values.get("symbols") {
+        let (x, y) = (rng.gen_range(0..size), rng.gen_range(0..size));
+        if grid[(x, y)] != b'.' { continue }
+        let mut adjacent = Vec::new();
+        for ny in y.saturating_sub(1)..(y + 2).min(size) {
+            for nx in x.saturating_sub(1)..(x + 2).min(size) {
+                if let Some(n) = owner[grid.index((nx, ny))] { adjacent.push(n) }
+            }
+        }
+        adjacent.sort();
+        adjacent.dedup();
+        if adjacent.iter().any(|n| has_symbol[*n]) { continue }
+        for n in &adjacent { has_symbol[*n] = true }
+        grid[(x, y)] = if adjacent.len() == 2 { b'*' } else { *b"*#+$/=%@&-".choose(rng).unwrap() };
+    }
+    return Ok(grid.to_string());
+}
+
+fn day04(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let cards = values.get("cards");
+    let (winning, numbers) = (values.get("winning"), values.get("numbers"));
+    if winning + numbers > 99 {
+        return Err("there are only 99 different numbers".to_string());
+    }
+    // `copies[i]` is the number of copies of card `i + 1` won by the cards so far, and `total` is
+    // their sum, which is the answer to part 2. It has to fit in 32 bits.
+    let mut copies = vec![1; cards];
+    let mut total = cards;
+    let mut out = String::new();
+    for card in 1..=cards {
+        // Cards never win copies of cards past the end of the table.
+        let most = winning.min(numbers).min(cards - card);
+        let mut matches = if rng.gen_bool(0.5) { 0 } else { rng.gen_range(0..=most) };
+        if total + matches * copies[card - 1] > 1_000_000_000 { matches = 0 }
+        total += matches * copies[card - 1];
+        for i in card..card + matches { copies[i] += copies[card - 1] }
+        let mut pool: Vec<usize> = (1..=99).collect();
+        pool.shuffle(rng);
+        let (win, rest) = pool.split_at(winning);
+        let mut have: Vec<usize> = win[..matches].to_vec();
+        have.extend(&rest[..numbers - matches]);
+        have.shuffle(rng);
+        let format = |list: &[usize]| -> String {
+            return list.iter().map(|n| format!("{:2}", n)).collect::<Vec<_>>().join(" ");
+        };
+        writeln!(out, "Card {:3}: {} | {}", card, format(win), format(&have)).unwrap();
+    }
+    return Ok(out);
+}
+
+fn day05(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    const CATEGORIES: [&str; 8] = [
+        "seed", "soil", "fertilizer", "water", "light", "temperature", "humidity", "location",
+    ];
+    const LIMIT: u64 = 1 << 32;
+    let mut out = String::from("seeds:");
+    for _ in 0..values.get("seeds") {
+        let length = rng.gen_range(1..=LIMIT / 8);
+        write!(out, " {} {}", rng.gen_range(0..LIMIT - length), length).unwrap();
+    }
+    out.push('\n');
+    let stages = values.get("stages");
+    for stage in 0..stages {
+        if stages < CATEGORIES.len() {
+            write!(out, "\n{}-to-{} map:\n", CATEGORIES[stage], CATEGORIES[stage + 1]).unwrap();
+        } else {
+            write!(out, "\nstage{}-to-stage{} map:\n", stage, stage + 1).unwrap();
+        }
+        // Split the numbers into consecutive ranges and shuffle them around.
+        let mut cuts: Vec<u64> =
+            (0..values.get("ranges")).map(|_| rng.gen_range(1..LIMIT)).collect();
+        cuts.extend([0, LIMIT]);
+        cuts.sort();
+        cuts.dedup();
+        let sources: Vec<(u64, u64)> = cuts.windows(2).map(|w| (w[0], w[1] - w[0])).collect();
+        let mut order: Vec<usize> = (0..sources.len()).collect();
+        order.shuffle(rng);
+        let mut destination = 0;
+        for i in order {
+            let (source, length) = sources[i];
+            // Leave out some of the ranges, which then map to themselves.
+            if rng.gen_range(0..8) != 0 {
+                writeln!(out, "{} {} {}", destination, source, length).unwrap();
+            }
+            destination += length;
+        }
+    }
+    return Ok(out);
+}
+
+fn day06(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    loop {
+        let mut times = Vec::new();
+        let mut distances = Vec::new();
+        for _ in 0..values.get("races") {
+            let time: u64 = rng.gen_range(7..100);
+            // The record must be beatable, so it is less than `(time / 2) * (time - time / 2)`.
+            let best = (time / 2) * (time - time / 2);
+            times.push(time.to_string());
+            distances.push(rng.gen_range(best / 2..best).to_string());
+        }
+        // The same must be true when the numbers are joined together for part 2.
+        let time: u64 = times.concat().parse().unwrap();
+        let distance: u64 = distances.concat().parse().unwrap();
+        if distance >= (time / 2) * (time - time / 2) { continue }
+        // Line the numbers up in columns.
+        let (mut time_line, mut distance_line) = ("Time:    ".to_string(), "Distance:".to_string());
+        for (t, d) in times.iter().zip(&distances) {
+            let width = t.len().max(d.len());
+            write!(time_line, " {:>1$}", t, width).unwrap();
+            write!(distance_line, " {:>1$}", d, width).unwrap();
+        }
+        return Ok(format!("{}\n{}\n", time_line, distance_line));
+    }
+}
+
+fn day07(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let mut seen = HashSet::new();
+    let mut out = String::new();
+    while seen.len() < values.get("hands") {
+        let hand = letters(rng, b"23456789TJQKA", 5);
+        if !seen.insert(hand.clone()) { continue }
+        writeln!(out, "{} {}", hand, rng.gen_range(1..=1000)).unwrap();
+    }
+    return Ok(out);
+}
+
+fn day08(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let steps = letters(rng, b"LR", values.get("steps"));
+    // Each ghost follows a path of two parallel chains of nodes, so the choice of left or right
+    // doesn't matter. The path from the start node leads to an end node, and from there back to
+    // the start of the path. The path lengths are distinct primes, so that part 2 is their
+    // product.
+    let cycle = values.get("cycle");
+    let primes: Vec<usize> = (cycle / 2..=cycle)
+        .filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0))
+        .collect();
+    let lengths: Vec<usize> = primes.choose_multiple(rng, values.get("ghosts")).copied().collect();
+    // Names of chain nodes end in neither `A` nor `Z`.
+    let mut names: Vec<String> = Vec::new();
+    for a in b'A'..=b'Z' {
+        for b in b'A'..=b'Z' {
+            for c in b'B'..=b'Y' { names.push(String::from_utf8(vec![a, b, c]).unwrap()) }
+        }
+    }
+    names.shuffle(rng);
+    let mut chain_names = names.into_iter();
+    let mut prefixes: Vec<String> = Vec::new();
+    for a in b'A'..=b'Z' {
+        for b in b'A'..=b'Z' {
+            if a == b && (a == b'A' || a == b'Z') { continue }
+            prefixes.push(String::from_utf8(vec![a, b]).unwrap());
+        }
+    }
+    prefixes.shuffle(rng);
+    let mut lines = Vec::new();
+    let mut node = |name: &str, (left, right): (&str, &str), rng: &mut ChaCha8Rng| {
+        let (left, right) = if rng.gen() { (left, right) } else { (right, left) };
+        lines.push(format!("{} = ({}, {})", name, left, right));
+    };
+    for (i, length) in lengths.iter().enumerate() {
+        let (start, end) = if i == 0 {
+            ("AAA".to_string(), "ZZZ".to_string())
+        } else {
+            (format!("{}A", prefixes[2 * i]), format!("{}Z", prefixes[2 * i + 1]))
+        };
+        let chain: Vec<(String, String)> = (1..*length)
+            .map(|_| (chain_names.next().unwrap(), chain_names.next().unwrap()))
+            .collect();
+        node(&start, (&chain[0].0, &chain[0].1), rng);
+        node(&end, (&chain[0].0, &chain[0].1), rng);
+        for k in 0..chain.len() {
+            let next = match chain.get(k + 1) {
+                Some((x, y)) => (x.as_str(), y.as_str()),
+                None => (end.as_str(), end.as_str()),
+            };
+            node(&chain[k].0, next, rng);
+            node(&chain[k].1, next, rng);
+        }
+    }
+    lines.shuffle(rng);
+    return Ok(format!("{}\n\n{}\n", steps, lines.join("\n")));
+}
+
+fn day09(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    // Values are limited so that the sum of the extrapolated values fits in 32 bits.
+    const LIMIT: i64 = 1_000_000;
+    let n = values.get("values");
+    let mut out = String::new();
+    for _ in 0..values.get("lines") {
+        let mut degree = rng.gen_range(0..=values.get("degree").min(n - 1));
+        // Build the table of differences from the bottom up, including one extra value at either
+        // end for the extrapolated values.
+        let sequence = loop {
+            let mut row: Vec<i64> = vec![rng.gen_range(-5..=5); n + 2];
+            for _ in 0..degree {
+                let mut next = vec![rng.gen_range(-20..=20)];
+                for delta in &row[..n + 1] { next.push(next.last().unwrap() + delta) }
+                row = next;
+                if row.iter().any(|x| x.abs() > LIMIT) { break }
+            }
+            if row.iter().all(|x| x.abs() <= LIMIT) { break row }
+            degree -= 1;
+        };
+        let sequence: Vec<String> = sequence[1..=n].iter().map(|x| x.to_string()).collect();
+        writeln!(out, "{}", sequence.join(" ")).unwrap();
+    }
+    return Ok(out);
+}
+
+// Grows a loop of cells in a `size` x `size` grid by pushing its edges outwards by one cell at a
+// time, until it has at least `length` cells or there's no room left. `next[i]` is the next cell
+// around the loop from cell `i`, or `usize::MAX` if cell `i` is not on the loop, and `cells` lists
+// the cells on the loop.
+fn grow_loop(
+    rng: &mut ChaCha8Rng,
+    size: usize,
+    next: &mut [usize],
+    cells: &mut Vec<usize>,
+    length: usize,
+) {
+    for _ in 0..100 * length {
+        if cells.len() >= length { break }
+        let a = *cells.choose(rng).unwrap();
+        let b = next[a];
+        let (ax, ay, bx, by) = (a % size, a / size, b % size, b / size);
+        let (dx, dy) = (bx as isize - ax as isize, by as isize - ay as isize);
+        let (px, py) = if rng.gen() { (dy, -dx) } else { (-dy, dx) };
+        let (cx, cy) = (ax as isize + px, ay as isize + py);
+        let (ex, ey) = (bx as isize + px, by as isize + py);
+        let limit = 0..size as isize;
+        if ![cx, cy, ex, ey].iter().all(|v| limit.contains(v)) { continue }
+        let (c, e) = (cy as usize * size + cx as usize, ey as usize * size + ex as usize);
+        if next[c] != usize::MAX || next[e] != usize::MAX { continue }
+        next[a] = c;
+        next[c] = e;
+        next[e] = b;
+        cells.extend([c, e]);
+    }
+}
+
+fn day10(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let size = values.get("size");
+    let length = values.get("length").min(size * size);
+    // Growing the loop directly would never enclose any tiles, so start with a loop grown at half
+    // the resolution and scale it up.
+    let half = size.div_ceil(2);
+    let mut next = vec![usize::MAX; half * half];
+    let (x, y) = (rng.gen_range(0..half - 1), rng.gen_range(0..half - 1));
+    let corner = y * half + x;
+    let mut cells = vec![corner, corner + 1, corner + half + 1, corner + half];
+    for i in 0..4 { next[cells[i]] = cells[(i + 1) % 4] }
+    grow_loop(rng, half, &mut next, &mut cells, length * 2 / 5);
+    let mut scaled_next = vec![usize::MAX; size * size];
+    let mut scaled_cells = Vec::new();
+    for &a in &cells {
+        let b = next[a];
+        let (ax, ay, bx, by) = (a % half, a / half, b % half, b / half);
+        // Each cell moves to twice its position, with an extra cell halfway to the next one.
+        let middle = (ay + by) * size + ax + bx;
+        scaled_next[2 * (ay * size + ax)] = middle;
+        scaled_next[middle] = 2 * (by * size + bx);
+        scaled_cells.extend([2 * (ay * size + ax), middle]);
+    }
+    let (mut next, mut cells) = (scaled_next, scaled_cells);
+    grow_loop(rng, size, &mut next, &mut cells, length);
+    // Draw the loop, surrounded by junk.
+    let mut grid = Grid::new(size, size, b'.');
+    for cell in grid.cells_mut() {
+        if rng.gen_range(0..100) < values.get("junk") { *cell = *b"|-LJ7F".choose(rng).unwrap() }
+    }
+    let mut previous = vec![0; size * size];
+    for &i in &cells { previous[next[i]] = i }
+    for &i in &cells {
+        // The directions from cell `i` to its neighbours on the loop.
+        let direction = |j: usize| -> u8 {
+            match j as isize - i as isize {
+                -1 => b'W',
+                1 => b'E',
+                d if d < 0 => b'N',
+                _ => b'S',
+            }
+        };
+        let mut ends = [direction(previous[i]), direction(next[i])];
+        ends.sort();
+        grid.cells_mut()[i] = match &ends {
+            b"NS" => b'|',
+            b"EW" => b'-',
+            b"EN" => b'L',
+            b"NW" => b'J',
+            b"SW" => b'7',
+            _ => b'F',
+        };
+    }
+    // Junk next to the start could be mistaken for part of the loop.
+    let start = *cells.choose(rng).unwrap();
+    let start = (start % size, start / size);
+    grid[start] = b'S';
+    let neighbours: Vec<(usize, usize)> = grid.neighbours(start).collect();
+    for n in neighbours {
+        if next[grid.index(n)] == usize::MAX { grid[n] = b'.' }
+    }
+    return Ok(grid.to_string());
+}
+
+fn day11(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let size = values.get("size");
+    let empty = values.get("empty").min(size - 1);
+    let rows = rand::seq::index::sample(rng, size, size - empty).into_vec();
+    let columns = rand::seq::index::sample(rng, size, size - empty).into_vec();
+    let galaxies = values.get("galaxies").min(rows.len() * columns.len());
+    let mut grid = Grid::new(size, size, b'.');
+    let mut placed = 0;
+    while placed < galaxies {
+        let position = (*columns.choose(rng).unwrap(), *rows.choose(rng).unwrap());
+        if grid[position] == b'#' { continue }
+        grid[position] = b'#';
+        placed += 1;
+    }
+    return Ok(grid.to_string());
+}
+
+fn day12(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let length = values.get("length");
+    let mut out = String::new();
+    for _ in 0..values.get("rows") {
+        // Pick some groups which fit within the row, then spread them out.
+        let groups: Vec<usize> = loop {
+            let count = rng.gen_range(1..=values.get("groups"));
+            let groups: Vec<usize> = (0..count).map(|_| rng.gen_range(1..=length.min(6))).collect();
+            if groups.iter().sum::<usize>() + count - 1 <= length { break groups }
+        };
+        let used = groups.iter().sum::<usize>() + groups.len() - 1;
+        let mut gaps = vec![0; groups.len() + 1];
+        for _ in 0..rng.gen_range(0..=length - used) {
+            let i = rng.gen_range(0..gaps.len());
+            gaps[i] += 1;
+        }
+        let mut springs = ".".repeat(gaps[0]);
+        for (i, group) in groups.iter().enumerate() {
+            if i > 0 { springs.push('.') }
+            springs.push_str(&"#".repeat(*group));
+            springs.push_str(&".".repeat(gaps[i + 1]));
+        }
+        // Forget the condition of about half of the springs.
+        let springs: String = springs
+            .chars()
+            .map(|c| if rng.gen() { '?' } else { c })
+            .collect();
+        let groups: Vec<String> = groups.iter().map(|g| g.to_string()).collect();
+        writeln!(out, "{} {}", springs, groups.join(",")).unwrap();
+    }
+    return Ok(out);
+}
+
+fn day13(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let size = values.get("size");
+    let mut patterns = Vec::new();
+    while patterns.len() < values.get("patterns") {
+        let (w, h) = (rng.gen_range(5..=size), rng.gen_range(5..=size));
+        let mut grid = Grid::new(w, h, b'.');
+        scatter(rng, &mut grid, b'#', 50);
+        // Make the pattern symmetric about a vertical mirror and a horizontal one.
+        let (a, b) = (rng.gen_range(1..w), rng.gen_range(1..h));
+        for y in b..h.min(2 * b) {
+            let row = grid.row(2 * b - 1 - y).to_vec();
+            grid.row_mut(y).copy_from_slice(&row);
+        }
+        for x in a..w.min(2 * a) {
+            for y in 0..h { grid[(x, y)] = grid[(2 * a - 1 - x, y)] }
+        }
+        // Smudge a cell which is beyond the reach of the vertical mirror, so that only the
+        // horizontal one is broken.
+        let reach = a.min(w - a);
+        let outside: Vec<usize> = (0..w).filter(|x| x + reach < a || *x >= a + reach).collect();
+        let Some(&x) = outside.choose(rng) else { continue };
+        let reach = b.min(h - b);
+        let y = rng.gen_range(b - reach..b + reach);
+        grid[(x, y)] = if grid[(x, y)] == b'#' { b'.' } else { b'#' };
+        let grid = if rng.gen() { grid.transpose() } else { grid };
+        // Each part needs exactly one mirror with the right number of smudges.
+        let counts: Vec<u32> = (1..grid.height()).map(|y| row_mismatches(&grid, y))
+            .chain((1..grid.width()).map(|x| column_mismatches(&grid, x)))
+            .collect();
+        if counts.iter().filter(|c| **c == 0).count() != 1 { continue }
+        if counts.iter().filter(|c| **c == 1).count() != 1 { continue }
+        patterns.push(grid.to_string());
+    }
+    return Ok(patterns.join("\n"));
+}
+
+fn day14(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let size = values.get("size");
+    let mut grid = Grid::new(size, size, b'.');
+    for cell in grid.cells_mut() {
+        let roll = rng.gen_range(0..100);
+        if roll < values.get("round") {
+            *cell = b'O';
+        } else if roll < values.get("round") + values.get("cube") {
+            *cell = b'#';
+        }
+    }
+    return Ok(grid.to_string());
+}
+
+fn day15(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let mut labels = Vec::new();
+    let mut seen = HashSet::new();
+    while labels.len() < values.get("labels") {
+        let length = rng.gen_range(2..=6);
+        let label = letters(rng, LOWERCASE, length);
+        if !seen.insert(label.clone()) { continue }
+        labels.push(label);
+    }
+    let steps: Vec<String> = (0..values.get("steps"))
+        .map(|_| {
+            let label = labels.choose(rng).unwrap();
+            if rng.gen_range(0..5) < 3 {
+                format!("{}={}", label, digit(rng))
+            } else {
+                format!("{}-", label)
+            }
+        })
+        .collect();
+    return Ok(steps.join(",") + "\n");
+}
+
+fn day16(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let size = values.get("size");
+    let mut grid = Grid::new(size, size, b'.');
+    for cell in grid.cells_mut() {
+        if rng.gen_range(0..100) < values.get("mirrors") { *cell = *b"/\\|-".choose(rng).unwrap() }
+    }
+    return Ok(grid.to_string());
+}
+
+fn day17(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let size = values.get("size");
+    // Like the real inputs, the heat loss is higher towards the middle of the map. A uniformly
+    // random map leaves the search with too many equally good paths to explore.
+    let mut grid = Grid::new(size, size, b'1');
+    for y in 0..size {
+        for x in 0..size {
+            let distance = x.abs_diff(size / 2) + y.abs_diff(size / 2);
+            let base = 1 + 5 * size.saturating_sub(distance) / size.max(1);
+            grid[(x, y)] = b'0' + (base + rng.gen_range(0..=3)).min(9) as u8;
+        }
+    }
+    return Ok(grid.to_string());
+}
+
+// Returns the edges of a random lagoon which is made of `columns` columns of varying height. Each
+// edge is a direction (0 = right, 1 = down, 2 = left, 3 = up) and a length of at most `max`.
+fn lagoon(rng: &mut ChaCha8Rng, columns: usize, max: i64) -> Vec<(usize, i64)> {
+    // `spans[i]` is the range `(bottom, top)` covered by column `i`. Neighbouring columns overlap,
+    // so the edges never touch each other.
+    let mut spans = vec![(0, rng.gen_range(1..=max))];
+    while spans.len() < columns {
+        let (bottom, top) = *spans.last().unwrap();
+        let mut shift = || if rng.gen() { rng.gen_range(1..=max) } else { -rng.gen_range(1..=max) };
+        let (b, t) = (bottom + shift(), top + shift());
+        if b < top && bottom < t && b < t && t - b <= max { spans.push((b, t)) }
+    }
+    let vertical = |from: i64, to: i64| if to > from { (3, to - from) } else { (1, from - to) };
+    let mut edges = vec![(3, spans[0].1 - spans[0].0)];
+    for i in 0..columns {
+        edges.push((0, rng.gen_range(1..=max)));
+        if i + 1 < columns { edges.push(vertical(spans[i].1, spans[i + 1].1)) }
+    }
+    edges.push((1, spans[columns - 1].1 - spans[columns - 1].0));
+    for i in (0..columns).rev() {
+        // The bottom edge retraces the widths of the columns.
+        edges.push((2, edges[2 * i + 1].1));
+        if i > 0 { edges.push(vertical(spans[i].0, spans[i - 1].0)) }
+    }
+    // Rotate the lagoon, and start from a random corner.
+    let turn = rng.gen_range(0..4);
+    for edge in &mut edges { edge.0 = (edge.0 + turn) % 4 }
+    let start = rng.gen_range(0..edges.len());
+    edges.rotate_left(start);
+    return edges;
+}
+
+fn day18(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    // The instructions for the two parts describe different lagoons with the same number of edges.
+    let columns = values.get("columns");
+    let small = lagoon(rng, columns, 10);
+    let large = lagoon(rng, columns, 0xfffff);
+    let mut out = String::new();
+    for ((d1, n1), (d2, n2)) in small.iter().zip(&large) {
+        writeln!(out, "{} {} (#{:05x}{})", char::from(b"RDLU"[*d1]), n1, n2, d2).unwrap();
+    }
+    return Ok(out);
+}
+
+fn day19(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let mut names = HashSet::from(["in".to_string()]);
+    // The workflows form a tree rooted at `in`, so every workflow is reachable. As in the real
+    // inputs, each rule splits the range of ratings which can reach it in two.
+    let mut queue = VecDeque::from([("in".to_string(), 0, [(1, 4000); 4])]);
+    let mut lines = Vec::new();
+    while let Some((name, depth, mut ranges)) = queue.pop_front() {
+        let num_rules = rng.gen_range(1..=values.get("rules"));
+        let mut rules = Vec::new();
+        loop {
+            let categories: Vec<usize> = (0..4).filter(|c| ranges[*c].0 < ranges[*c].1).collect();
+            let category =
+                if rules.len() < num_rules { categories.choose(rng).copied() } else { None };
+            let mut matched = ranges;
+            let condition = match category {
+                Some(c) => {
+                    let ((low, high), name) = (ranges[c], char::from(b"xmas"[c]));
+                    if rng.gen() {
+                        let x = rng.gen_range(low + 1..=high);
+                        (matched[c].1, ranges[c].0) = (x - 1, x);
+                        format!("{}<{}:", name, x)
+                    } else {
+                        let x = rng.gen_range(low..high);
+                        (matched[c].0, ranges[c].1) = (x + 1, x);
+                        format!("{}>{}:", name, x)
+                    }
+                }
+                None => String::new(),
+            };
+            let target = if names.len() < values.get("workflows")
+                && depth < values.get("depth")
+                && rng.gen_range(0..5) != 0
+            {
+                let child = loop {
+                    let length = rng.gen_range(2..=3);
+                    let child = letters(rng, LOWERCASE, length);
+                    if names.insert(child.clone()) { break child }
+                };
+                queue.push_back((child.clone(), depth + 1, matched));
+                child
+            } else if rng.gen() {
+                "A".to_string()
+            } else {
+                "R".to_string()
+            };
+            rules.push(condition + &target);
+            if category.is_none() { break }
+        }
+        lines.push(format!("{}{{{}}}", name, rules.join(",")));
+    }
+    lines.shuffle(rng);
+    let mut out = lines.join("\n") + "\n\n";
+    for _ in 0..values.get("parts") {
+        let [x, m, a, s] = [(); 4].map(|_| rng.gen_range(1..=4000));
+        writeln!(out, "{{x={},m={},a={},s={}}}", x, m, a, s).unwrap();
+    }
+    return Ok(out);
+}
+
+fn day20(rng: &mut ChaCha8Rng, _: &Values) -> Result<String, String> {
+    // The broadcaster feeds four 12-bit counters made of flip-flops. Each counter has a conjunction
+    // which fires when the counter reaches its target, resets the counter, and sends a pulse via
+    // an inverter to the conjunction in front of `rx`.
+    let mut names: Vec<String> = Vec::new();
+    for a in LOWERCASE {
+        for b in LOWERCASE {
+            if [*a, *b] != *b"rx" { names.push(String::from_utf8(vec![*a, *b]).unwrap()) }
+        }
+    }
+    names.shuffle(rng);
+    let mut names = names.into_iter();
+    let mut lines = Vec::new();
+    let last = names.next().unwrap();
+    let mut roots = Vec::new();
+    for _ in 0..4 {
+        let target = rng.gen_range(1 << 11..1 << 12) | 1;
+        let flip_flops: Vec<String> = (0..12).map(|_| names.next().unwrap()).collect();
+        let (conjunction, inverter) = (names.next().unwrap(), names.next().unwrap());
+        roots.push(flip_flops[0].clone());
+        let mut outs = vec![inverter.clone(), flip_flops[0].clone()];
+        for i in 1..12 {
+            if target >> i & 1 == 0 { outs.push(flip_flops[i].clone()) }
+        }
+        outs.shuffle(rng);
+        lines.push(format!("&{} -> {}", conjunction, outs.join(", ")));
+        lines.push(format!("&{} -> {}", inverter, last));
+        for i in 0..12 {
+            let mut outs = Vec::new();
+            if i < 11 { outs.push(flip_flops[i + 1].clone()) }
+            if target >> i & 1 == 1 { outs.push(conjunction.clone()) }
+            outs.shuffle(rng);
+            lines.push(format!("%{} -> {}", flip_flops[i], outs.join(", ")));
+        }
+    }
+    lines.push(format!("&{} -> rx", last));
+    lines.push(format!("broadcaster -> {}", roots.join(", ")));
+    lines.shuffle(rng);
+    return Ok(lines.join("\n") + "\n");
+}
+
+fn day21(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    // The solver relies on the number of steps being a whole number of tiles plus half a tile.
+    let size = values.get("size");
+    if (2 * 26501365 + 1) % size != 0 {
+        return Err(format!("size must divide {} evenly, such as 131 or 393", 2 * 26501365 + 1));
+    }
+    // Leave the middle row and column, the border, and a diamond connecting the middle of each
+    // edge free of rocks. No two rocks touch, even diagonally, so that every plot can be reached by
+    // a shortest route from the center, from each corner, and from the middle of each edge. The
+    // solver relies on the explored area growing in straight lines.
+    let c = size / 2;
+    let mut grid = Grid::new(size, size, b'.');
+    for y in 1..size - 1 {
+        for x in 1..size - 1 {
+            let d = x.abs_diff(c) + y.abs_diff(c);
+            if x == c || y == c || (c - 3..=c + 3).contains(&d) { continue }
+            if rng.gen_range(0..100) >= values.get("rocks") { continue }
+            let near = (y - 1..=y + 1).any(|ny| (x - 1..=x + 1).any(|nx| grid[(nx, ny)] == b'#'));
+            if !near { grid[(x, y)] = b'#' }
+        }
+    }
+    grid[(c, c)] = b'S';
+    return Ok(grid.to_string());
+}
+
+fn day22(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    let bricks = values.get("bricks");
+    let height = bricks / 4 + 10;
+    let mut filled = HashSet::new();
+    let mut lines = Vec::new();
+    while lines.len() < bricks {
+        let axis = rng.gen_range(0..3);
+        let length = rng.gen_range(1..=values.get("length"));
+        let mut start = [rng.gen_range(0..10), rng.gen_range(0..10), rng.gen_range(1..=height)];
+        let limit = if axis == 2 { height + 1 } else { 10 };
+        start[axis] = start[axis].min(limit - length);
+        let mut end = start;
+        end[axis] += length - 1;
+        let cubes: Vec<[usize; 3]> = (0..length)
+            .map(|i| {
+                let mut cube = start;
+                cube[axis] += i;
+                cube
+            })
+            .collect();
+        if cubes.iter().any(|cube| filled.contains(cube)) { continue }
+        filled.extend(cubes);
+        let [x1, y1, z1] = start;
+        let [x2, y2, z2] = end;
+        lines.push(format!("{},{},{}~{},{},{}", x1, y1, z1, x2, y2, z2));
+    }
+    return Ok(lines.join("\n") + "\n");
+}
+
+fn day23(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    // The paths form a 6x6 grid of junctions, with the corners at the start and end cut off. Each
+    // path between junctions may take a detour, and all the slopes lead away from the start.
+    let size = values.get("size");
+    let spacing = (size - 15) / 5;
+    let mut grid = Grid::new(size, size, b'#');
+    let place = |rng: &mut ChaCha8Rng| -> [usize; 6] {
+        let mut place = [0; 6].map(|_| rng.gen_range(0..=4));
+        for i in 0..6 { place[i] += 5 + i * spacing }
+        place[0] = 7;
+        place[5] = size - 8;
+        return place;
+    };
+    let (xs, ys) = (place(rng), place(rng));
+    // Draws a path from `(x, y)` to `(x + length, y)`, or the same with `x` and `y` swapped.
+    let path = |grid: &mut Grid<Vec<u8>>,
+                rng: &mut ChaCha8Rng,
+                (x, y): (usize, usize),
+                length: usize,
+                swap| {
+        let set = |grid: &mut Grid<Vec<u8>>, (i, j): (usize, usize), c| {
+            grid[if swap { (j, i) } else { (i, j) }] = c;
+        };
+        for i in x..=x + length { set(grid, (i, y), b'.') }
+        let offset: isize = *[0, 2, 3, -2, -3].choose(rng).unwrap();
+        if offset == 0 { return }
+        // Keep the two corners of the detour apart on short paths.
+        let reach = ((length - 3) / 2).min(8);
+        let (a, b) = (x + rng.gen_range(5..=reach), x + length - rng.gen_range(5..=reach));
+        let side = (y as isize + offset) as usize;
+        for i in a + 1..b { set(grid, (i, y), b'#') }
+        for j in y.min(side)..=y.max(side) {
+            set(grid, (a, j), b'.');
+            set(grid, (b, j), b'.');
+        }
+        for i in a..=b { set(grid, (i, side), b'.') }
+    };
+    for j in 0..6 {
+        for i in 0..6 {
+            if i < 5 { path(&mut grid, rng, (xs[i], ys[j]), xs[i + 1] - xs[i], false) }
+            if j < 5 { path(&mut grid, rng, (ys[j], xs[i]), ys[j + 1] - ys[j], true) }
+        }
+    }
+    for j in 0..6 {
+        for i in 0..6 {
+            if (i, j) == (5, 0) || (i, j) == (0, 5) { continue }
+            let (x, y) = (xs[i], ys[j]);
+            for (position, slope) in [((x + 1, y), b'>'), ((x - 1, y), b'>'),
+                                      ((x, y + 1), b'v'), ((x, y - 1), b'v')] {
+                if grid[position] == b'.' { grid[position] = slope }
+            }
+        }
+    }
+    for y in 0..8 { grid[(1, y)] = b'.' }
+    for x in 1..7 { grid[(x, 7)] = b'.' }
+    for x in size - 7..size - 1 { grid[(x, size - 8)] = b'.' }
+    for y in size - 8..size { grid[(size - 2, y)] = b'.' }
+    return Ok(grid.to_string());
+}
+
+fn day24(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    // Every hailstone is on a collision course with the same rock, at a different time.
+    let rock = [(); 3].map(|_| rng.gen_range(100_000_000_000_000i64..400_000_000_000_000));
+    let velocity = [(); 3].map(|_| rng.gen_range(-300i64..=300));
+    let mut times = HashSet::new();
+    let mut out = String::new();
+    while times.len() < values.get("hailstones") {
+        let time = rng.gen_range(10_000_000_000i64..1_000_000_000_000);
+        let v = [(); 3].map(|_| rng.gen_range(-300i64..=300));
+        if (0..3).any(|i| v[i] == velocity[i]) || !times.insert(time) { continue }
+        let p: Vec<i64> = (0..3).map(|i| rock[i] + (velocity[i] - v[i]) * time).collect();
+        writeln!(out, "{}, {}, {} @ {}, {}, {}", p[0], p[1], p[2], v[0], v[1], v[2]).unwrap();
+    }
+    return Ok(out);
+}
+
+fn day25(rng: &mut ChaCha8Rng, values: &Values) -> Result<String, String> {
+    // Two well-connected groups of components, joined by exactly three wires.
+    let n = values.get("components");
+    let split = rng.gen_range(n / 3..=n - n / 3);
+    let mut wires = HashSet::new();
+    for group in [0..split, split..n] {
+        let members: Vec<usize> = group.collect();
+        // Every component gets at least 4 wires, so that no other 3 wires disconnect the graph.
+        let mut degree = vec![0; n];
+        let most = members.len() * (members.len() - 1) / 2;
+        let target = (values.get("degree") * members.len() / 2).min(most);
+        let (mut count, mut i) = (0, 0);
+        while i < members.len() || count < target {
+            let a = if i < members.len() { members[i] } else { *members.choose(rng).unwrap() };
+            if i < members.len() && degree[a] >= 4 {
+                i += 1;
+                continue;
+            }
+            let b = *members.choose(rng).unwrap();
+            if a == b || wires.contains(&(b, a)) || !wires.insert((a, b)) { continue }
+            degree[a] += 1;
+            degree[b] += 1;
+            count += 1;
+        }
+    }
+    let mut bridge = (0..3)
+        .map(|_| (rng.gen_range(0..split), rng.gen_range(split..n)))
+        .collect::<HashSet<_>>();
+    while bridge.len() < 3 { bridge.insert((rng.gen_range(0..split), rng.gen_range(split..n))); }
+    wires.extend(bridge);
+    // Each wire is listed once, under either of its components.
+    let mut names = HashSet::new();
+    let names: Vec<String> = std::iter::from_fn(|| Some(letters(rng, LOWERCASE, 3)))
+        .filter(|name| names.insert(name.clone()))
+        .take(n)
+        .collect();
+    let mut lists = vec![Vec::new(); n];
+    let mut wires: Vec<(usize, usize)> = wires.into_iter().collect();
+    wires.sort();
+    for (a, b) in wires {
+        let (a, b) = if rng.gen() { (a, b) } else { (b, a) };
+        lists[a].push(names[b].as_str());
+    }
+    let mut lines: Vec<String> = (0..n)
+        .filter(|i| !lists[*i].is_empty())
+        .map(|i| format!("{}: {}", names[i], lists[i].join(" ")))
+        .collect();
+    lines.shuffle(rng);
+    return Ok(lines.join("\n") + "\n");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solvable() {
+        for day in 1..=NUM_DAYS {
+            for seed in 0..2 {
+                let input = generate(day, seed, &[]).unwrap();
+                if let Err(error) = crate::solve(day, &input) {
+                    panic!("seed {} generated an invalid input:\n{}", seed, error);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reproducible() {
+        let params = [("rows".to_string(), 10), ("length".to_string(), 12)];
+        let input = generate(12, 7, &params).unwrap();
+        assert_eq!(input.lines().count(), 10);
+        assert_eq!(generate(12, 7, &params).unwrap(), input);
+        assert_ne!(generate(12, 8, &params).unwrap(), input);
+    }
+
+    #[test]
+    fn bad_params() {
+        assert_eq!(parse_assignment("size=17"), Ok(("size".to_string(), 17)));
+        assert!(parse_assignment("size").is_err());
+        assert!(parse_assignment("size=-1").is_err());
+        assert!(generate(17, 0, &[("size".to_string(), 0)]).is_err());
+        assert!(generate(17, 0, &[("rows".to_string(), 10)]).is_err());
+        assert!(generate(21, 0, &[("size".to_string(), 133)]).is_err());
+    }
+}
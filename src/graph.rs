@@ -0,0 +1,336 @@
+use crate::buffer::{too_small, Buffer};
+use crate::error::Result;
+#[cfg(feature = "std")]
+use std::fmt::Write;
+
+// Graphs for the days whose puzzles are networks. Nodes are numbered from 0 as `NodeId`s, and the
+// edges from each node are kept together (in compressed sparse row form), so that a graph only
+// needs a buffer for its nodes and one for its edges, whatever its shape. Like the solvers' other
+// storage, the buffers are provided by the caller.
+
+pub type NodeId = u16;
+
+// Marks a missing node, such as a name which hasn't been seen yet.
+pub const NO_NODE: NodeId = NodeId::MAX;
+
+// The number of keys that `name_key` can return.
+pub const NAME_KEYS: usize = 27 * 27 * 27;
+
+// Returns a key for a name of 1 to 3 letters, by reading the letters as digits from 1 to 26 in
+// base 27. Upper and lower case letters are treated alike. Names of up to 2 letters have keys below
+// `27 * 27`, and 0 isn't the key of any name, so a day can use it for a special one.
+#[inline]
+pub fn name_key(name: &[u8]) -> Option<usize> {
+    if name.is_empty() || name.len() > 3 { return None }
+    let mut key = 0;
+    for c in name {
+        // Setting bit 5 lowers the case of a letter, and leaves anything else outside `a..=z`.
+        let digit = (c | 0x20).wrapping_sub(b'a');
+        if digit >= 26 { return None }
+        key = 27 * key + digit as usize + 1;
+    }
+    return Some(key);
+}
+
+// Numbers the nodes of a graph by their names, in the order in which they are first seen. Each
+// name has a key, which indexes a table of the ids given out so far, and the names themselves are
+// kept for output.
+pub struct Names<'a, 'i> {
+    ids: &'a mut [NodeId],
+    names: Buffer<'a, &'i [u8]>,
+}
+
+impl<'a, 'i> Names<'a, 'i> {
+    // `ids` must have an entry for each key, and `names` for each node.
+    pub fn new(ids: &'a mut [NodeId], names: &'a mut [&'i [u8]]) -> Names<'a, 'i> {
+        ids.fill(NO_NODE);
+        return Names{ids, names: Buffer::new(names)};
+    }
+
+    // Returns the id of `name`, which has the given key, numbering it if it's new. Fails if there
+    // is no room for another node, at `name`, which must be part of the input.
+    #[inline]
+    pub fn intern(&mut self, key: usize, name: &'i [u8]) -> Result<NodeId> {
+        let id = self.ids[key];
+        if id != NO_NODE { return Ok(id) }
+        if self.names.len() == NO_NODE as usize { return Err(too_small(name)) }
+        let id = self.names.len() as NodeId;
+        self.names.push(name, name)?;
+        self.ids[key] = id;
+        return Ok(id);
+    }
+
+    // Returns the id of the name with the given key, if it has been seen.
+    #[inline]
+    pub fn get(&self, key: usize) -> Option<NodeId> {
+        return Some(self.ids[key]).filter(|id| *id != NO_NODE);
+    }
+
+    pub fn len(&self) -> usize {
+        return self.names.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.names.is_empty();
+    }
+
+    #[inline]
+    pub fn name(&self, id: NodeId) -> &'i [u8] {
+        return self.names[id as usize];
+    }
+
+    // Returns the names of the nodes, indexed by id.
+    pub fn names(&self) -> &[&'i [u8]] {
+        return &self.names;
+    }
+}
+
+// A graph in which each edge leads to a node and carries a value of type `W`, such as its length.
+// The edges from node `u` are `adjacent[offsets[u]..offsets[u + 1]]`. In an undirected graph, each
+// edge is stored once at either end.
+#[derive(Clone, Copy, Debug)]
+pub struct Graph<'a, W> {
+    offsets: &'a [u32],
+    adjacent: &'a [(NodeId, W)],
+    directed: bool,
+}
+
+// Returns the lengths of the buffers for a graph with `num_nodes` nodes and `num_edges` edges: one
+// for the offsets, and one for the edges from each node.
+pub fn buffer_lens(num_nodes: usize, num_edges: usize, directed: bool) -> (usize, usize) {
+    return (num_nodes + 1, if directed { num_edges } else { 2 * num_edges });
+}
+
+impl<'a, W: Copy> Graph<'a, W> {
+    // Builds a directed graph of `num_nodes` nodes from a list of edges `(a, b, w)`, each of which
+    // leads from `a` to `b`. The edges from each node stay in the order in which they are listed.
+    // The buffers must have the lengths from `buffer_lens`.
+    pub fn directed(
+        num_nodes: usize,
+        edges: impl Iterator<Item = (NodeId, NodeId, W)> + Clone,
+        offsets: &'a mut [u32],
+        adjacent: &'a mut [(NodeId, W)],
+    ) -> Graph<'a, W> {
+        return Graph::build(num_nodes, edges, true, offsets, adjacent);
+    }
+
+    // Like `directed`, but each edge leads both ways.
+    pub fn undirected(
+        num_nodes: usize,
+        edges: impl Iterator<Item = (NodeId, NodeId, W)> + Clone,
+        offsets: &'a mut [u32],
+        adjacent: &'a mut [(NodeId, W)],
+    ) -> Graph<'a, W> {
+        return Graph::build(num_nodes, edges, false, offsets, adjacent);
+    }
+
+    fn build(
+        num_nodes: usize,
+        edges: impl Iterator<Item = (NodeId, NodeId, W)> + Clone,
+        directed: bool,
+        offsets: &'a mut [u32],
+        adjacent: &'a mut [(NodeId, W)],
+    ) -> Graph<'a, W> {
+        // Count the edges from each node, so that `offsets[u]` is where the edges from `u` start.
+        let offsets = &mut offsets[..num_nodes + 1];
+        offsets.fill(0);
+        for (a, b, _) in edges.clone() {
+            offsets[a as usize + 1] += 1;
+            if !directed && a != b { offsets[b as usize + 1] += 1 }
+        }
+        for u in 0..num_nodes { offsets[u + 1] += offsets[u] }
+        let adjacent = &mut adjacent[..offsets[num_nodes] as usize];
+        // Place each edge at the next free position for its node, moving `offsets[u]` along to the
+        // end of the edges from `u`, and then shift the offsets back.
+        for (a, b, w) in edges {
+            adjacent[offsets[a as usize] as usize] = (b, w);
+            offsets[a as usize] += 1;
+            if !directed && a != b {
+                adjacent[offsets[b as usize] as usize] = (a, w);
+                offsets[b as usize] += 1;
+            }
+        }
+        offsets.copy_within(0..num_nodes, 1);
+        offsets[0] = 0;
+        return Graph{offsets, adjacent, directed};
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        return self.offsets.len() - 1;
+    }
+
+    pub fn is_directed(&self) -> bool {
+        return self.directed;
+    }
+
+    // Returns the edges from node `u`, each with the node it leads to.
+    #[inline]
+    pub fn edges(&self, u: NodeId) -> &'a [(NodeId, W)] {
+        let u = u as usize;
+        return &self.adjacent[self.offsets[u] as usize..self.offsets[u + 1] as usize];
+    }
+
+    #[inline]
+    pub fn degree(&self, u: NodeId) -> usize {
+        return self.edges(u).len();
+    }
+
+    // Returns the nodes in the order that a breadth-first search from `start` reaches them, only
+    // following an edge `(u, v, w)` from `u` to `v` if `follow(u, v, w)` is true. The search
+    // doesn't keep track of the nodes it has seen, so `follow` must mark them, and only return true
+    // once for each node. `queue` must hold a node for each node that can be reached.
+    pub fn bfs<'b, F: FnMut(NodeId, NodeId, W) -> bool>(
+        &self,
+        start: NodeId,
+        queue: &'b mut [NodeId],
+        follow: F,
+    ) -> Bfs<'a, 'b, W, F> {
+        queue[0] = start;
+        return Bfs{graph: *self, queue, head: 0, tail: 1, follow};
+    }
+
+    // Like `bfs`, but for a depth-first search, which returns each node before those it leads to.
+    // `stack` must hold an entry for each node on the longest path that the search follows.
+    pub fn dfs<'b, F: FnMut(NodeId, NodeId, W) -> bool>(
+        &self,
+        start: NodeId,
+        stack: &'b mut [(NodeId, u32)],
+        follow: F,
+    ) -> Dfs<'a, 'b, W, F> {
+        return Dfs{graph: *self, stack, len: 0, next: Some(start), follow};
+    }
+
+    // Labels each node with the number of its connected component, numbering the components in
+    // order of their lowest node, and returns the number of components. Components are found by
+    // following the edges from each node, so for a directed graph, a node is only in the same
+    // component as a lower one if it can be reached from it. `component` must hold a label for
+    // each node, and `queue` a node for each node.
+    pub fn components(&self, component: &mut [NodeId], queue: &mut [NodeId]) -> usize {
+        let component = &mut component[..self.num_nodes()];
+        component.fill(NO_NODE);
+        let mut count = 0;
+        for u in 0..self.num_nodes() {
+            if component[u] != NO_NODE { continue }
+            let label = count as NodeId;
+            component[u] = label;
+            let follow = |_, v: NodeId, _| {
+                if component[v as usize] != NO_NODE { return false }
+                component[v as usize] = label;
+                return true;
+            };
+            for _ in self.bfs(u as NodeId, queue, follow) {}
+            count += 1;
+        }
+        return count;
+    }
+}
+
+// The iterator returned by `Graph::bfs`. The queue holds the nodes which have been reached, of
+// which those before `head` have been returned.
+pub struct Bfs<'a, 'b, W, F> {
+    graph: Graph<'a, W>,
+    queue: &'b mut [NodeId],
+    head: usize,
+    tail: usize,
+    follow: F,
+}
+
+impl<W: Copy, F: FnMut(NodeId, NodeId, W) -> bool> Iterator for Bfs<'_, '_, W, F> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        if self.head == self.tail { return None }
+        let u = self.queue[self.head];
+        self.head += 1;
+        for (v, w) in self.graph.edges(u) {
+            if !(self.follow)(u, *v, *w) { continue }
+            self.queue[self.tail] = *v;
+            self.tail += 1;
+        }
+        return Some(u);
+    }
+}
+
+// The iterator returned by `Graph::dfs`. Each entry on the stack is a node on the current path,
+// and the position of the next of its edges to try. `next` is the node to return next, if it has
+// been found already.
+pub struct Dfs<'a, 'b, W, F> {
+    graph: Graph<'a, W>,
+    stack: &'b mut [(NodeId, u32)],
+    len: usize,
+    next: Option<NodeId>,
+    follow: F,
+}
+
+impl<W: Copy, F: FnMut(NodeId, NodeId, W) -> bool> Iterator for Dfs<'_, '_, W, F> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        if let Some(u) = self.next.take() {
+            self.stack[self.len] = (u, 0);
+            self.len += 1;
+            return Some(u);
+        }
+        while self.len > 0 {
+            let (u, i) = &mut self.stack[self.len - 1];
+            let Some(&(v, w)) = self.graph.edges(*u).get(*i as usize) else {
+                self.len -= 1;
+                continue;
+            };
+            *i += 1;
+            if !(self.follow)(*u, v, w) { continue }
+            self.stack[self.len] = (v, 0);
+            self.len += 1;
+            return Some(v);
+        }
+        return None;
+    }
+}
+
+// Quotes a string for the DOT language.
+#[cfg(feature = "std")]
+fn dot_string(s: &str) -> String {
+    let mut result = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    return result;
+}
+
+#[cfg(feature = "std")]
+impl<W: Copy> Graph<'_, W> {
+    // Writes the graph in Graphviz's DOT language, as a graph called `name`, with each node
+    // labelled by `label` and each edge by `edge_label`, unless it returns an empty string. Each
+    // edge of an undirected graph is written once.
+    pub fn dot(
+        &self,
+        name: &str,
+        label: impl Fn(NodeId) -> String,
+        edge_label: impl Fn(W) -> String,
+    ) -> String {
+        let (kind, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        let mut out = format!("{} {} {{\n", kind, dot_string(name));
+        for u in 0..self.num_nodes() as NodeId {
+            writeln!(out, "    {} [label={}];", u, dot_string(&label(u))).unwrap();
+        }
+        for u in 0..self.num_nodes() as NodeId {
+            for (v, w) in self.edges(u) {
+                if !self.directed && *v < u { continue }
+                let edge_label = edge_label(*w);
+                let attributes = match edge_label.is_empty() {
+                    true => String::new(),
+                    false => format!(" [label={}]", dot_string(&edge_label)),
+                };
+                writeln!(out, "    {} {} {}{};", u, arrow, v, attributes).unwrap();
+            }
+        }
+        out.push_str("}\n");
+        return out;
+    }
+}
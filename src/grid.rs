@@ -0,0 +1,231 @@
+use crate::error::{Error, Result};
+use core::fmt;
+use core::ops::{Index, IndexMut};
+
+// A rectangular grid of bytes. Cell `(x, y)` is stored at `cells[y * stride + x]`. Grids parsed
+// from the input borrow it directly, in which case each row is followed by its newline and the
+// stride is one more than the width. Copies are stored compactly, either in a `Vec` or in a buffer
+// provided by the caller.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: T,
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl<'a> Grid<&'a [u8]> {
+    // Parses a grid from lines of the same (nonzero) width, each terminated by a newline.
+    pub fn parse(input: &'a [u8]) -> Result<Grid<&'a [u8]>> {
+        let Some(width) = input.iter().position(|b| *b == b'\n') else {
+            return Err(Error::byte(&input[input.len()..], b'\n'));
+        };
+        if width == 0 { return Err(Error::new(input, "a row of cells")) }
+        let mut height = 0;
+        for line in input.chunks(width + 1) {
+            match line.iter().position(|b| *b == b'\n') {
+                Some(end) if end == width => {}
+                Some(end) if end < width => {
+                    return Err(Error::new(&line[end..], "a line of the same width"));
+                }
+                _ if line.len() <= width => return Err(Error::byte(&line[line.len()..], b'\n')),
+                _ => return Err(Error::new(&line[width..], "a line of the same width")),
+            }
+            height += 1;
+        }
+        return Ok(Grid{cells: input, width, height, stride: width + 1});
+    }
+}
+
+#[cfg(feature = "std")]
+impl Grid<Vec<u8>> {
+    // Creates a grid with every cell set to `value`.
+    pub fn new(width: usize, height: usize, value: u8) -> Grid<Vec<u8>> {
+        return Grid{cells: vec![value; width * height], width, height, stride: width};
+    }
+}
+
+impl<'a> Grid<&'a mut [u8]> {
+    // Like `Grid::new`, but stores the cells at the start of `cells`, which must hold at least
+    // `width * height` of them.
+    pub fn new_in(
+        cells: &'a mut [u8],
+        width: usize,
+        height: usize,
+        value: u8,
+    ) -> Grid<&'a mut [u8]> {
+        let cells = &mut cells[..width * height];
+        cells.fill(value);
+        return Grid{cells, width, height, stride: width};
+    }
+}
+
+impl<T: AsRef<[u8]>> Grid<T> {
+    pub fn width(&self) -> usize { self.width }
+    pub fn height(&self) -> usize { self.height }
+    pub fn stride(&self) -> usize { self.stride }
+
+    // The underlying storage, for code which does its own index arithmetic with `stride`.
+    pub fn cells(&self) -> &[u8] {
+        return self.cells.as_ref();
+    }
+
+    // Returns the index of `(x, y)` within `cells()`.
+    pub fn index(&self, (x, y): (usize, usize)) -> usize {
+        return y * self.stride + x;
+    }
+
+    // Returns the coordinates of the cell at `index` within `cells()`.
+    pub fn position(&self, index: usize) -> (usize, usize) {
+        return (index % self.stride, index / self.stride);
+    }
+
+    pub fn contains(&self, (x, y): (usize, usize)) -> bool {
+        return x < self.width && y < self.height;
+    }
+
+    // Returns the cell at `(x, y)`, or `None` if it is out of bounds. Signed coordinates can be
+    // cast to `usize`, since negative values wrap around to something out of bounds.
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<u8> {
+        if !self.contains((x, y)) { return None }
+        return Some(self.cells()[self.index((x, y))]);
+    }
+
+    pub fn row(&self, y: usize) -> &[u8] {
+        return &self.cells()[y * self.stride..][..self.width];
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[u8]> {
+        return (0..self.height).map(|y| self.row(y));
+    }
+
+    // Returns the coordinates of the first cell (in row-major order) which is equal to `value`.
+    pub fn find(&self, value: u8) -> Option<(usize, usize)> {
+        for (y, row) in self.rows().enumerate() {
+            if let Some(x) = row.iter().position(|c| *c == value) { return Some((x, y)) }
+        }
+        return None;
+    }
+
+    // Iterates over the in-bounds cells directly above, left, right, and below `(x, y)`.
+    pub fn neighbours(&self, (x, y): (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let candidates = [
+            (x, y.wrapping_sub(1)),
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y + 1),
+        ];
+        return candidates.into_iter().filter(|p| self.contains(*p));
+    }
+
+    // Fails at the first cell which doesn't satisfy `valid`.
+    pub fn validate(&self, valid: impl Fn(u8) -> bool, expected: &'static str) -> Result<()> {
+        for row in self.rows() {
+            if let Some(x) = row.iter().position(|c| !valid(*c)) {
+                return Err(Error::new(&row[x..], expected));
+            }
+        }
+        return Ok(());
+    }
+
+    // Fails unless the grid has as many rows as it has columns.
+    pub fn check_square(&self) -> Result<()> {
+        if self.height > self.width {
+            return Err(Error::new(&self.cells()[self.index((0, self.width))..], "a square grid"));
+        }
+        if self.height < self.width {
+            let cells = self.cells();
+            return Err(Error::new(&cells[cells.len()..], "a square grid"));
+        }
+        return Ok(());
+    }
+
+    // Returns a compact, owned copy of the grid.
+    #[cfg(feature = "std")]
+    pub fn to_owned(&self) -> Grid<Vec<u8>> {
+        return self.map(|c| c);
+    }
+
+    // Returns an owned copy of the grid with `f` applied to each cell.
+    #[cfg(feature = "std")]
+    pub fn map(&self, f: impl Fn(u8) -> u8) -> Grid<Vec<u8>> {
+        let mut cells = vec![0; self.width * self.height];
+        self.map_into(&mut cells, f);
+        return Grid{cells, width: self.width, height: self.height, stride: self.width};
+    }
+
+    // Like `to_owned`, but stores the copy at the start of `cells`, which must hold at least
+    // `width * height` bytes.
+    pub fn copy_into<'a>(&self, cells: &'a mut [u8]) -> Grid<&'a mut [u8]> {
+        return self.map_into(cells, |c| c);
+    }
+
+    // Like `map`, but stores the copy at the start of `cells`, which must hold at least
+    // `width * height` bytes.
+    pub fn map_into<'a>(&self, cells: &'a mut [u8], f: impl Fn(u8) -> u8) -> Grid<&'a mut [u8]> {
+        let cells = &mut cells[..self.width * self.height];
+        for (row, copy) in self.rows().zip(cells.chunks_mut(self.width)) {
+            for (c, x) in copy.iter_mut().zip(row) { *c = f(*x) }
+        }
+        return Grid{cells, width: self.width, height: self.height, stride: self.width};
+    }
+
+    // Returns a copy of the grid reflected along its main diagonal, so that rows become columns.
+    #[cfg(feature = "std")]
+    pub fn transpose(&self) -> Grid<Vec<u8>> {
+        let mut result = Grid::new(self.height, self.width, 0);
+        for (y, row) in self.rows().enumerate() {
+            for (x, c) in row.iter().enumerate() { result[(y, x)] = *c }
+        }
+        return result;
+    }
+
+    // Returns a copy of the grid rotated a quarter turn clockwise.
+    #[cfg(feature = "std")]
+    pub fn rotate(&self) -> Grid<Vec<u8>> {
+        let mut result = Grid::new(self.height, self.width, 0);
+        for (y, row) in self.rows().enumerate() {
+            for (x, c) in row.iter().enumerate() { result[(self.height - 1 - y, x)] = *c }
+        }
+        return result;
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> Grid<T> {
+    pub fn cells_mut(&mut self) -> &mut [u8] {
+        return self.cells.as_mut();
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [u8] {
+        let (start, width) = (y * self.stride, self.width);
+        return &mut self.cells_mut()[start..][..width];
+    }
+}
+
+impl<T: AsRef<[u8]>> Index<(usize, usize)> for Grid<T> {
+    type Output = u8;
+    fn index(&self, (x, y): (usize, usize)) -> &u8 {
+        return &self.row(y)[x];
+    }
+}
+
+impl<T: AsRef<[u8]> + AsMut<[u8]>> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut u8 {
+        return &mut self.row_mut(y)[x];
+    }
+}
+
+// Prints the grid in the same format as the puzzle input: one line per row. Bytes which aren't
+// UTF-8 are replaced as in `String::from_utf8_lossy`.
+impl<T: AsRef<[u8]>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            for chunk in row.utf8_chunks() {
+                f.write_str(chunk.valid())?;
+                if !chunk.invalid().is_empty() { f.write_str("\u{FFFD}")? }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
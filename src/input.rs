@@ -0,0 +1,21 @@
+use std::fs::File;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+
+// Reads the whole of `reader` as a puzzle input. A single `read` call on a pipe may return only
+// part of the data, so this keeps reading until EOF and grows the buffer as needed. The solvers
+// then parse the input in place as a borrowed `&str`.
+pub fn read(mut reader: impl Read) -> io::Result<String> {
+    let mut buffer = Vec::new();
+    reader.read_to_end(&mut buffer)?;
+    return String::from_utf8(buffer).map_err(|e| {
+        let offset = e.utf8_error().valid_up_to();
+        io::Error::new(io::ErrorKind::InvalidData, format!("not UTF-8 (at byte {})", offset))
+    });
+}
+
+// Reads the puzzle input from a file.
+pub fn read_file(path: &Path) -> io::Result<String> {
+    return read(File::open(path)?);
+}
@@ -0,0 +1,170 @@
+// The solvers' core is `no_std`, so that it can run on targets without an operating system. Each
+// day's `solve_bytes` takes the input as bytes, along with any scratch buffers that the day needs,
+// and doesn't allocate. Everything else, including the `&str` entry points which size the buffers
+// from the input and the command line tools built on them, needs the `std` feature.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+pub mod bench;
+pub mod buffer;
+#[cfg(feature = "std")]
+pub mod cli;
+#[cfg(feature = "std")]
+mod dispatch;
+pub mod error;
+pub mod explain;
+#[cfg(feature = "std")]
+pub mod gen;
+pub mod graph;
+pub mod grid;
+#[cfg(feature = "std")]
+pub mod input;
+#[cfg(feature = "std")]
+pub mod lint;
+pub mod phases;
+#[cfg(feature = "std")]
+pub mod registry;
+#[cfg(feature = "std")]
+pub mod render;
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+#[cfg(feature = "std")]
+pub use dispatch::{draw, graph, lint, run, solve, solve_part, solve_parts};
+#[cfg(feature = "std")]
+pub use dispatch::{DRAWABLE_DAYS, GRAPH_DAYS, LINTABLE_DAYS};
+
+pub const NUM_DAYS: u8 = 25;
+
+// The answer to one part of a puzzle. Each day computes its answers in whatever
+// integer type suits it best, and they are all widened to a common type here.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Answer(i128);
+
+macro_rules! answer_from {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(x: $t) -> Answer { Answer(x as i128) }
+        })*
+    };
+}
+
+answer_from!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+// What a solver expects of an input whose answer would overflow, for its error. The days total up
+// their answers as `Answer`s with checked arithmetic, so that a scaled-up input which would wrap
+// around in a narrower type is reported instead of giving a wrong answer.
+pub const OVERFLOW: &str = "an input whose answer fits in 128 bits";
+
+impl Answer {
+    pub fn checked_add(self, other: impl Into<Answer>) -> Option<Answer> {
+        return self.0.checked_add(other.into().0).map(Answer);
+    }
+
+    pub fn checked_sub(self, other: impl Into<Answer>) -> Option<Answer> {
+        return self.0.checked_sub(other.into().0).map(Answer);
+    }
+
+    pub fn checked_mul(self, other: impl Into<Answer>) -> Option<Answer> {
+        return self.0.checked_mul(other.into().0).map(Answer);
+    }
+}
+
+// Narrows an answer to a 64-bit integer, for callers which can't use `i128`, failing if it doesn't
+// fit.
+impl TryFrom<Answer> for i64 {
+    type Error = core::num::TryFromIntError;
+
+    fn try_from(answer: Answer) -> core::result::Result<i64, Self::Error> {
+        return i64::try_from(answer.0);
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+// One of the two parts of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Part { One, Two }
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+// Returns whether the given day has the given part. Day 25 only has one part.
+pub fn has_part(day: u8, part: Part) -> bool {
+    return part == Part::One || day != NUM_DAYS;
+}
+
+// The answers for a single day. A part is missing if it wasn't solved, or if
+// the day doesn't have it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Solution {
+    pub part1: Option<Answer>,
+    pub part2: Option<Answer>,
+}
+
+impl Solution {
+    // Returns a solution with an answer for only one part.
+    pub fn only(part: Part, answer: Answer) -> Solution {
+        match part {
+            Part::One => Solution{part1: Some(answer), part2: None},
+            Part::Two => Solution{part1: None, part2: Some(answer)},
+        }
+    }
+}
+
+impl From<(Answer, Answer)> for Solution {
+    fn from((part1, part2): (Answer, Answer)) -> Solution {
+        Solution{part1: Some(part1), part2: Some(part2)}
+    }
+}
+
+impl From<Answer> for Solution {
+    fn from(part1: Answer) -> Solution {
+        Solution{part1: Some(part1), part2: None}
+    }
+}
+
+// Formats the solution in the same way as the original binaries: one answer per
+// line.
+impl fmt::Display for Solution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(part1) = self.part1 { writeln!(f, "{}", part1)? }
+        if let Some(part2) = self.part2 { writeln!(f, "{}", part2)? }
+        Ok(())
+    }
+}
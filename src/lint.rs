@@ -0,0 +1,38 @@
+use std::fmt;
+
+// Some of the fast solvers rely on structure which the official inputs have but the puzzle text
+// doesn't promise, such as day 21's clear lanes through the middle of the map. The parsers reject
+// inputs which the solvers can't handle at all, but an input which breaks one of these assumptions
+// can still parse and give a wrong answer. Each such day has a `lint` function which checks its
+// assumptions one by one, so that an input can be vetted before its answers are trusted.
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Assumption {
+    pub description: &'static str,
+    // Why the input breaks the assumption, or `None` if it holds.
+    pub violation: Option<String>,
+}
+
+impl Assumption {
+    pub fn new(description: &'static str, violation: Option<String>) -> Assumption {
+        return Assumption{description, violation};
+    }
+}
+
+// Formats a position within a grid in the same way as a parse error, counting from 1.
+pub fn position((x, y): (usize, usize)) -> String {
+    return format!("line {}, column {}", y + 1, x + 1);
+}
+
+// Formats the result as a single line, such as:
+//
+//   ok    the border of the map is clear
+//   FAIL  the row and column through `S` are clear: '#' at line 66, column 3
+impl fmt::Display for Assumption {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.violation {
+            None => write!(f, "ok    {}", self.description),
+            Some(violation) => write!(f, "FAIL  {}: {}", self.description, violation),
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::cell::RefCell;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+
+// Phase timing, where the solvers mark the end of each phase of their work, such as parsing or one
+// of the parts, with `phase!`, and the time spent in each phase is added up. Like `explain!`, while
+// timing is off each mark is a single relaxed load and a branch. The timings are kept per thread.
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    return ENABLED.load(Ordering::Relaxed);
+}
+
+// The total time spent in one phase, over all of the runs since the timings were last taken.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Phase {
+    pub name: &'static str,
+    pub total: Duration,
+}
+
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct Timings {
+    // When the current phase started, if a run is being timed.
+    last: Option<Instant>,
+    runs: usize,
+    phases: Vec<Phase>,
+}
+
+#[cfg(feature = "std")]
+thread_local! {
+    static TIMINGS: RefCell<Timings> = RefCell::new(Timings::default());
+}
+
+// Starts timing a run of a solver, whose first phase starts now.
+#[cfg(feature = "std")]
+pub fn start() {
+    if !enabled() { return }
+    TIMINGS.with_borrow_mut(|timings| {
+        timings.runs += 1;
+        timings.last = Some(Instant::now());
+    });
+}
+
+// Ends the current phase, adding the time since the previous one ended to the total for `name`.
+// Phases with the same name are added together, so a phase can end inside a loop.
+#[cfg(feature = "std")]
+pub fn lap(name: &'static str) {
+    let now = Instant::now();
+    TIMINGS.with_borrow_mut(|timings| {
+        let Some(last) = timings.last.replace(now) else { return };
+        match timings.phases.iter_mut().find(|phase| phase.name == name) {
+            Some(phase) => phase.total += now - last,
+            None => timings.phases.push(Phase{name, total: now - last}),
+        }
+    });
+}
+
+// Returns the number of runs timed and the total for each phase, in the order that the phases
+// first ended, and starts again from nothing.
+#[cfg(feature = "std")]
+pub fn take() -> (usize, Vec<Phase>) {
+    let timings = TIMINGS.take();
+    return (timings.runs, timings.phases);
+}
+
+// Ends the current phase while phase timing is on.
+#[cfg(feature = "std")]
+#[macro_export]
+macro_rules! phase {
+    ($name:expr) => {
+        if $crate::phases::enabled() { $crate::phases::lap($name) }
+    };
+}
+
+// Without `std` there is no clock, so the name is only type checked.
+#[cfg(not(feature = "std"))]
+#[macro_export]
+macro_rules! phase {
+    ($name:expr) => {
+        let _: &'static str = $name;
+    };
+}
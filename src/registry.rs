@@ -0,0 +1,196 @@
+use crate::{Answer, Solution, NUM_DAYS};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+// A registry of verified answers for many inputs, so that the solvers can be checked against every
+// input that anyone has verified, not just the one in `inputs/`. Inputs are personal and aren't
+// checked in, so each one is identified by the SHA-256 of its contents. The registry is a text
+// file with one line per input:
+//
+//   day17 1f8b...e2a4 102 94
+//
+// giving the day, the hash in hex, and the answer to each part (`-` for day 25's part 2). Blank
+// lines and lines starting with `#` are ignored.
+
+pub type Hash = [u8; 32];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub day: u8,
+    pub hash: Hash,
+    pub solution: Solution,
+}
+
+// The result of recording the answers for an input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recorded {
+    New,
+    // The input was already recorded with the same answers.
+    Unchanged,
+    // The input was already recorded with these different answers, which were kept.
+    Conflict(Solution),
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Registry {
+    // Sorted by day and then by hash, so that the file is stable under edits.
+    entries: Vec<Entry>,
+}
+
+impl Registry {
+    pub fn parse(text: &str) -> Result<Registry, String> {
+        let mut registry = Registry::default();
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') { continue }
+            let entry = parse_entry(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+            if let Recorded::Conflict(_) = registry.record(entry.day, entry.hash, entry.solution) {
+                return Err(format!("line {}: input recorded twice with different answers", i + 1));
+            }
+        }
+        return Ok(registry);
+    }
+
+    // Reads the registry from a file. A missing file is an empty registry.
+    pub fn load(path: &Path) -> Result<Registry, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => Registry::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Registry::default()),
+            Err(error) => Err(format!("can't read {}: {}", path.display(), error)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        return fs::write(path, self.to_string());
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        return &self.entries;
+    }
+
+    pub fn lookup(&self, day: u8, hash: &Hash) -> Option<Solution> {
+        let i = self.entries.binary_search_by_key(&(day, hash), |e| (e.day, &e.hash)).ok()?;
+        return Some(self.entries[i].solution);
+    }
+
+    // Records the answers for an input, unless it was already recorded with different answers.
+    pub fn record(&mut self, day: u8, hash: Hash, solution: Solution) -> Recorded {
+        match self.entries.binary_search_by_key(&(day, &hash), |e| (e.day, &e.hash)) {
+            Ok(i) if self.entries[i].solution == solution => Recorded::Unchanged,
+            Ok(i) => Recorded::Conflict(self.entries[i].solution),
+            Err(i) => {
+                self.entries.insert(i, Entry{day, hash, solution});
+                Recorded::New
+            }
+        }
+    }
+}
+
+impl fmt::Display for Registry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# Verified answers for each input, keyed by its SHA-256.")?;
+        writeln!(f, "# Add an input with `aoc record <day> --inputs <dir>`.")?;
+        for entry in &self.entries {
+            writeln!(f, "day{:02} {} {} {}", entry.day, hex(&entry.hash),
+                     answer(entry.solution.part1), answer(entry.solution.part2))?;
+        }
+        Ok(())
+    }
+}
+
+fn answer(answer: Option<Answer>) -> String {
+    return answer.map_or("-".to_string(), |answer| answer.to_string());
+}
+
+fn parse_entry(line: &str) -> Result<Entry, String> {
+    let fields: Vec<&str> = line.split_whitespace().collect();
+    let [day, hash, part1, part2] = fields[..] else {
+        return Err("expected a day, a hash, and two answers".to_string());
+    };
+    let day = match day.strip_prefix("day").map(str::parse) {
+        Some(Ok(day @ 1..=NUM_DAYS)) => day,
+        _ => return Err(format!("not a day: {:?}", day)),
+    };
+    let Some(hash) = parse_hex(hash) else { return Err(format!("not a SHA-256: {:?}", hash)) };
+    let parse_answer = |s: &str| match s {
+        "-" => Ok(None),
+        _ => s.parse::<i128>().map(|x| Some(Answer::from(x)))
+              .map_err(|_| format!("not an answer: {:?}", s)),
+    };
+    let solution = Solution{part1: parse_answer(part1)?, part2: parse_answer(part2)?};
+    return Ok(Entry{day, hash, solution});
+}
+
+pub fn hex(hash: &Hash) -> String {
+    return hash.iter().map(|byte| format!("{:02x}", byte)).collect();
+}
+
+fn parse_hex(s: &str) -> Option<Hash> {
+    if s.len() != 64 || !s.is_ascii() { return None }
+    let mut hash = [0; 32];
+    for i in 0..32 {
+        hash[i] = u8::from_str_radix(&s[2 * i..2 * i + 2], 16).ok()?;
+    }
+    return Some(hash);
+}
+
+// Returns whether `actual` agrees with the recorded answers in `expected`. Parts which weren't
+// solved aren't compared, so that solving a single part can still be checked.
+pub fn agrees(expected: &Solution, actual: &Solution) -> bool {
+    let agrees = |expected, actual: Option<Answer>| actual.is_none() || actual == expected;
+    return agrees(expected.part1, actual.part1) && agrees(expected.part2, actual.part2);
+}
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// Computes the SHA-256 hash of `data`, as specified in FIPS 180-4.
+pub fn sha256(data: &[u8]) -> Hash {
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a,
+        0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+    ];
+    // The message is padded with a 1 bit, then zeros, then its length in bits, to a multiple of
+    // 64 bytes.
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 { message.push(0) }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+    for block in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for i in 0..16 {
+            w[i] = u32::from_be_bytes(block[4 * i..4 * i + 4].try_into().unwrap());
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(ch).wrapping_add(K[i]).wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            (h, g, f, e, d, c, b, a) = (g, f, e, d.wrapping_add(t1), c, b, a, t1.wrapping_add(t2));
+        }
+        for (x, y) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *x = x.wrapping_add(y);
+        }
+    }
+    let mut hash = [0; 32];
+    for (chunk, x) in hash.chunks_mut(4).zip(state) { chunk.copy_from_slice(&x.to_be_bytes()) }
+    return hash;
+}
@@ -0,0 +1,187 @@
+use crate::grid::Grid;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// Renders grids as images, for debugging and for explaining solutions. Each cell of a grid is
+// drawn as a square of pixels, coloured according to a palette which maps cell values to colours.
+
+pub type Colour = [u8; 3];
+
+pub const BLACK: Colour = [0, 0, 0];
+
+// A colour for each possible cell value. Values without an entry are drawn in black.
+#[derive(Clone)]
+pub struct Palette {
+    colours: [Colour; 256],
+}
+
+impl Palette {
+    pub const fn new(entries: &[(u8, Colour)]) -> Palette {
+        let mut colours = [BLACK; 256];
+        let mut i = 0;
+        while i < entries.len() {
+            colours[entries[i].0 as usize] = entries[i].1;
+            i += 1;
+        }
+        return Palette{colours};
+    }
+
+    pub fn colour(&self, value: u8) -> Colour {
+        return self.colours[value as usize];
+    }
+}
+
+// An RGB image, stored row by row with 3 bytes per pixel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    // Draws each cell of `grid` as a `scale` by `scale` square.
+    pub fn from_grid<T: AsRef<[u8]>>(grid: &Grid<T>, palette: &Palette, scale: usize) -> Image {
+        let (width, height) = (grid.width() * scale, grid.height() * scale);
+        let mut pixels = Vec::with_capacity(width * height * 3);
+        for row in grid.rows() {
+            let start = pixels.len();
+            for cell in row {
+                for _ in 0..scale { pixels.extend_from_slice(&palette.colour(*cell)) }
+            }
+            for _ in 1..scale { pixels.extend_from_within(start..start + width * 3) }
+        }
+        return Image{width, height, pixels};
+    }
+
+    // Writes the image as a binary PPM (P6) file.
+    pub fn write_ppm(&self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        return out.write_all(&self.pixels);
+    }
+
+    // Writes the image as a PNG file. The image data is stored without compression, which keeps
+    // the encoder small at the cost of larger files.
+    pub fn write_png(&self, out: &mut impl Write) -> io::Result<()> {
+        out.write_all(b"\x89PNG\r\n\x1a\n")?;
+        let mut header = Vec::new();
+        header.extend_from_slice(&(self.width as u32).to_be_bytes());
+        header.extend_from_slice(&(self.height as u32).to_be_bytes());
+        // 8 bits per channel, RGB, default compression, filtering, and no interlacing.
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+        write_chunk(out, b"IHDR", &header)?;
+        // Each row of the image is preceded by its filter type, which is always 0 (none).
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width * 3).take(self.height) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+        write_chunk(out, b"IDAT", &zlib_stored(&raw))?;
+        return write_chunk(out, b"IEND", &[]);
+    }
+}
+
+fn write_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(&[kind.as_slice(), data].concat());
+    return out.write_all(&crc.to_be_bytes());
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB88320 } else { crc >> 1 };
+        }
+    }
+    return !crc;
+}
+
+// Wraps `data` in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() { out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]) }
+    while let Some(block) = blocks.next() {
+        let is_last = blocks.peek().is_none();
+        out.push(is_last as u8);
+        out.extend_from_slice(&(block.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(!(block.len() as u16)).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    out.extend_from_slice(&((b << 16) | a).to_be_bytes());
+    return out;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat { Ppm, Png }
+
+pub fn parse_image_format(s: &str) -> Result<ImageFormat, String> {
+    match s {
+        "ppm" => Ok(ImageFormat::Ppm),
+        "png" => Ok(ImageFormat::Png),
+        _ => Err(format!("unknown image format: {:?} (expected `ppm` or `png`)", s)),
+    }
+}
+
+// A sequence of images written to a directory as `frame0000.png`, `frame0001.png`, and so on. A
+// single frame is a still picture, and several frames make an animation.
+//
+// The solvers emit frames as they go and don't stop for I/O errors, so the first error is kept
+// and reported by `finish`, and any frames after it are dropped.
+pub struct Frames {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    count: usize,
+    error: Option<io::Error>,
+}
+
+impl Frames {
+    pub fn new(dir: &Path, format: ImageFormat, scale: usize) -> Frames {
+        let error = fs::create_dir_all(dir).err();
+        return Frames{dir: dir.to_path_buf(), format, scale, count: 0, error};
+    }
+
+    pub fn emit<T: AsRef<[u8]>>(&mut self, grid: &Grid<T>, palette: &Palette) {
+        if self.error.is_some() { return }
+        let image = Image::from_grid(grid, palette, self.scale);
+        let extension = match self.format {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        };
+        let path = self.dir.join(format!("frame{:04}.{}", self.count, extension));
+        let result = File::create(path).and_then(|file| {
+            let mut out = BufWriter::new(file);
+            match self.format {
+                ImageFormat::Ppm => image.write_ppm(&mut out)?,
+                ImageFormat::Png => image.write_png(&mut out)?,
+            }
+            return out.flush();
+        });
+        match result {
+            Ok(()) => self.count += 1,
+            Err(error) => self.error = Some(error),
+        }
+    }
+
+    // Returns the number of frames written, or the first error.
+    pub fn finish(self) -> io::Result<usize> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.count),
+        }
+    }
+}
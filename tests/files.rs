@@ -0,0 +1,94 @@
+// Tests for solving several input files in one invocation of a per-day binary. Each file is solved
+// in turn, labelled with its name, and a bad file doesn't stop the others from being solved.
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const EXAMPLE: &str = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+
+// Writes the given inputs to files in a directory of the test's own under Cargo's target
+// directory, so that nothing is left behind elsewhere, returning their paths.
+fn write_inputs(test: &str, inputs: &[(&str, &str)]) -> Vec<PathBuf> {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(test);
+    fs::create_dir_all(&dir).unwrap();
+    return inputs.iter().map(|(name, input)| {
+        let path = dir.join(name);
+        fs::write(&path, input).unwrap();
+        return path;
+    }).collect();
+}
+
+fn day15(args: &[&str], files: &[PathBuf]) -> Output {
+    return Command::new(env!("CARGO_BIN_EXE_day15")).args(args).args(files).output().unwrap();
+}
+
+#[test]
+fn labels_each_file() {
+    let files = write_inputs("labels", &[("a.input", EXAMPLE), ("b.input", "qp=3\n")]);
+    let output = day15(&[], &files);
+    assert!(output.status.success());
+    let expected = format!("{}: 1320 145\n{}: 97 6\n", files[0].display(), files[1].display());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), expected);
+}
+
+#[test]
+fn single_file_is_unlabelled() {
+    let files = write_inputs("single", &[("a.input", EXAMPLE)]);
+    let output = day15(&["--part", "1"], &files);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "1320\n");
+}
+
+#[test]
+fn continues_after_a_bad_file() {
+    let files = write_inputs("bad", &[("bad.input", "rn=x\n"), ("a.input", EXAMPLE)]);
+    let output = day15(&["--format", "json"], &files);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("\"part1\": 1320"), "{}", stdout);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with(&format!("{}: day15: line 1", files[0].display())), "{}", stderr);
+}
+
+// Errors go to stderr, so that stdout holds nothing but one JSON object per input.
+#[test]
+fn unreadable_file_is_reported_on_stderr() {
+    let files = write_inputs("unreadable", &[("a.input", EXAMPLE)]);
+    let missing = files[0].with_file_name("missing.input");
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "15", "--format", "json"])
+        .args([&missing, &files[0]])
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.lines().all(|line| line.starts_with('{')), "{}", stdout);
+    assert_eq!(stdout.lines().count(), 1, "{}", stdout);
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.starts_with(&format!("day15: can't read {}", missing.display())), "{}", stderr);
+}
+
+#[test]
+fn conflicting_record_is_reported_on_stderr() {
+    let files = write_inputs("conflict", &[("a.input", EXAMPLE)]);
+    let registry = files[0].with_file_name("answers.txt");
+    let record = || {
+        return Command::new(env!("CARGO_BIN_EXE_aoc"))
+            .args(["record", "15", "--registry"])
+            .args([&registry, &files[0]])
+            .output()
+            .unwrap();
+    };
+    // Record the example's answers afresh, then change the recorded part 1 answer.
+    let _ = fs::remove_file(&registry);
+    assert!(record().status.success());
+    let recorded = fs::read_to_string(&registry).unwrap();
+    fs::write(&registry, recorded.replace(" 1320 ", " 1321 ")).unwrap();
+    let output = record();
+    assert!(!output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let expected = format!("day15 {}: already recorded with 1321", files[0].display());
+    assert!(stderr.starts_with(&expected), "{}", stderr);
+}
@@ -0,0 +1,131 @@
+// Tests for the shared graph type used by the days whose puzzles are networks.
+
+use aoc2023rs::graph::{self, name_key, Graph, Names, NodeId, NAME_KEYS, NO_NODE};
+
+// The edges of a graph of 5 nodes, with a cycle through 0, 1, and 2, and 4 on its own.
+const EDGES: [(NodeId, NodeId, u8); 5] = [(0, 1, 5), (2, 0, 7), (1, 2, 6), (0, 3, 8), (1, 0, 9)];
+
+fn build(directed: bool, offsets: &mut [u32], adjacent: &mut [(NodeId, u8)]) -> String {
+    let edges = EDGES.iter().copied();
+    let graph = match directed {
+        true => Graph::directed(5, edges, offsets, adjacent),
+        false => Graph::undirected(5, edges, offsets, adjacent),
+    };
+    return (0..5).map(|u| format!("{:?}", graph.edges(u))).collect::<Vec<_>>().join(" ");
+}
+
+#[test]
+fn names() {
+    assert_eq!(name_key(b"a"), Some(1));
+    assert_eq!(name_key(b"ZZ"), Some(27 * 26 + 26));
+    assert_eq!(name_key(b"zzz"), Some(NAME_KEYS - 1));
+    assert_eq!(name_key(b"AbC"), name_key(b"abc"));
+    assert_eq!(name_key(b""), None);
+    assert_eq!(name_key(b"abcd"), None);
+    assert_eq!(name_key(b"a1"), None);
+
+    let (mut ids, mut buffer) = ([0; NAME_KEYS], [&b""[..]; 2]);
+    let mut names = Names::new(&mut ids, &mut buffer);
+    let input: &[u8] = b"abc xy abc qq";
+    assert_eq!(names.intern(name_key(&input[0..3]).unwrap(), &input[0..3]), Ok(0));
+    assert_eq!(names.intern(name_key(&input[4..6]).unwrap(), &input[4..6]), Ok(1));
+    assert_eq!(names.intern(name_key(&input[7..10]).unwrap(), &input[7..10]), Ok(0));
+    let error = names.intern(name_key(&input[11..]).unwrap(), &input[11..]).unwrap_err();
+    assert_eq!(error.offset(input), 11);
+    assert_eq!(names.get(name_key(b"xy").unwrap()), Some(1));
+    assert_eq!(names.get(name_key(b"qq").unwrap()), None);
+    assert_eq!(names.names(), [&b"abc"[..], b"xy"]);
+    assert_eq!(names.name(1), b"xy");
+}
+
+#[test]
+fn build_order() {
+    let (offsets_len, adjacent_len) = graph::buffer_lens(5, EDGES.len(), true);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    assert_eq!(build(true, &mut offsets, &mut adjacent),
+               "[(1, 5), (3, 8)] [(2, 6), (0, 9)] [(0, 7)] [] []");
+    let (offsets_len, adjacent_len) = graph::buffer_lens(5, EDGES.len(), false);
+    let (mut offsets, mut adjacent) = (vec![0; offsets_len], vec![(0, 0); adjacent_len]);
+    assert_eq!(build(false, &mut offsets, &mut adjacent),
+               "[(1, 5), (2, 7), (3, 8), (1, 9)] [(0, 5), (2, 6), (0, 9)] [(0, 7), (1, 6)] \
+                [(0, 8)] []");
+}
+
+#[test]
+fn search() {
+    let (mut offsets, mut adjacent) = ([0; 6], [(0, 0); 10]);
+    let graph = Graph::undirected(5, EDGES.iter().copied(), &mut offsets, &mut adjacent);
+    let mut seen = [false; 5];
+    seen[3] = true;
+    let mut follow = |_, v: NodeId, _| !std::mem::replace(&mut seen[v as usize], true);
+    let mut queue = [NO_NODE; 5];
+    let order: Vec<NodeId> = graph.bfs(3, &mut queue, &mut follow).collect();
+    assert_eq!(order, [3, 0, 1, 2]);
+
+    let mut seen = [false; 5];
+    seen[3] = true;
+    let mut follow = |_, v: NodeId, _| !std::mem::replace(&mut seen[v as usize], true);
+    let mut stack = [(0, 0); 5];
+    let order: Vec<NodeId> = graph.dfs(3, &mut stack, &mut follow).collect();
+    assert_eq!(order, [3, 0, 1, 2]);
+
+    // Only following the edges of weight below 8 cuts 3 off from the rest.
+    let mut seen = [false; 5];
+    seen[0] = true;
+    let follow = |_, v: NodeId, w| w < 8 && !std::mem::replace(&mut seen[v as usize], true);
+    let order: Vec<NodeId> = graph.dfs(0, &mut stack, follow).collect();
+    assert_eq!(order, [0, 1, 2]);
+}
+
+#[test]
+fn components() {
+    let (mut component, mut queue) = ([0; 5], [0; 5]);
+    let (mut offsets, mut adjacent) = ([0; 6], [(0, 0); 10]);
+    let graph = Graph::undirected(5, EDGES.iter().copied(), &mut offsets, &mut adjacent);
+    assert_eq!(graph.components(&mut component, &mut queue), 2);
+    assert_eq!(component, [0, 0, 0, 0, 1]);
+    // In a directed graph, a node is only in the same component as a lower one if it can be
+    // reached from it, so 3 is on its own even though it leads to 0.
+    let graph = Graph::directed(5, [(3, 0, 0)].into_iter(), &mut offsets, &mut adjacent);
+    assert_eq!(graph.components(&mut component, &mut queue), 5);
+    assert_eq!(component, [0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn dot() {
+    let (mut offsets, mut adjacent) = ([0; 3], [(0, ""); 4]);
+    let edges = [(0, 1, "a \"quoted\" label"), (1, 1, "")].into_iter();
+    let graph = Graph::undirected(2, edges.clone(), &mut offsets, &mut adjacent);
+    let label = |u| format!("node {}", u);
+    assert_eq!(graph.dot("g", label, |w: &str| w.to_string()), "\
+graph \"g\" {
+    0 [label=\"node 0\"];
+    1 [label=\"node 1\"];
+    0 -- 1 [label=\"a \\\"quoted\\\" label\"];
+    1 -- 1;
+}
+");
+    let graph = Graph::directed(2, edges, &mut offsets, &mut adjacent);
+    assert!(graph.dot("g", label, |w: &str| w.to_string()).contains("    0 -> 1 [label="));
+}
+
+#[test]
+fn days() {
+    let day08 = "LR\n\nAAA = (BBB, ZZZ)\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+    let dot = aoc2023rs::graph(8, day08).unwrap();
+    assert!(dot.starts_with("digraph \"day08\" {\n    0 [label=\"AAA\"];\n"));
+    assert!(dot.contains("    0 -> 2 [label=\"R\"];\n"));
+    let day25 = "abc: def ghi\ndef: ghi\n";
+    assert_eq!(aoc2023rs::graph(25, day25).unwrap(), "\
+graph \"day25\" {
+    0 [label=\"abc\"];
+    1 [label=\"def\"];
+    2 [label=\"ghi\"];
+    0 -- 1;
+    0 -- 2;
+    1 -- 2;
+}
+");
+    let error = aoc2023rs::graph(25, "abc: de\n").unwrap_err();
+    assert_eq!((error.line, error.expected.as_str()), (1, "a component name of 3 letters"));
+}
@@ -0,0 +1,63 @@
+// Tests for the shared grid type used by the grid-based days.
+
+use aoc2023rs::error::ParseError;
+use aoc2023rs::grid::Grid;
+
+fn parse_error(input: &str) -> String {
+    let error = Grid::parse(input.as_bytes()).unwrap_err();
+    let error = ParseError::new(0, input, error);
+    return format!("{}:{}: expected {}", error.line, error.column, error.expected);
+}
+
+#[test]
+fn parse() {
+    let grid = Grid::parse(b"ab.\n.cd\n").unwrap();
+    assert_eq!((grid.width(), grid.height(), grid.stride()), (3, 2, 4));
+    assert_eq!(grid.row(1), b".cd");
+    assert_eq!(grid[(1, 0)], b'b');
+    assert_eq!(grid.get((2, 1)), Some(b'd'));
+    assert_eq!(grid.get((3, 1)), None);
+    assert_eq!(grid.get((-1isize as usize, 0)), None);
+    assert_eq!(grid.find(b'c'), Some((1, 1)));
+    assert_eq!(grid.position(grid.index((2, 1))), (2, 1));
+    assert_eq!(grid.to_string(), "ab.\n.cd\n");
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(parse_error(""), "1:1: expected '\\n'");
+    assert_eq!(parse_error("\n"), "1:1: expected a row of cells");
+    assert_eq!(parse_error("abc\nde\n"), "2:3: expected a line of the same width");
+    assert_eq!(parse_error("abc\ndefg\n"), "2:4: expected a line of the same width");
+    assert_eq!(parse_error("abc\ndef"), "2:4: expected '\\n'");
+    assert_eq!(parse_error("abc\nde"), "2:3: expected '\\n'");
+}
+
+#[test]
+fn validate() {
+    let grid = Grid::parse(b"..#\n#x.\n").unwrap();
+    let error = grid.validate(|c| c == b'.' || c == b'#', "'.' or '#'").unwrap_err();
+    assert_eq!(error.offset(b"..#\n#x.\n"), 5);
+    assert!(grid.check_square().is_err());
+    assert!(Grid::parse(b"ab\ncd\n").unwrap().check_square().is_ok());
+}
+
+#[test]
+fn neighbours() {
+    let grid = Grid::new(3, 2, b'.');
+    let corner: Vec<_> = grid.neighbours((0, 0)).collect();
+    assert_eq!(corner, [(1, 0), (0, 1)]);
+    let edge: Vec<_> = grid.neighbours((1, 1)).collect();
+    assert_eq!(edge, [(1, 0), (0, 1), (2, 1)]);
+}
+
+#[test]
+fn transform() {
+    let grid = Grid::parse(b"abc\ndef\n").unwrap();
+    assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+    assert_eq!(grid.rotate().to_string(), "da\neb\nfc\n");
+    assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF\n");
+    let mut owned = grid.to_owned();
+    owned[(0, 1)] = b'x';
+    assert_eq!(owned.row(1), b"xef");
+}
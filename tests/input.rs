@@ -0,0 +1,44 @@
+// Tests for the shared input loader. Pipes deliver data in chunks, so a reader which returns only a
+// few bytes at a time must still produce the complete input.
+
+use aoc2023rs::input;
+use std::io;
+use std::io::Read;
+
+// A reader which returns at most `chunk` bytes per call, and is interrupted before every other
+// chunk, in the same way as a slow pipe.
+struct Chunked<'a> {
+    data: &'a [u8],
+    chunk: usize,
+    interrupt: bool,
+}
+
+impl Read for Chunked<'_> {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        self.interrupt = !self.interrupt;
+        if self.interrupt { return Err(io::ErrorKind::Interrupted.into()) }
+        let n = self.chunk.min(buffer.len()).min(self.data.len());
+        buffer[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        return Ok(n);
+    }
+}
+
+#[test]
+fn reads_chunked_input_to_eof() {
+    // Larger than any of the buffers that the solvers used to read into.
+    let expected = "#.#..#.##.\n".repeat(100_000);
+    for chunk in [1, 7, 4096, 65536] {
+        let reader = Chunked{data: expected.as_bytes(), chunk, interrupt: false};
+        let actual = input::read(reader).unwrap();
+        assert!(actual == expected, "chunk size {}: read {} of {} bytes",
+                chunk, actual.len(), expected.len());
+    }
+}
+
+#[test]
+fn rejects_invalid_utf8() {
+    let error = input::read(&b"1abc2\n\xff\n"[..]).unwrap_err();
+    assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(error.to_string(), "not UTF-8 (at byte 6)");
+}
@@ -0,0 +1,113 @@
+// Regression tests against the puzzle inputs in `inputs/`. Each day's answers are checked against
+// `inputs/dayNN.output`, both when solving the whole day and when solving each part on its own.
+// Puzzle inputs are personal and are not checked in, so any day without both an input and an
+// expected output is skipped rather than failed.
+
+use aoc2023rs::{cli, input, Part, NUM_DAYS};
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// Produces a line-by-line comparison of the expected and actual output, marking missing lines
+// with `-` and unexpected lines with `+`.
+fn diff(expected: &str, actual: &str) -> String {
+    let mut result = String::new();
+    let mut expected = expected.lines();
+    let mut actual = actual.lines();
+    loop {
+        match (expected.next(), actual.next()) {
+            (None, None) => break,
+            (Some(e), Some(a)) if e == a => writeln!(result, "  {}", e).unwrap(),
+            (e, a) => {
+                if let Some(e) = e { writeln!(result, "- {}", e).unwrap() }
+                if let Some(a) = a { writeln!(result, "+ {}", a).unwrap() }
+            }
+        }
+    }
+    return result;
+}
+
+fn inputs_dir() -> PathBuf {
+    return Path::new(env!("CARGO_MANIFEST_DIR")).join("inputs");
+}
+
+fn check(day: u8) {
+    let dir = inputs_dir();
+    let input_path = cli::input_path(&dir, day);
+    let output_path = input_path.with_extension("output");
+    let (Ok(input), Ok(expected)) =
+        (input::read_file(&input_path), fs::read_to_string(&output_path)) else {
+        eprintln!("day{:02}: skipped (no input)", day);
+        return;
+    };
+    let actual = match aoc2023rs::solve(day, &input) {
+        Ok(solution) => solution.to_string(),
+        Err(error) => panic!("{}", error),
+    };
+    if actual != expected {
+        panic!("day{:02}: output differs from {}:\n{}",
+               day, output_path.display(), diff(&expected, &actual));
+    }
+    // Day 25 only has one line of output, so it is only checked for part 1.
+    for (part, expected) in [Part::One, Part::Two].into_iter().zip(expected.lines()) {
+        match aoc2023rs::solve_part(day, part, &input) {
+            Ok(answer) => assert_eq!(answer.to_string(), expected, "day{:02} part {}", day, part),
+            Err(error) => panic!("{}", error),
+        }
+    }
+}
+
+// The tests below only look for each day's input under its usual name, so any other input or
+// expected output in `inputs/`, such as a second input for a day or a misnamed output, would
+// silently go untested.
+#[test]
+fn every_input_is_tested() {
+    let dir = inputs_dir();
+    let Ok(entries) = fs::read_dir(&dir) else { return };
+    let tested: Vec<PathBuf> = (1..=NUM_DAYS)
+        .map(|day| cli::input_path(&dir, day))
+        .flat_map(|path| [path.with_extension("output"), path])
+        .collect();
+    let mut untested: Vec<String> = entries
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|e| e == "input" || e == "output"))
+        .filter(|path| !tested.contains(path))
+        .map(|path| path.display().to_string())
+        .collect();
+    untested.sort();
+    assert!(untested.is_empty(), "not covered by any day's test: {}", untested.join(", "));
+}
+
+macro_rules! days {
+    ($($name:ident: $day:expr,)*) => {
+        $(#[test] fn $name() { check($day) })*
+    };
+}
+
+days! {
+    day01: 1,
+    day02: 2,
+    day03: 3,
+    day04: 4,
+    day05: 5,
+    day06: 6,
+    day07: 7,
+    day08: 8,
+    day09: 9,
+    day10: 10,
+    day11: 11,
+    day12: 12,
+    day13: 13,
+    day14: 14,
+    day15: 15,
+    day16: 16,
+    day17: 17,
+    day18: 18,
+    day19: 19,
+    day20: 20,
+    day21: 21,
+    day22: 22,
+    day23: 23,
+    day24: 24,
+    day25: 25,
+}
@@ -0,0 +1,29 @@
+use aoc2023rs::{Answer, Solution};
+
+const WORDS: [&str; 9] = ["one", "two", "three", "four", "five", "six", "seven", "eight", "nine"];
+
+// Returns the digits at every position in the line, optionally including spelled-out ones.
+fn digits(line: &str, words: bool) -> Vec<u32> {
+    let mut result = Vec::new();
+    for i in 0..line.len() {
+        let rest = &line[i..];
+        if let Some(d) = rest.chars().next().unwrap().to_digit(10) { result.push(d) }
+        for (value, word) in (1..).zip(WORDS) {
+            if words && rest.starts_with(word) { result.push(value) }
+        }
+    }
+    return result;
+}
+
+fn calibration(input: &str, words: bool) -> u32 {
+    let mut total = 0;
+    for line in input.lines() {
+        let digits = digits(line, words);
+        total += 10 * digits[0] + digits[digits.len() - 1];
+    }
+    return total;
+}
+
+pub fn solve(input: &str) -> Solution {
+    return (Answer::from(calibration(input, false)), Answer::from(calibration(input, true))).into();
+}
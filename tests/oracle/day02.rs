@@ -0,0 +1,27 @@
+use aoc2023rs::{Answer, Solution};
+
+pub fn solve(input: &str) -> Solution {
+    let mut possible = 0;
+    let mut power = 0;
+    for line in input.lines() {
+        let (game, draws) = line.split_once(": ").unwrap();
+        let id: u32 = game.strip_prefix("Game ").unwrap().parse().unwrap();
+        // The most cubes of each colour seen in any one draw.
+        let (mut red, mut green, mut blue) = (0, 0, 0);
+        for draw in draws.split("; ") {
+            for cubes in draw.split(", ") {
+                let (count, colour) = cubes.split_once(' ').unwrap();
+                let count: u32 = count.parse().unwrap();
+                match colour {
+                    "red" => red = red.max(count),
+                    "green" => green = green.max(count),
+                    "blue" => blue = blue.max(count),
+                    _ => panic!("unknown colour"),
+                }
+            }
+        }
+        if red <= 12 && green <= 13 && blue <= 14 { possible += id }
+        power += red * green * blue;
+    }
+    return (Answer::from(possible), Answer::from(power)).into();
+}
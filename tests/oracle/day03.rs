@@ -0,0 +1,51 @@
+use aoc2023rs::{Answer, Solution};
+
+// Finds every number in the schematic as `(value, y, first x, last x)`.
+fn numbers(grid: &[&[u8]]) -> Vec<(u32, usize, usize, usize)> {
+    let mut result = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            if !row[x].is_ascii_digit() {
+                x += 1;
+                continue;
+            }
+            let start = x;
+            let mut value = 0;
+            while x < row.len() && row[x].is_ascii_digit() {
+                value = 10 * value + (row[x] - b'0') as u32;
+                x += 1;
+            }
+            result.push((value, y, start, x - 1));
+        }
+    }
+    return result;
+}
+
+// Returns whether the cell `(x, y)` touches the number, including diagonally.
+fn touches((_, ny, first, last): (u32, usize, usize, usize), x: usize, y: usize) -> bool {
+    return y + 1 >= ny && y <= ny + 1 && x + 1 >= first && x <= last + 1;
+}
+
+pub fn solve(input: &str) -> Solution {
+    let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let numbers = numbers(&grid);
+    let mut symbols = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            if *c != b'.' && !c.is_ascii_digit() { symbols.push((*c, x, y)) }
+        }
+    }
+    let mut parts = 0;
+    for number in &numbers {
+        if symbols.iter().any(|(_, x, y)| touches(*number, *x, *y)) { parts += number.0 }
+    }
+    let mut ratios = 0;
+    for (c, x, y) in &symbols {
+        if *c != b'*' { continue }
+        let adjacent: Vec<u32> =
+            numbers.iter().filter(|n| touches(**n, *x, *y)).map(|n| n.0).collect();
+        if adjacent.len() == 2 { ratios += adjacent[0] * adjacent[1] }
+    }
+    return (Answer::from(parts), Answer::from(ratios)).into();
+}
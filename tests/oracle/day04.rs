@@ -0,0 +1,22 @@
+use aoc2023rs::{Answer, Solution};
+
+pub fn solve(input: &str) -> Solution {
+    let mut matches = Vec::new();
+    for line in input.lines() {
+        let (_, numbers) = line.split_once(": ").unwrap();
+        let (winning, have) = numbers.split_once(" | ").unwrap();
+        let winning: Vec<&str> = winning.split_whitespace().collect();
+        matches.push(have.split_whitespace().filter(|n| winning.contains(n)).count());
+    }
+    let points: u32 = matches.iter().filter(|m| **m > 0).map(|m| 1 << (m - 1)).sum();
+    // Scratch every copy of every card one at a time, in order.
+    let mut copies = vec![1u64; matches.len()];
+    let mut scratched = 0;
+    for card in 0..matches.len() {
+        for _ in 0..copies[card] {
+            scratched += 1;
+            for won in card + 1..=card + matches[card] { copies[won] += 1 }
+        }
+    }
+    return (Answer::from(points), Answer::from(scratched)).into();
+}
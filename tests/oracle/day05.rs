@@ -0,0 +1,72 @@
+use aoc2023rs::{Answer, Solution};
+
+// The ranges `(destination, source, length)` of each map.
+type Map = Vec<(u64, u64, u64)>;
+
+fn apply(map: &Map, x: u64) -> u64 {
+    for (destination, source, length) in map {
+        if (*source..source + length).contains(&x) { return destination + x - source }
+    }
+    return x;
+}
+
+fn location(maps: &[Map], seed: u64) -> u64 {
+    return maps.iter().fold(seed, |x, map| apply(map, x));
+}
+
+// Returns every number which the map sends to `y`.
+fn preimages(map: &Map, y: u64) -> Vec<u64> {
+    let mut result: Vec<u64> = map
+        .iter()
+        .filter(|(destination, _, length)| (*destination..destination + length).contains(&y))
+        .map(|(destination, source, _)| source + y - destination)
+        .collect();
+    result.push(y);
+    result.retain(|x| apply(map, *x) == y);
+    return result;
+}
+
+pub fn solve(input: &str) -> Solution {
+    let mut blocks = input.split("\n\n");
+    let seeds: Vec<u64> = blocks
+        .next()
+        .unwrap()
+        .strip_prefix("seeds:")
+        .unwrap()
+        .split_whitespace()
+        .map(|n| n.parse().unwrap())
+        .collect();
+    let maps: Vec<Map> = blocks
+        .map(|block| {
+            block
+                .lines()
+                .skip(1)
+                .map(|line| {
+                    let n: Vec<u64> = line.split(' ').map(|n| n.parse().unwrap()).collect();
+                    (n[0], n[1], n[2])
+                })
+                .collect()
+        })
+        .collect();
+    let part1 = seeds.iter().map(|seed| location(&maps, *seed)).min().unwrap();
+    // Every seed range is far too big to try every seed, but within a range the location only
+    // increases until one of the maps reaches the edge of one of its ranges. So the lowest location
+    // comes from the start of a seed range or from a seed which reaches the edge of a range.
+    let mut candidates: Vec<u64> = seeds.chunks(2).map(|pair| pair[0]).collect();
+    for (stage, map) in maps.iter().enumerate() {
+        for (_, source, length) in map {
+            let mut xs = vec![*source, source + length];
+            for earlier in maps[..stage].iter().rev() {
+                xs = xs.iter().flat_map(|x| preimages(earlier, *x)).collect();
+            }
+            candidates.extend(xs);
+        }
+    }
+    let part2 = candidates
+        .iter()
+        .filter(|x| seeds.chunks(2).any(|pair| (pair[0]..pair[0] + pair[1]).contains(x)))
+        .map(|seed| location(&maps, *seed))
+        .min()
+        .unwrap();
+    return (Answer::from(part1), Answer::from(part2)).into();
+}
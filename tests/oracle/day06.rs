@@ -0,0 +1,30 @@
+use aoc2023rs::day06::ways;
+use aoc2023rs::{Answer, Solution};
+
+// Tries holding the button for every possible length of time.
+fn ways_to_win(time: u64, distance: u64) -> u64 {
+    return (0..=time).filter(|t| t * (time - t) > distance).count() as u64;
+}
+
+pub fn solve(input: &str) -> Solution {
+    let mut lines = input.lines();
+    let times: Vec<&str> = lines.next().unwrap().split_whitespace().skip(1).collect();
+    let distances: Vec<&str> = lines.next().unwrap().split_whitespace().skip(1).collect();
+    let mut product = 1;
+    for (time, distance) in times.iter().zip(&distances) {
+        product *= ways_to_win(time.parse().unwrap(), distance.parse().unwrap());
+    }
+    let single = ways_to_win(times.concat().parse().unwrap(), distances.concat().parse().unwrap());
+    return (Answer::from(product), Answer::from(single)).into();
+}
+
+#[test]
+fn small_races() {
+    // The binary search and probe should agree with trying every time for every race which can be
+    // won at all.
+    for time in 1..100 {
+        for distance in 0..(time / 2) * (time - time / 2) {
+            assert_eq!(ways(time, distance), ways_to_win(time, distance), "{} {}", time, distance);
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use aoc2023rs::{Answer, Solution};
+
+const CARDS: &[u8] = b"23456789TJQKA";
+
+// Ranks the type of a hand without jokers, from 0 (high card) to 6 (five of a kind).
+fn kind(hand: &[u8]) -> u8 {
+    let mut counts: Vec<usize> =
+        CARDS.iter().map(|c| hand.iter().filter(|h| *h == c).count()).collect();
+    counts.sort();
+    counts.reverse();
+    return match (counts[0], counts[1]) {
+        (5, _) => 6,
+        (4, _) => 5,
+        (3, 2) => 4,
+        (3, _) => 3,
+        (2, 2) => 2,
+        (2, _) => 1,
+        _ => 0,
+    };
+}
+
+// Tries every card in place of each joker and returns the best type.
+fn best_kind(hand: &[u8]) -> u8 {
+    let Some(j) = hand.iter().position(|c| *c == b'J') else { return kind(hand) };
+    let mut best = 0;
+    for c in CARDS.iter().filter(|c| **c != b'J') {
+        let mut hand = hand.to_vec();
+        hand[j] = *c;
+        best = best.max(best_kind(&hand));
+    }
+    return best;
+}
+
+fn winnings(input: &str, jokers: bool) -> u64 {
+    let order: &[u8] = if jokers { b"J23456789TQKA" } else { CARDS };
+    let mut hands: Vec<((u8, Vec<usize>), u64)> = input
+        .lines()
+        .map(|line| {
+            let (hand, bid) = line.split_once(' ').unwrap();
+            let hand = hand.as_bytes();
+            let kind = if jokers { best_kind(hand) } else { kind(hand) };
+            let values = hand.iter().map(|c| order.iter().position(|o| o == c).unwrap()).collect();
+            ((kind, values), bid.parse().unwrap())
+        })
+        .collect();
+    hands.sort();
+    return (1..).zip(&hands).map(|(rank, (_, bid))| rank * bid).sum();
+}
+
+pub fn solve(input: &str) -> Solution {
+    return (Answer::from(winnings(input, false)), Answer::from(winnings(input, true))).into();
+}
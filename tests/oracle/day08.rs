@@ -0,0 +1,28 @@
+use aoc2023rs::{Answer, Solution};
+use std::collections::HashMap;
+
+pub fn solve(input: &str) -> Solution {
+    let (steps, body) = input.split_once("\n\n").unwrap();
+    let mut nodes = HashMap::new();
+    for line in body.lines() {
+        nodes.insert(&line[0..3], (&line[7..10], &line[12..15]));
+    }
+    let step = |node: &str, i: usize| -> &str {
+        let (left, right) = nodes[node];
+        return if steps.as_bytes()[i % steps.len()] == b'L' { left } else { right };
+    };
+    let mut node = "AAA";
+    let mut part1 = 0;
+    while node != "ZZZ" {
+        node = step(node, part1);
+        part1 += 1;
+    }
+    // Move all the ghosts together until they are all on an end node at once.
+    let mut ghosts: Vec<&str> = nodes.keys().filter(|n| n.ends_with('A')).copied().collect();
+    let mut part2 = 0;
+    while !ghosts.iter().all(|n| n.ends_with('Z')) {
+        for ghost in &mut ghosts { *ghost = step(ghost, part2) }
+        part2 += 1;
+    }
+    return (Answer::from(part1), Answer::from(part2)).into();
+}
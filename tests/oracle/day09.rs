@@ -0,0 +1,26 @@
+use aoc2023rs::{Answer, Solution};
+
+// Builds the whole table of differences, then extends every row at both ends.
+fn extrapolate(sequence: Vec<i64>) -> (i64, i64) {
+    let mut rows = vec![sequence];
+    while rows.last().unwrap().iter().any(|x| *x != 0) {
+        let row = rows.last().unwrap();
+        rows.push(row.windows(2).map(|w| w[1] - w[0]).collect());
+    }
+    let (mut next, mut previous) = (0, 0);
+    for row in rows.iter().rev() {
+        next += row.last().copied().unwrap_or(0);
+        previous = row.first().copied().unwrap_or(0) - previous;
+    }
+    return (next, previous);
+}
+
+pub fn solve(input: &str) -> Solution {
+    let (mut next, mut previous) = (0, 0);
+    for line in input.lines() {
+        let (n, p) = extrapolate(line.split(' ').map(|x| x.parse().unwrap()).collect());
+        next += n;
+        previous += p;
+    }
+    return (Answer::from(next), Answer::from(previous)).into();
+}
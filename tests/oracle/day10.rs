@@ -0,0 +1,77 @@
+use aoc2023rs::{Answer, Solution};
+
+// The directions `(dx, dy)` that a pipe connects.
+fn ends(c: u8) -> Vec<(isize, isize)> {
+    return match c {
+        b'|' => vec![(0, -1), (0, 1)],
+        b'-' => vec![(1, 0), (-1, 0)],
+        b'L' => vec![(0, -1), (1, 0)],
+        b'J' => vec![(0, -1), (-1, 0)],
+        b'7' => vec![(0, 1), (-1, 0)],
+        b'F' => vec![(0, 1), (1, 0)],
+        _ => vec![],
+    };
+}
+
+pub fn solve(input: &str) -> Solution {
+    let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let (w, h) = (grid[0].len() as isize, grid.len() as isize);
+    let at = |(x, y): (isize, isize)| -> u8 {
+        if x < 0 || y < 0 || x >= w || y >= h { return b'.' }
+        return grid[y as usize][x as usize];
+    };
+    let start = (0..h)
+        .flat_map(|y| (0..w).map(move |x| (x, y)))
+        .find(|p| at(*p) == b'S')
+        .unwrap();
+    // The start connects to whichever of its neighbours connect back to it.
+    let start_ends: Vec<(isize, isize)> = [(0, -1), (0, 1), (1, 0), (-1, 0)]
+        .into_iter()
+        .filter(|(dx, dy)| ends(at((start.0 + dx, start.1 + dy))).contains(&(-dx, -dy)))
+        .collect();
+    let connections = |p: (isize, isize)| if p == start { start_ends.clone() } else { ends(at(p)) };
+    // Walk around the loop.
+    let mut path = vec![start];
+    let mut previous = start;
+    let mut position = (start.0 + start_ends[0].0, start.1 + start_ends[0].1);
+    while position != start {
+        path.push(position);
+        let (dx, dy) = connections(position)
+            .into_iter()
+            .find(|(dx, dy)| (position.0 + dx, position.1 + dy) != previous)
+            .unwrap();
+        previous = position;
+        position = (position.0 + dx, position.1 + dy);
+    }
+    // Draw the loop at three times the size, so that there are gaps between adjacent pipes, and
+    // flood fill the outside from the edges.
+    let (sw, sh) = (3 * w as usize, 3 * h as usize);
+    let mut wall = vec![vec![false; sw]; sh];
+    for (x, y) in &path {
+        let (cx, cy) = (3 * x + 1, 3 * y + 1);
+        wall[cy as usize][cx as usize] = true;
+        for (dx, dy) in connections((*x, *y)) {
+            wall[(cy + dy) as usize][(cx + dx) as usize] = true;
+        }
+    }
+    let mut outside = vec![vec![false; sw]; sh];
+    let mut stack = Vec::new();
+    for x in 0..sw { stack.extend([(x, 0), (x, sh - 1)]) }
+    for y in 0..sh { stack.extend([(0, y), (sw - 1, y)]) }
+    while let Some((x, y)) = stack.pop() {
+        if wall[y][x] || outside[y][x] { continue }
+        outside[y][x] = true;
+        if x > 0 { stack.push((x - 1, y)) }
+        if y > 0 { stack.push((x, y - 1)) }
+        if x + 1 < sw { stack.push((x + 1, y)) }
+        if y + 1 < sh { stack.push((x, y + 1)) }
+    }
+    let mut enclosed = 0;
+    for y in 0..h {
+        for x in 0..w {
+            if path.contains(&(x, y)) { continue }
+            if !outside[3 * y as usize + 1][3 * x as usize + 1] { enclosed += 1 }
+        }
+    }
+    return (Answer::from(path.len() / 2), Answer::from(enclosed)).into();
+}
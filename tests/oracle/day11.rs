@@ -0,0 +1,29 @@
+use aoc2023rs::{Answer, Solution};
+
+// Sums the distances between every pair of galaxies, walking along the rows and columns between
+// them and counting each empty one `expansion` times.
+fn distances(grid: &[&[u8]], expansion: u64) -> u64 {
+    let empty_row: Vec<bool> = grid.iter().map(|row| !row.contains(&b'#')).collect();
+    let empty_column: Vec<bool> =
+        (0..grid[0].len()).map(|x| grid.iter().all(|row| row[x] != b'#')).collect();
+    let mut galaxies = Vec::new();
+    for (y, row) in grid.iter().enumerate() {
+        for (x, c) in row.iter().enumerate() {
+            if *c == b'#' { galaxies.push((x, y)) }
+        }
+    }
+    let mut total = 0;
+    for i in 0..galaxies.len() {
+        for j in i + 1..galaxies.len() {
+            let ((ax, ay), (bx, by)) = (galaxies[i], galaxies[j]);
+            for x in ax.min(bx)..ax.max(bx) { total += if empty_column[x] { expansion } else { 1 } }
+            for y in ay.min(by)..ay.max(by) { total += if empty_row[y] { expansion } else { 1 } }
+        }
+    }
+    return total;
+}
+
+pub fn solve(input: &str) -> Solution {
+    let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    return (Answer::from(distances(&grid, 2)), Answer::from(distances(&grid, 1000000))).into();
+}
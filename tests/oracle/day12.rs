@@ -0,0 +1,29 @@
+use aoc2023rs::{Answer, Solution};
+
+// Tries every combination of conditions for the unknown springs.
+fn arrangements(springs: &[u8], groups: &[usize]) -> u64 {
+    let unknown: Vec<usize> = (0..springs.len()).filter(|i| springs[*i] == b'?').collect();
+    let mut count = 0;
+    let mut row = springs.to_vec();
+    for mask in 0u64..1 << unknown.len() {
+        for (bit, i) in unknown.iter().enumerate() {
+            row[*i] = if mask >> bit & 1 == 1 { b'#' } else { b'.' };
+        }
+        let lengths: Vec<usize> =
+            row.split(|c| *c == b'.').filter(|s| !s.is_empty()).map(|s| s.len()).collect();
+        if lengths == groups { count += 1 }
+    }
+    return count;
+}
+
+pub fn solve(input: &str) -> Solution {
+    let (mut folded, mut unfolded) = (0, 0);
+    for line in input.lines() {
+        let (springs, groups) = line.split_once(' ').unwrap();
+        let groups: Vec<usize> = groups.split(',').map(|g| g.parse().unwrap()).collect();
+        folded += arrangements(springs.as_bytes(), &groups);
+        let springs = [springs; 5].join("?");
+        unfolded += arrangements(springs.as_bytes(), &groups.repeat(5));
+    }
+    return (Answer::from(folded), Answer::from(unfolded)).into();
+}
@@ -0,0 +1,34 @@
+use aoc2023rs::{Answer, Solution};
+
+// Counts the cells which differ from their reflection in a mirror between rows `y - 1` and `y`.
+fn smudges(grid: &[&[u8]], y: usize) -> usize {
+    let mut count = 0;
+    for (a, b) in (0..y).rev().zip(y..grid.len()) {
+        count += grid[a].iter().zip(grid[b]).filter(|(x, y)| x != y).count();
+    }
+    return count;
+}
+
+// Summarizes the mirror with exactly the given number of smudges.
+fn summarize(grid: &[&[u8]], wanted: usize) -> usize {
+    for y in 1..grid.len() {
+        if smudges(grid, y) == wanted { return 100 * y }
+    }
+    let columns: Vec<Vec<u8>> =
+        (0..grid[0].len()).map(|x| grid.iter().map(|row| row[x]).collect()).collect();
+    let columns: Vec<&[u8]> = columns.iter().map(|c| c.as_slice()).collect();
+    for x in 1..columns.len() {
+        if smudges(&columns, x) == wanted { return x }
+    }
+    panic!("no mirror");
+}
+
+pub fn solve(input: &str) -> Solution {
+    let (mut clean, mut smudged) = (0, 0);
+    for pattern in input.split("\n\n") {
+        let grid: Vec<&[u8]> = pattern.lines().map(|line| line.as_bytes()).collect();
+        clean += summarize(&grid, 0);
+        smudged += summarize(&grid, 1);
+    }
+    return (Answer::from(clean), Answer::from(smudged)).into();
+}
@@ -0,0 +1,71 @@
+use aoc2023rs::day14::{buffer_len, read_input, spun_load};
+use aoc2023rs::{gen, Answer, Solution};
+use std::collections::HashMap;
+
+type Platform = Vec<Vec<u8>>;
+
+// Moves every round rock one cell at a time in the direction `(dx, dy)` until none can move.
+fn tilt(platform: &mut Platform, (dx, dy): (isize, isize)) {
+    let (w, h) = (platform[0].len() as isize, platform.len() as isize);
+    loop {
+        let mut moved = false;
+        for y in 0..h {
+            for x in 0..w {
+                let (nx, ny) = (x + dx, y + dy);
+                if nx < 0 || ny < 0 || nx >= w || ny >= h { continue }
+                let (x, y, nx, ny) = (x as usize, y as usize, nx as usize, ny as usize);
+                if platform[y][x] == b'O' && platform[ny][nx] == b'.' {
+                    platform[y][x] = b'.';
+                    platform[ny][nx] = b'O';
+                    moved = true;
+                }
+            }
+        }
+        if !moved { break }
+    }
+}
+
+fn spin(platform: &mut Platform) {
+    for direction in [(0, -1), (-1, 0), (0, 1), (1, 0)] { tilt(platform, direction) }
+}
+
+fn load(platform: &Platform) -> usize {
+    let h = platform.len();
+    return (0..h).map(|y| (h - y) * platform[y].iter().filter(|c| **c == b'O').count()).sum();
+}
+
+fn parse(input: &str) -> Platform {
+    return input.lines().map(|line| line.as_bytes().to_vec()).collect();
+}
+
+pub fn solve(input: &str) -> Solution {
+    let mut platform = parse(input);
+    tilt(&mut platform, (0, -1));
+    let tilted = load(&platform);
+    // Spin until a state repeats, remembering when each state was first seen.
+    let mut platform = parse(input);
+    let mut seen = HashMap::new();
+    let mut history = Vec::new();
+    let (start, period) = loop {
+        if let Some(start) = seen.get(&platform) { break (*start, history.len() - start) }
+        seen.insert(platform.clone(), history.len());
+        history.push(platform.clone());
+        spin(&mut platform);
+    };
+    let spun = load(&history[start + (1000000000 - start) % period]);
+    return (Answer::from(tilted), Answer::from(spun)).into();
+}
+
+#[test]
+fn few_cycles() {
+    for seed in 0..5 {
+        let input = gen::generate(14, seed, &[("size".to_string(), 10)]).unwrap();
+        let grid = read_input(&input).unwrap();
+        let mut cells = vec![0; buffer_len(&grid)];
+        let mut platform = parse(&input);
+        for cycles in 0..40 {
+            assert_eq!(spun_load(&grid, cycles, &mut cells), load(&platform), "{} cycles", cycles);
+            spin(&mut platform);
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use aoc2023rs::{Answer, Solution};
+
+fn hash(s: &str) -> usize {
+    let mut value = 0;
+    for c in s.bytes() { value = (value + c as usize) * 17 % 256 }
+    return value;
+}
+
+pub fn solve(input: &str) -> Solution {
+    let steps: Vec<&str> = input.trim_end().split(',').collect();
+    let total: usize = steps.iter().map(|s| hash(s)).sum();
+    let mut boxes: Vec<Vec<(&str, usize)>> = vec![Vec::new(); 256];
+    for step in &steps {
+        if let Some(label) = step.strip_suffix('-') {
+            boxes[hash(label)].retain(|(l, _)| *l != label);
+        } else {
+            let (label, focal) = step.split_once('=').unwrap();
+            let focal = focal.parse().unwrap();
+            let lenses = &mut boxes[hash(label)];
+            match lenses.iter_mut().find(|(l, _)| *l == label) {
+                Some(lens) => lens.1 = focal,
+                None => lenses.push((label, focal)),
+            }
+        }
+    }
+    let mut power = 0;
+    for (i, lenses) in boxes.iter().enumerate() {
+        for (j, (_, focal)) in lenses.iter().enumerate() { power += (i + 1) * (j + 1) * focal }
+    }
+    return (Answer::from(total), Answer::from(power)).into();
+}
@@ -0,0 +1,34 @@
+use aoc2023rs::{Answer, Solution};
+use std::collections::HashSet;
+
+type Beam = (isize, isize, isize, isize);
+
+// Follows every beam until it leaves the grid or repeats itself, and counts the energized tiles.
+fn energized(grid: &[&[u8]], start: Beam) -> usize {
+    let (w, h) = (grid[0].len() as isize, grid.len() as isize);
+    let mut seen = HashSet::new();
+    let mut beams = vec![start];
+    while let Some((x, y, dx, dy)) = beams.pop() {
+        if x < 0 || y < 0 || x >= w || y >= h || !seen.insert((x, y, dx, dy)) { continue }
+        let directions = match grid[y as usize][x as usize] {
+            b'/' => vec![(-dy, -dx)],
+            b'\\' => vec![(dy, dx)],
+            b'|' if dx != 0 => vec![(0, -1), (0, 1)],
+            b'-' if dy != 0 => vec![(-1, 0), (1, 0)],
+            _ => vec![(dx, dy)],
+        };
+        for (dx, dy) in directions { beams.push((x + dx, y + dy, dx, dy)) }
+    }
+    let tiles: HashSet<(isize, isize)> = seen.iter().map(|(x, y, _, _)| (*x, *y)).collect();
+    return tiles.len();
+}
+
+pub fn solve(input: &str) -> Solution {
+    let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let (w, h) = (grid[0].len() as isize, grid.len() as isize);
+    let mut starts = Vec::new();
+    for x in 0..w { starts.extend([(x, 0, 0, 1), (x, h - 1, 0, -1)]) }
+    for y in 0..h { starts.extend([(0, y, 1, 0), (w - 1, y, -1, 0)]) }
+    let best = starts.iter().map(|start| energized(&grid, *start)).max().unwrap();
+    return (Answer::from(energized(&grid, (0, 0, 1, 0))), Answer::from(best)).into();
+}
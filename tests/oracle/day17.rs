@@ -0,0 +1,32 @@
+use aoc2023rs::{Answer, Solution};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+
+// Runs Dijkstra's algorithm over states `(x, y, direction, run)`, where `run` is the number of
+// blocks moved in a straight line so far.
+fn least_heat(grid: &[Vec<u32>], min_run: usize, max_run: usize) -> u32 {
+    let (w, h) = (grid[0].len() as isize, grid.len() as isize);
+    const DIRECTIONS: [(isize, isize); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+    let mut done = HashSet::new();
+    let mut queue = BinaryHeap::from([Reverse((0, 0, 0, 0, 0)), Reverse((0, 0, 0, 1, 0))]);
+    while let Some(Reverse((heat, x, y, direction, run))) = queue.pop() {
+        if (x, y) == (w - 1, h - 1) && run >= min_run { return heat }
+        if !done.insert((x, y, direction, run)) { continue }
+        for turn in [0, 1, 3] {
+            if turn == 0 && run == max_run { continue }
+            if turn != 0 && run < min_run && run != 0 { continue }
+            let d = (direction + turn) % 4;
+            let (nx, ny) = (x + DIRECTIONS[d].0, y + DIRECTIONS[d].1);
+            if nx < 0 || ny < 0 || nx >= w || ny >= h { continue }
+            let run = if turn == 0 { run + 1 } else { 1 };
+            queue.push(Reverse((heat + grid[ny as usize][nx as usize], nx, ny, d, run)));
+        }
+    }
+    panic!("no path");
+}
+
+pub fn solve(input: &str) -> Solution {
+    let grid: Vec<Vec<u32>> =
+        input.lines().map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect()).collect();
+    return (Answer::from(least_heat(&grid, 1, 3)), Answer::from(least_heat(&grid, 4, 10))).into();
+}
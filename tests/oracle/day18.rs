@@ -0,0 +1,69 @@
+use aoc2023rs::{Answer, Solution};
+
+// Digs the trench on a grid whose rows and columns are compressed so that each one covers a span of
+// the lagoon with no corners in it, then flood fills the outside.
+fn area(instructions: &[(u8, i64)]) -> i64 {
+    let mut corners = vec![(0, 0)];
+    for (direction, length) in instructions {
+        let (x, y) = *corners.last().unwrap();
+        corners.push(match direction {
+            b'R' => (x + length, y),
+            b'D' => (x, y + length),
+            b'L' => (x - length, y),
+            _ => (x, y - length),
+        });
+    }
+    // The cells at each corner, with room for the outside around them.
+    let bounds = |coordinates: Vec<i64>| -> Vec<i64> {
+        let (min, max) = (*coordinates.iter().min().unwrap(), *coordinates.iter().max().unwrap());
+        let mut bounds: Vec<i64> = coordinates.iter().flat_map(|c| [*c, c + 1]).collect();
+        bounds.extend([min - 1, max + 2]);
+        bounds.sort();
+        bounds.dedup();
+        return bounds;
+    };
+    let xs = bounds(corners.iter().map(|c| c.0).collect());
+    let ys = bounds(corners.iter().map(|c| c.1).collect());
+    let (w, h) = (xs.len() - 1, ys.len() - 1);
+    let mut trench = vec![vec![false; w]; h];
+    for pair in corners.windows(2) {
+        let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+        for j in 0..h {
+            for i in 0..w {
+                let inside_x = x0.min(x1) <= xs[i] && xs[i + 1] <= x0.max(x1) + 1;
+                let inside_y = y0.min(y1) <= ys[j] && ys[j + 1] <= y0.max(y1) + 1;
+                if inside_x && inside_y { trench[j][i] = true }
+            }
+        }
+    }
+    let mut outside = vec![vec![false; w]; h];
+    let mut stack = vec![(0, 0)];
+    while let Some((i, j)) = stack.pop() {
+        if trench[j][i] || outside[j][i] { continue }
+        outside[j][i] = true;
+        if i > 0 { stack.push((i - 1, j)) }
+        if j > 0 { stack.push((i, j - 1)) }
+        if i + 1 < w { stack.push((i + 1, j)) }
+        if j + 1 < h { stack.push((i, j + 1)) }
+    }
+    let mut total = 0;
+    for j in 0..h {
+        for i in 0..w {
+            if !outside[j][i] { total += (xs[i + 1] - xs[i]) * (ys[j + 1] - ys[j]) }
+        }
+    }
+    return total;
+}
+
+pub fn solve(input: &str) -> Solution {
+    let mut small = Vec::new();
+    let mut large = Vec::new();
+    for line in input.lines() {
+        let parts: Vec<&str> = line.split(' ').collect();
+        small.push((parts[0].as_bytes()[0], parts[1].parse().unwrap()));
+        let colour = &parts[2][2..8];
+        let length = i64::from_str_radix(&colour[..5], 16).unwrap();
+        large.push((b"RDLU"[(colour.as_bytes()[5] - b'0') as usize], length));
+    }
+    return (Answer::from(area(&small)), Answer::from(area(&large))).into();
+}
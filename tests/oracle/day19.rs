@@ -0,0 +1,80 @@
+use aoc2023rs::{Answer, Solution};
+use std::collections::HashMap;
+
+// A rule `(category, comparison, value, target)`. The last rule of each workflow has no condition.
+type Rule<'a> = (usize, u8, u64, &'a str);
+
+fn accepted(workflows: &HashMap<&str, Vec<Rule>>, part: [u64; 4]) -> bool {
+    let mut name = "in";
+    while name != "A" && name != "R" {
+        for (category, comparison, value, target) in &workflows[name] {
+            let matched = match comparison {
+                b'<' => part[*category] < *value,
+                b'>' => part[*category] > *value,
+                _ => true,
+            };
+            if matched {
+                name = target;
+                break;
+            }
+        }
+    }
+    return name == "A";
+}
+
+pub fn solve(input: &str) -> Solution {
+    let (workflow_lines, part_lines) = input.split_once("\n\n").unwrap();
+    let mut workflows = HashMap::new();
+    for line in workflow_lines.lines() {
+        let (name, rules) = line.strip_suffix('}').unwrap().split_once('{').unwrap();
+        let rules: Vec<Rule> = rules
+            .split(',')
+            .map(|rule| match rule.split_once(':') {
+                Some((condition, target)) => {
+                    let category = b"xmas".iter().position(|c| *c == condition.as_bytes()[0]);
+                    let value = condition[2..].parse().unwrap();
+                    (category.unwrap(), condition.as_bytes()[1], value, target)
+                }
+                None => (0, b'=', 0, rule),
+            })
+            .collect();
+        workflows.insert(name, rules);
+    }
+    let mut total = 0;
+    for line in part_lines.lines() {
+        let line = line.strip_prefix('{').unwrap().strip_suffix('}').unwrap();
+        let mut part = [0; 4];
+        for (i, rating) in line.split(',').enumerate() { part[i] = rating[2..].parse().unwrap() }
+        if accepted(&workflows, part) { total += part.iter().sum::<u64>() }
+    }
+    // Every rating between two consecutive thresholds is treated the same way by every rule, so it
+    // is enough to try one part for each combination of spans between thresholds.
+    let mut bounds = [(); 4].map(|_| vec![1, 4001]);
+    for rules in workflows.values() {
+        for (category, comparison, value, _) in rules {
+            match comparison {
+                b'<' => bounds[*category].push(*value),
+                b'>' => bounds[*category].push(value + 1),
+                _ => {}
+            }
+        }
+    }
+    for bound in &mut bounds {
+        bound.sort();
+        bound.dedup();
+    }
+    let span = |c: usize, i: usize| bounds[c][i + 1] - bounds[c][i];
+    let mut combinations = 0;
+    for x in 0..bounds[0].len() - 1 {
+        for m in 0..bounds[1].len() - 1 {
+            for a in 0..bounds[2].len() - 1 {
+                for s in 0..bounds[3].len() - 1 {
+                    let part = [bounds[0][x], bounds[1][m], bounds[2][a], bounds[3][s]];
+                    if !accepted(&workflows, part) { continue }
+                    combinations += span(0, x) * span(1, m) * span(2, a) * span(3, s);
+                }
+            }
+        }
+    }
+    return (Answer::from(total), Answer::from(combinations)).into();
+}
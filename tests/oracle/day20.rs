@@ -0,0 +1,83 @@
+use aoc2023rs::{Answer, Solution};
+use num_integer::lcm;
+use std::collections::{HashMap, VecDeque};
+
+struct Network<'a> {
+    modules: HashMap<&'a str, (u8, Vec<&'a str>)>,
+    // The state of each flip-flop, and the last pulse from each input of each conjunction.
+    on: HashMap<&'a str, bool>,
+    memory: HashMap<&'a str, HashMap<&'a str, bool>>,
+}
+
+impl<'a> Network<'a> {
+    fn new(input: &'a str) -> Network<'a> {
+        let mut modules: HashMap<&str, (u8, Vec<&str>)> = HashMap::new();
+        for line in input.lines() {
+            let (name, outs) = line.split_once(" -> ").unwrap();
+            let (kind, name) = match name.as_bytes()[0] {
+                b'%' | b'&' => (name.as_bytes()[0], &name[1..]),
+                _ => (b'b', name),
+            };
+            modules.insert(name, (kind, outs.split(", ").collect()));
+        }
+        let mut memory: HashMap<&str, HashMap<&str, bool>> = HashMap::new();
+        for (name, (_, outs)) in &modules {
+            for out in outs { memory.entry(out).or_default().insert(name, false); }
+        }
+        return Network{modules, on: HashMap::new(), memory};
+    }
+
+    // Presses the button and returns the number of low and high pulses sent, and the modules which
+    // sent a high pulse to `watch`.
+    fn press(&mut self, watch: &str) -> (u64, u64, Vec<&'a str>) {
+        let (mut low, mut high, mut senders) = (0, 0, Vec::new());
+        let mut queue = VecDeque::from([("button", "broadcaster", false)]);
+        while let Some((from, to, pulse)) = queue.pop_front() {
+            if pulse { high += 1 } else { low += 1 }
+            if pulse && to == watch { senders.push(from) }
+            let Some((kind, outs)) = self.modules.get(to) else { continue };
+            let out = match kind {
+                b'%' => {
+                    if pulse { continue }
+                    let on = self.on.entry(to).or_default();
+                    *on = !*on;
+                    *on
+                }
+                b'&' => {
+                    let memory = self.memory.get_mut(to).unwrap();
+                    memory.insert(from, pulse);
+                    !memory.values().all(|p| *p)
+                }
+                _ => pulse,
+            };
+            for next in outs { queue.push_back((to, next, out)) }
+        }
+        return (low, high, senders);
+    }
+}
+
+pub fn solve(input: &str) -> Solution {
+    let mut network = Network::new(input);
+    let (mut low, mut high) = (0, 0);
+    for _ in 0..1000 {
+        let (l, h, _) = network.press("");
+        low += l;
+        high += h;
+    }
+    // Pressing the button until `rx` gets a low pulse would take far too long. It gets one when
+    // every input of the conjunction in front of it has most recently sent a high pulse, and each
+    // of those fires on a fixed cycle, so find the first press at which each one fires.
+    let mut network = Network::new(input);
+    let last = *network.modules.iter().find(|(_, (_, outs))| outs == &["rx"]).unwrap().0;
+    let mut first = network.memory[last].keys().map(|input| (*input, 0)).collect::<HashMap<_, _>>();
+    let mut presses: u64 = 0;
+    while first.values().any(|p| *p == 0) {
+        presses += 1;
+        for sender in network.press(last).2 {
+            let p = first.get_mut(sender).unwrap();
+            if *p == 0 { *p = presses }
+        }
+    }
+    let part2 = first.values().fold(1, |x, p| lcm(x, *p));
+    return (Answer::from(low * high), Answer::from(part2)).into();
+}
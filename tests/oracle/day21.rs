@@ -0,0 +1,81 @@
+use aoc2023rs::day21::{buffer_len, count, distances, far_plots, read_input, STEPS};
+use aoc2023rs::{gen, Answer, Solution};
+use std::collections::HashSet;
+
+// Takes one step at a time from every reachable position.
+fn plots(grid: &[&[u8]], steps: usize) -> usize {
+    let size = grid.len() as isize;
+    let mut positions = HashSet::from([(size / 2, size / 2)]);
+    for _ in 0..steps {
+        let mut next = HashSet::new();
+        for (x, y) in positions {
+            for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+                if nx < 0 || ny < 0 || nx >= size || ny >= size { continue }
+                if grid[ny as usize][nx as usize] != b'#' { next.insert((nx, ny)); }
+            }
+        }
+        positions = next;
+    }
+    return positions.len();
+}
+
+// Counts the positions reachable in each of the given numbers of steps in the infinitely tiled
+// grid, using a breadth first search over enough copies of the grid. A position can be reached in
+// exactly `n` steps if it can be reached in at most `n` steps with the same parity, since we can
+// always step back and forth.
+fn tiled_plots(grid: &[&[u8]], steps: &[usize]) -> Vec<usize> {
+    let size = grid.len();
+    let most = *steps.iter().max().unwrap();
+    let tiles = 2 * (most / size + 1) + 1;
+    let width = tiles * size;
+    let mut distances = vec![usize::MAX; width * width];
+    let center = width / 2;
+    distances[center * width + center] = 0;
+    let mut queue = vec![(center, center)];
+    let mut front = 0;
+    while front < queue.len() {
+        let (x, y) = queue[front];
+        front += 1;
+        let distance = distances[y * width + x];
+        if distance == most { continue }
+        for (nx, ny) in [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)] {
+            let i = ny * width + nx;
+            if grid[ny % size][nx % size] == b'#' || distances[i] != usize::MAX { continue }
+            distances[i] = distance + 1;
+            queue.push((nx, ny));
+        }
+    }
+    return steps
+        .iter()
+        .map(|n| distances.iter().filter(|d| **d <= *n && **d % 2 == n % 2).count())
+        .collect();
+}
+
+pub fn solve(input: &str) -> Solution {
+    let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    // Simulating all 26501365 steps is out of the question. That number is an even number of tiles
+    // plus half a tile, and the number of reachable positions after `size / 2 + 2 * m * size`
+    // steps grows quadratically in `m`, so extrapolate from the first three values.
+    let size = grid.len();
+    let m = (STEPS / size / 2) as i128;
+    let counts = tiled_plots(&grid, &[size / 2, size / 2 + 2 * size, size / 2 + 4 * size]);
+    let [a, b, c] = [0, 1, 2].map(|i| counts[i] as i128);
+    let part2 = a + m * (b - a) + m * (m - 1) / 2 * (c - 2 * b + a);
+    return (Answer::from(plots(&grid, 64)), Answer::from(part2 as u64)).into();
+}
+
+#[test]
+fn fewer_steps() {
+    for seed in 0..4 {
+        let input = gen::generate(21, seed, &[]).unwrap();
+        let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+        let size = grid.len();
+        let steps: Vec<usize> = (1..=3).map(|m| size / 2 + 2 * m * size).collect();
+        let expected = tiled_plots(&grid, &steps);
+        let parsed = read_input(input.as_bytes()).unwrap();
+        let counts = count(&parsed, distances(&parsed, &mut vec![0; buffer_len(&parsed)]));
+        for (n, expected) in steps.iter().zip(expected) {
+            assert_eq!(far_plots(&counts, *n), expected, "seed {}, {} steps", seed, n);
+        }
+    }
+}
@@ -0,0 +1,57 @@
+use aoc2023rs::{Answer, Solution};
+use std::collections::HashSet;
+
+type Brick = Vec<(u32, u32, u32)>;
+
+// Returns whether the brick could move down one step without hitting the ground or another brick.
+fn can_fall(brick: &Brick, filled: &HashSet<(u32, u32, u32)>) -> bool {
+    return brick.iter().all(|(x, y, z)| {
+        *z > 1 && (brick.contains(&(*x, *y, z - 1)) || !filled.contains(&(*x, *y, z - 1)))
+    });
+}
+
+// Moves bricks down one step at a time until none of them can move, and returns the number of
+// bricks which moved.
+fn settle(bricks: &mut [Brick]) -> usize {
+    let mut filled: HashSet<(u32, u32, u32)> = bricks.iter().flatten().copied().collect();
+    let mut moved = vec![false; bricks.len()];
+    loop {
+        let mut changed = false;
+        for (i, brick) in bricks.iter_mut().enumerate() {
+            if !can_fall(brick, &filled) { continue }
+            for cube in brick.iter() { filled.remove(cube); }
+            for cube in brick.iter_mut() { cube.2 -= 1 }
+            filled.extend(brick.iter().copied());
+            moved[i] = true;
+            changed = true;
+        }
+        if !changed { break }
+    }
+    return moved.iter().filter(|m| **m).count();
+}
+
+pub fn solve(input: &str) -> Solution {
+    let mut bricks: Vec<Brick> = Vec::new();
+    for line in input.lines() {
+        let (a, b) = line.split_once('~').unwrap();
+        let a: Vec<u32> = a.split(',').map(|n| n.parse().unwrap()).collect();
+        let b: Vec<u32> = b.split(',').map(|n| n.parse().unwrap()).collect();
+        let mut brick = Vec::new();
+        for x in a[0]..=b[0] {
+            for y in a[1]..=b[1] {
+                for z in a[2]..=b[2] { brick.push((x, y, z)) }
+            }
+        }
+        bricks.push(brick);
+    }
+    settle(&mut bricks);
+    let (mut safe, mut falling) = (0, 0);
+    for i in 0..bricks.len() {
+        let mut rest = bricks.clone();
+        rest.remove(i);
+        let filled: HashSet<(u32, u32, u32)> = rest.iter().flatten().copied().collect();
+        if !rest.iter().any(|brick| can_fall(brick, &filled)) { safe += 1 }
+        falling += settle(&mut rest);
+    }
+    return (Answer::from(safe), Answer::from(falling)).into();
+}
@@ -0,0 +1,82 @@
+use aoc2023rs::{Answer, Solution};
+
+type Position = (usize, usize);
+
+// The positions which can be reached in one step from `(x, y)`, optionally obeying the slopes.
+fn moves(grid: &[&[u8]], (x, y): Position, slopes: bool) -> Vec<Position> {
+    let mut result = Vec::new();
+    for (dx, dy, slope) in [(1, 0, b'>'), (-1, 0, b'<'), (0, 1, b'v'), (0, -1, b'^')] {
+        let c = grid[y][x];
+        if slopes && c != b'.' && c != slope { continue }
+        // Stepping left from column 0 wraps around to a huge column, which is out of bounds.
+        let (nx, ny) = (x.wrapping_add_signed(dx), y.wrapping_add_signed(dy));
+        if ny >= grid.len() || nx >= grid[0].len() { continue }
+        if grid[ny][nx] != b'#' { result.push((nx, ny)) }
+    }
+    return result;
+}
+
+// Tries every path through the grid one step at a time.
+fn longest_walk(grid: &[&[u8]], visited: &mut Vec<Vec<bool>>, position: Position) -> Option<usize> {
+    if position.1 == grid.len() - 1 { return Some(0) }
+    let mut best = None;
+    visited[position.1][position.0] = true;
+    for next in moves(grid, position, true) {
+        if visited[next.1][next.0] { continue }
+        if let Some(length) = longest_walk(grid, visited, next) {
+            best = best.max(Some(length + 1));
+        }
+    }
+    visited[position.1][position.0] = false;
+    return best;
+}
+
+// Tries every path through the graph of junctions, where the edges are the corridors between them
+// and `visited` is a bit set of junctions.
+fn longest_path(edges: &[Vec<(usize, usize)>], visited: u64, junction: usize) -> Option<usize> {
+    // Junction 1 is the end.
+    if junction == 1 { return Some(0) }
+    let mut best = None;
+    for (next, length) in &edges[junction] {
+        if visited & 1 << next != 0 { continue }
+        if let Some(rest) = longest_path(edges, visited | 1 << junction, *next) {
+            best = best.max(Some(rest + length));
+        }
+    }
+    return best;
+}
+
+pub fn solve(input: &str) -> Solution {
+    let grid: Vec<&[u8]> = input.lines().map(|line| line.as_bytes()).collect();
+    let start = (1, 0);
+    let end = (grid[0].len() - 2, grid.len() - 1);
+    let mut visited = vec![vec![false; grid[0].len()]; grid.len()];
+    let part1 = longest_walk(&grid, &mut visited, start).unwrap();
+    // Walking every path one step at a time is too slow when the slopes don't matter, so follow
+    // each corridor from each junction to find the graph of junctions first.
+    let mut junctions = vec![start, end];
+    for y in 0..grid.len() {
+        for x in 0..grid[0].len() {
+            if grid[y][x] == b'#' { continue }
+            if moves(&grid, (x, y), false).len() > 2 { junctions.push((x, y)) }
+        }
+    }
+    let mut edges = Vec::new();
+    for junction in &junctions {
+        let mut corridors = Vec::new();
+        for first in moves(&grid, *junction, false) {
+            let (mut previous, mut position, mut length) = (*junction, first, 1);
+            while !junctions.contains(&position) {
+                let next = moves(&grid, position, false).into_iter().find(|p| *p != previous);
+                let Some(next) = next else { break };
+                (previous, position, length) = (position, next, length + 1);
+            }
+            if let Some(i) = junctions.iter().position(|j| *j == position) {
+                corridors.push((i, length));
+            }
+        }
+        edges.push(corridors);
+    }
+    let part2 = longest_path(&edges, 1, 0).unwrap();
+    return (Answer::from(part1), Answer::from(part2)).into();
+}
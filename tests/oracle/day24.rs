@@ -0,0 +1,85 @@
+use aoc2023rs::{Answer, Solution};
+
+type Hailstone = ([i128; 3], [i128; 3]);
+
+// Counts crossings in the test area using exact integer arithmetic.
+fn crossings(hailstones: &[Hailstone], min: i128, max: i128) -> usize {
+    let mut total = 0;
+    for (i, (a, va)) in hailstones.iter().enumerate() {
+        for (b, vb) in &hailstones[i + 1..] {
+            // Solve `a + va * ta = b + vb * tb` in x and y, with the times as fractions over `det`.
+            let mut det = va[1] * vb[0] - va[0] * vb[1];
+            if det == 0 { continue }
+            let mut ta = -vb[1] * (b[0] - a[0]) + vb[0] * (b[1] - a[1]);
+            let mut tb = -va[1] * (b[0] - a[0]) + va[0] * (b[1] - a[1]);
+            if det < 0 { (det, ta, tb) = (-det, -ta, -tb) }
+            if ta < 0 || tb < 0 { continue }
+            let (x, y) = (a[0] * det + va[0] * ta, a[1] * det + va[1] * ta);
+            let area = min * det..=max * det;
+            if area.contains(&x) && area.contains(&y) { total += 1 }
+        }
+    }
+    return total;
+}
+
+// Returns the time at which `p + v * t = rock + velocity * t` in one dimension, if there is a
+// whole number which works.
+fn meet(p: i128, v: i128, rock: i128, velocity: i128) -> Option<Option<i128>> {
+    if v == velocity { return if p == rock { Some(None) } else { None } }
+    if (rock - p) % (v - velocity) != 0 { return None }
+    return Some(Some((rock - p) / (v - velocity)));
+}
+
+// Tries every rock velocity in x and y within the range that the generator uses, finds where the
+// rock would have to start to hit the first two hailstones, and checks that it hits all the
+// others at the same times in every dimension.
+fn throw(hailstones: &[Hailstone]) -> i128 {
+    let (a, va) = hailstones[0];
+    let (b, vb) = hailstones[1];
+    for vx in -300..=300 {
+        for vy in -300..=300 {
+            let (ua, ub) = ([va[0] - vx, va[1] - vy], [vb[0] - vx, vb[1] - vy]);
+            let det = ua[1] * ub[0] - ua[0] * ub[1];
+            if det == 0 { continue }
+            let ta = -ub[1] * (b[0] - a[0]) + ub[0] * (b[1] - a[1]);
+            let tb = -ua[1] * (b[0] - a[0]) + ua[0] * (b[1] - a[1]);
+            if ta % det != 0 || tb % det != 0 { continue }
+            let (ta, tb) = (ta / det, tb / det);
+            if ta == tb { continue }
+            let rock_x = a[0] + ua[0] * ta;
+            let rock_y = a[1] + ua[1] * ta;
+            let dz = b[2] + vb[2] * tb - a[2] - va[2] * ta;
+            if dz % (tb - ta) != 0 { continue }
+            let vz = dz / (tb - ta);
+            let rock_z = a[2] + (va[2] - vz) * ta;
+            let rock = [rock_x, rock_y, rock_z];
+            let velocity = [vx, vy, vz];
+            let hits_all = hailstones.iter().all(|(p, v)| {
+                let times: Option<Vec<Option<i128>>> =
+                    (0..3).map(|i| meet(p[i], v[i], rock[i], velocity[i])).collect();
+                let Some(times) = times else { return false };
+                let mut times = times.into_iter().flatten();
+                let Some(t) = times.next() else { return true };
+                return times.all(|u| u == t);
+            });
+            if hits_all { return rock_x + rock_y + rock_z }
+        }
+    }
+    panic!("no rock velocity found");
+}
+
+pub fn solve(input: &str) -> Solution {
+    let hailstones: Vec<Hailstone> = input
+        .lines()
+        .map(|line| {
+            let (p, v) = line.split_once(" @ ").unwrap();
+            let parse = |s: &str| -> [i128; 3] {
+                let n: Vec<i128> = s.split(", ").map(|n| n.trim().parse().unwrap()).collect();
+                return [n[0], n[1], n[2]];
+            };
+            (parse(p), parse(v))
+        })
+        .collect();
+    let part1 = crossings(&hailstones, 200000000000000, 400000000000000);
+    return (Answer::from(part1), Answer::from(throw(&hailstones) as i64)).into();
+}
@@ -0,0 +1,52 @@
+use aoc2023rs::{Answer, Solution};
+use std::collections::HashMap;
+
+// Returns the sizes of the connected components of the graph, ignoring the wires in `cut`.
+fn components(n: usize, wires: &[(usize, usize)], cut: [usize; 3]) -> Vec<usize> {
+    let mut seen = vec![false; n];
+    let mut sizes = Vec::new();
+    for start in 0..n {
+        if seen[start] { continue }
+        seen[start] = true;
+        let mut stack = vec![start];
+        let mut size = 0;
+        while let Some(node) = stack.pop() {
+            size += 1;
+            for (i, (a, b)) in wires.iter().enumerate() {
+                if cut.contains(&i) { continue }
+                let other = if *a == node { *b } else if *b == node { *a } else { continue };
+                if !seen[other] {
+                    seen[other] = true;
+                    stack.push(other);
+                }
+            }
+        }
+        sizes.push(size);
+    }
+    return sizes;
+}
+
+pub fn solve(input: &str) -> Solution {
+    let mut ids = HashMap::new();
+    let mut wires = Vec::new();
+    for line in input.lines() {
+        let (a, rest) = line.split_once(": ").unwrap();
+        for b in rest.split(' ') {
+            let n = ids.len();
+            let a = *ids.entry(a).or_insert(n);
+            let n = ids.len();
+            let b = *ids.entry(b).or_insert(n);
+            wires.push((a, b));
+        }
+    }
+    // Try cutting every combination of three wires.
+    for i in 0..wires.len() {
+        for j in i + 1..wires.len() {
+            for k in j + 1..wires.len() {
+                let sizes = components(ids.len(), &wires, [i, j, k]);
+                if sizes.len() == 2 { return Answer::from(sizes[0] * sizes[1]).into() }
+            }
+        }
+    }
+    panic!("no three wires split the graph");
+}
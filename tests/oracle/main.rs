@@ -0,0 +1,99 @@
+// Differential tests which check each day's solver against a slow but obviously correct reference
+// implementation (an "oracle"). The oracles simulate the puzzles as literally as they can, so they
+// only run on small generated inputs. Set `ORACLE_SEEDS` to try more inputs than the default.
+//
+// Some parts can't be simulated literally at their full scale, such as a billion spin cycles or
+// 26501365 steps. For those, the oracle uses the most direct method which is still feasible, and
+// the shortcut in the solver is also checked against literal simulation at smaller scales.
+
+use aoc2023rs::{gen, Solution};
+use std::env;
+use std::fmt::Write;
+
+mod day01;
+mod day02;
+mod day03;
+mod day04;
+mod day05;
+mod day06;
+mod day07;
+mod day08;
+mod day09;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
+mod day24;
+mod day25;
+
+// Generates inputs for the given day with the given parameters, solves them with both the solver
+// and the oracle, and reports every input where the two disagree.
+fn check(day: u8, seeds: u64, params: &[(&str, usize)], oracle: fn(&str) -> Solution) {
+    let seeds = match env::var("ORACLE_SEEDS") {
+        Ok(value) => value.parse().expect("ORACLE_SEEDS should be a number"),
+        Err(_) => seeds,
+    };
+    let assignments: Vec<(String, usize)> =
+        params.iter().map(|(name, value)| (name.to_string(), *value)).collect();
+    let mut report = String::new();
+    for seed in 0..seeds {
+        let input = gen::generate(day, seed, &assignments).unwrap();
+        let expected = oracle(&input);
+        let actual = match aoc2023rs::solve(day, &input) {
+            Ok(solution) => solution,
+            Err(error) => panic!("seed {}: {}", seed, error),
+        };
+        if actual == expected { continue }
+        let params: Vec<String> = params.iter().map(|(n, v)| format!(" {}={}", n, v)).collect();
+        writeln!(report, "aoc gen {} --seed {}{}", day, seed, params.concat()).unwrap();
+        writeln!(report, "  oracle: {}", expected.to_string().replace('\n', " ")).unwrap();
+        writeln!(report, "  solver: {}", actual.to_string().replace('\n', " ")).unwrap();
+    }
+    if !report.is_empty() { panic!("day{:02}: solver disagrees with oracle:\n{}", day, report) }
+}
+
+macro_rules! days {
+    ($($name:ident: $day:expr, $seeds:expr, [$($param:ident = $value:expr),*],)*) => {
+        $(#[test] fn $name() {
+            check($day, $seeds, &[$((stringify!($param), $value)),*], $name::solve)
+        })*
+    };
+}
+
+days! {
+    day01: 1, 10, [lines = 100],
+    day02: 2, 10, [games = 50],
+    day03: 3, 10, [size = 30, numbers = 200, symbols = 100],
+    day04: 4, 10, [cards = 15],
+    day05: 5, 10, [seeds = 4, stages = 7, ranges = 10],
+    day06: 6, 10, [races = 3],
+    day07: 7, 10, [hands = 100],
+    day08: 8, 10, [steps = 30, ghosts = 3, cycle = 60],
+    day09: 9, 10, [lines = 50],
+    day10: 10, 10, [size = 30, length = 300],
+    day11: 11, 10, [size = 30, galaxies = 40, empty = 5],
+    day12: 12, 10, [rows = 10, length = 3],
+    day13: 13, 10, [patterns = 20],
+    day14: 14, 10, [size = 10],
+    day15: 15, 10, [steps = 200, labels = 50],
+    day16: 16, 10, [size = 20],
+    day17: 17, 10, [size = 20],
+    day18: 18, 10, [columns = 6],
+    day19: 19, 10, [workflows = 20, rules = 2, parts = 50],
+    day20: 20, 2, [],
+    day21: 21, 2, [],
+    day22: 22, 10, [bricks = 40],
+    day23: 23, 2, [size = 115],
+    day24: 24, 5, [],
+    day25: 25, 3, [components = 20],
+}
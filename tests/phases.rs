@@ -0,0 +1,17 @@
+// Tests for phase timing, where the solvers mark the end of each phase of their work.
+
+use aoc2023rs::phases;
+
+#[test]
+fn times_each_phase() {
+    let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+    phases::set_enabled(true);
+    aoc2023rs::solve(15, input).unwrap();
+    aoc2023rs::solve(15, input).unwrap();
+    let (runs, timings) = phases::take();
+    assert_eq!(runs, 2);
+    let names: Vec<&str> = timings.iter().map(|phase| phase.name).collect();
+    assert_eq!(names, ["steps", "focusing power", "rest"]);
+    // Taking the timings starts again from nothing.
+    assert_eq!(phases::take(), (0, Vec::new()));
+}
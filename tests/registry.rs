@@ -0,0 +1,57 @@
+// Tests for the registry of verified answers, which is keyed by the SHA-256 of each input.
+
+use aoc2023rs::registry::{self, Recorded, Registry};
+use aoc2023rs::{Answer, Solution};
+
+#[test]
+fn sha256() {
+    let hex = |data: &[u8]| registry::hex(&registry::sha256(data));
+    assert_eq!(hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+    assert_eq!(hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    // 56 bytes, so that the padding needs a second block.
+    assert_eq!(hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+               "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1");
+}
+
+#[test]
+fn parse() {
+    let text = "\
+# A comment.
+day25 08ba2259c5a6d6be4a5a982ed1ad824b00cdb32c2c36978107076ec3477ef5ec 54 -
+
+day05 071c16b135eff73a39137db53b4cc0940b4b23c29d250e0a3929b4e076284bda 35 46
+";
+    let registry = Registry::parse(text).unwrap();
+    let days: Vec<u8> = registry.entries().iter().map(|entry| entry.day).collect();
+    assert_eq!(days, [5, 25]);
+    let hash = registry::sha256(b"");
+    assert_eq!(registry.lookup(5, &hash), None);
+    let hash = registry.entries()[1].hash;
+    assert_eq!(registry.lookup(25, &hash), Some(Solution::from(Answer::from(54))));
+    assert_eq!(registry.lookup(24, &hash), None);
+    assert_eq!(Registry::parse(&registry.to_string()), Ok(registry));
+
+    assert_eq!(Registry::parse("day05 abc 1 2").unwrap_err(), "line 1: not a SHA-256: \"abc\"");
+    assert_eq!(Registry::parse("day26 ").unwrap_err(),
+               "line 1: expected a day, a hash, and two answers");
+}
+
+#[test]
+fn record() {
+    let mut registry = Registry::default();
+    let hash = registry::sha256(b"input");
+    let solution = Solution::from((Answer::from(1), Answer::from(2)));
+    let other = Solution::from((Answer::from(1), Answer::from(3)));
+    assert_eq!(registry.record(7, hash, solution), Recorded::New);
+    assert_eq!(registry.record(7, hash, solution), Recorded::Unchanged);
+    assert_eq!(registry.record(7, hash, other), Recorded::Conflict(solution));
+    assert_eq!(registry.record(8, hash, other), Recorded::New);
+    assert_eq!(registry.lookup(7, &hash), Some(solution));
+    let duplicated = format!("day07 {0} 1 2\nday07 {0} 1 3\n", registry::hex(&hash));
+    assert_eq!(Registry::parse(&duplicated).unwrap_err(),
+               "line 2: input recorded twice with different answers");
+
+    assert!(registry::agrees(&solution, &solution));
+    assert!(registry::agrees(&solution, &Solution::from(Answer::from(1))));
+    assert!(!registry::agrees(&solution, &other));
+}
@@ -0,0 +1,64 @@
+// Tests for the image renderer used by the grid-based days to draw their working.
+
+use aoc2023rs::grid::Grid;
+use aoc2023rs::render::{crc32, Image, Palette};
+
+const PALETTE: Palette = Palette::new(&[(b'#', [255, 255, 255]), (b'o', [200, 100, 0])]);
+
+// Splits a PNG file into its chunks, checking the signature and every checksum.
+fn chunks(png: &[u8]) -> Vec<(String, Vec<u8>)> {
+    assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+    let mut result = Vec::new();
+    let mut rest = &png[8..];
+    while !rest.is_empty() {
+        let length = u32::from_be_bytes(rest[..4].try_into().unwrap()) as usize;
+        let (kind_and_data, tail) = rest[4..].split_at(4 + length);
+        let crc = u32::from_be_bytes(tail[..4].try_into().unwrap());
+        assert_eq!(crc32(kind_and_data), crc);
+        let kind = String::from_utf8(kind_and_data[..4].to_vec()).unwrap();
+        result.push((kind, kind_and_data[4..].to_vec()));
+        rest = &tail[4..];
+    }
+    return result;
+}
+
+#[test]
+fn from_grid() {
+    let grid = Grid::parse(b"#.\no#\n").unwrap();
+    let image = Image::from_grid(&grid, &PALETTE, 2);
+    assert_eq!((image.width, image.height), (4, 4));
+    let pixel = |x: usize, y: usize| &image.pixels[(y * 4 + x) * 3..][..3];
+    assert_eq!(pixel(1, 1), [255, 255, 255]);
+    assert_eq!(pixel(2, 0), [0, 0, 0]);
+    assert_eq!(pixel(0, 3), [200, 100, 0]);
+    assert_eq!(pixel(3, 2), [255, 255, 255]);
+}
+
+#[test]
+fn ppm() {
+    let grid = Grid::parse(b"#o\n").unwrap();
+    let mut out = Vec::new();
+    Image::from_grid(&grid, &PALETTE, 1).write_ppm(&mut out).unwrap();
+    assert_eq!(out, b"P6\n2 1\n255\n\xff\xff\xff\xc8\x64\x00");
+}
+
+#[test]
+fn png() {
+    assert_eq!(crc32(b"IEND"), 0xAE426082);
+    let grid = Grid::parse(b"#o.\n...\n").unwrap();
+    let mut out = Vec::new();
+    Image::from_grid(&grid, &PALETTE, 1).write_png(&mut out).unwrap();
+    let chunks = chunks(&out);
+    let kinds: Vec<&str> = chunks.iter().map(|(kind, _)| kind.as_str()).collect();
+    assert_eq!(kinds, ["IHDR", "IDAT", "IEND"]);
+    assert_eq!(chunks[0].1, [0, 0, 0, 3, 0, 0, 0, 2, 8, 2, 0, 0, 0]);
+    // The image data is a zlib header, a single stored block, and an Adler-32 checksum.
+    let idat = &chunks[1].1;
+    assert_eq!(idat[..3], [0x78, 0x01, 1]);
+    let length = u16::from_le_bytes([idat[3], idat[4]]) as usize;
+    assert_eq!(length, 2 * (1 + 3 * 3));
+    assert_eq!(u16::from_le_bytes([idat[5], idat[6]]), !(length as u16));
+    let data = &idat[7..7 + length];
+    assert_eq!(data[..7], [0, 255, 255, 255, 200, 100, 0]);
+    assert_eq!(idat.len(), 7 + length + 4);
+}
@@ -0,0 +1,44 @@
+// Solves generated inputs which are larger than the puzzle inputs in whichever way each solver's
+// storage depends on, to check that the storage is sized from the input rather than fixed.
+
+use aoc2023rs::gen;
+
+fn solve(day: u8, params: &[(&str, usize)]) {
+    let assignments: Vec<(String, usize)> =
+        params.iter().map(|(name, value)| (name.to_string(), *value)).collect();
+    let input = gen::generate(day, 0, &assignments).unwrap();
+    if let Err(error) = aoc2023rs::solve(day, &input) { panic!("day{:02}: {}", day, error) }
+}
+
+macro_rules! days {
+    ($($name:ident: $day:expr, [$($param:ident = $value:expr),*],)*) => {
+        $(#[test] fn $name() { solve($day, &[$((stringify!($param), $value)),*]) })*
+    };
+}
+
+days! {
+    day04: 4, [cards = 2000, winning = 40, numbers = 59],
+    day08: 8, [steps = 5000],
+    day11: 11, [galaxies = 2000],
+    day12: 12, [rows = 100, length = 30, groups = 20],
+    day15: 15, [labels = 5000],
+    day17: 17, [size = 300],
+    day19: 19, [workflows = 5000, depth = 100],
+    day22: 22, [bricks = 3000],
+    day24: 24, [hailstones = 1000],
+    day25: 25, [components = 5000],
+}
+
+// The cores take their storage from the caller, who might not size it from the input, so a buffer
+// that is too small must be reported as an error at the step which needed more space.
+#[test]
+fn too_small_buffers_are_errors() {
+    use aoc2023rs::day15::{self, Lens};
+    let input = b"rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7\n";
+    let mut lenses = [Lens::default(); 11];
+    let (part1, part2) = day15::solve_bytes(input, &mut lenses).unwrap();
+    assert_eq!((part1, part2), (1320.into(), 145.into()));
+    let mut lenses = [Lens::default(); 5];
+    let error = day15::solve_bytes(input, &mut lenses).unwrap_err();
+    assert_eq!(error.offset(input), "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,".len());
+}